@@ -1,11 +1,19 @@
 use std::sync::Arc;
 
-use actix_web::{get, web};
-use actix_web::{HttpResponse, Responder};
+use actix_web::http::header;
+use actix_web::{delete, get, post, web};
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use chrono::Utc;
 
+use crate::builds::{Acknowledgement, Build};
 use crate::engine::state::EngineState;
+use crate::utils::hash::stable_hash;
 
-use super::models::{BuildViewModel, ServerInfoModel};
+use super::models::{
+    AcknowledgementRequest, BuildDetailsViewModel, BuildStatusViewModel, BuildViewModel,
+    ServerInfoModel, ViewViewModel,
+};
+use super::query::BuildQuery;
 
 #[get("/server")]
 pub fn server_info(state: web::Data<Arc<EngineState>>) -> impl Responder {
@@ -19,18 +27,260 @@ pub fn server_info(state: web::Data<Arc<EngineState>>) -> impl Responder {
 }
 
 #[get("/builds")]
-pub fn get_builds(state: web::Data<Arc<EngineState>>) -> impl Responder {
-    // Convert to view models
-    let builds: Vec<BuildViewModel> = state
-        .builds
-        .all()
+pub fn get_builds(
+    request: HttpRequest,
+    state: web::Data<Arc<EngineState>>,
+    query: web::Query<BuildQuery>,
+) -> impl Responder {
+    to_builds_response(&request, &query, state.builds.all())
+}
+
+#[get("/builds/{id}")]
+pub fn get_build(state: web::Data<Arc<EngineState>>, id: web::Path<u64>) -> impl Responder {
+    let build = match state.builds.get(id.into_inner()) {
+        Option::Some(build) => build,
+        Option::None => return HttpResponse::NotFound().finish(),
+    };
+
+    // Serialize to JSON and return.
+    let json = serde_json::to_string(&BuildDetailsViewModel::from(&build)).unwrap();
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json)
+}
+
+#[get("/views")]
+pub fn get_views(state: web::Data<Arc<EngineState>>) -> impl Responder {
+    let builds = state.builds.all();
+    let views: Vec<ViewViewModel> = state
+        .views
         .iter()
-        .map(BuildViewModel::from)
+        .map(|view| ViewViewModel {
+            name: view.name.clone(),
+            status: BuildStatusViewModel::from(
+                &state
+                    .builds
+                    .current_status_for_view(view, &view.pull_requests),
+            ),
+            builds: builds.iter().filter(|b| view.matches(b)).count(),
+        })
         .collect();
 
     // Serialize to JSON and return.
+    let json = serde_json::to_string(&views).unwrap();
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json)
+}
+
+#[get("/views/{name}/builds")]
+pub fn get_view_builds(
+    request: HttpRequest,
+    state: web::Data<Arc<EngineState>>,
+    name: web::Path<String>,
+    query: web::Query<BuildQuery>,
+) -> impl Responder {
+    let view = match state.get_view(&name) {
+        Option::Some(view) => view,
+        Option::None => return HttpResponse::NotFound().finish(),
+    };
+
+    let builds = state
+        .builds
+        .all()
+        .into_iter()
+        .filter(|b| view.matches(b))
+        .collect();
+    to_builds_response(&request, &query, builds)
+}
+
+#[post("/builds/{partition}/ack")]
+pub fn acknowledge_build(
+    http: HttpRequest,
+    state: web::Data<Arc<EngineState>>,
+    partition: web::Path<u64>,
+    request: web::Json<AcknowledgementRequest>,
+) -> impl Responder {
+    if !is_authorized(&http, &state) {
+        return unauthorized();
+    }
+    match get_acknowledgement(request.into_inner()) {
+        Ok(acknowledgement) => to_response(state.acknowledge(*partition, acknowledgement)),
+        Err(message) => HttpResponse::BadRequest().body(message),
+    }
+}
+
+#[delete("/builds/{partition}/ack")]
+pub fn unacknowledge_build(
+    http: HttpRequest,
+    state: web::Data<Arc<EngineState>>,
+    partition: web::Path<u64>,
+) -> impl Responder {
+    if !is_authorized(&http, &state) {
+        return unauthorized();
+    }
+    to_response(state.unacknowledge(*partition))
+}
+
+#[post("/builds/{partition}/mute")]
+pub fn mute_build(
+    http: HttpRequest,
+    state: web::Data<Arc<EngineState>>,
+    partition: web::Path<u64>,
+    request: web::Json<AcknowledgementRequest>,
+) -> impl Responder {
+    if !is_authorized(&http, &state) {
+        return unauthorized();
+    }
+    match get_acknowledgement(request.into_inner()) {
+        Ok(acknowledgement) => to_response(state.mute(*partition, acknowledgement)),
+        Err(message) => HttpResponse::BadRequest().body(message),
+    }
+}
+
+#[delete("/builds/{partition}/mute")]
+pub fn unmute_build(
+    http: HttpRequest,
+    state: web::Data<Arc<EngineState>>,
+    partition: web::Path<u64>,
+) -> impl Responder {
+    if !is_authorized(&http, &state) {
+        return unauthorized();
+    }
+    to_response(state.unmute(*partition))
+}
+
+fn to_builds_response(
+    request: &HttpRequest,
+    query: &BuildQuery,
+    builds: Vec<Build>,
+) -> HttpResponse {
+    let page = match query.apply(builds) {
+        Ok(page) => page,
+        Err(message) => return HttpResponse::BadRequest().body(message),
+    };
+
+    // Convert to view models
+    let builds: Vec<BuildViewModel> = page.builds.iter().map(BuildViewModel::from).collect();
+
+    // Serialize to JSON and return, unless the
+    // client already has the same representation.
     let json = serde_json::to_string(&builds).unwrap();
+    let total = page.total.to_string();
+    let etag = format!("\"{:x}\"", stable_hash(&[&json, &total]));
+    if is_not_modified(request, &etag) {
+        return HttpResponse::NotModified()
+            .header(header::ETAG, etag)
+            .header("X-Total-Count", total)
+            .finish();
+    }
     HttpResponse::Ok()
         .content_type("application/json")
+        .header(header::ETAG, etag)
+        .header("X-Total-Count", total)
         .body(json)
 }
+
+fn is_not_modified(request: &HttpRequest, etag: &str) -> bool {
+    match request.headers().get(header::IF_NONE_MATCH) {
+        Some(value) => match value.to_str() {
+            Ok(value) => value
+                .split(',')
+                .map(|tag| tag.trim())
+                .any(|tag| tag == "*" || tag == etag || tag.trim_start_matches("W/") == etag),
+            Err(_) => false,
+        },
+        None => false,
+    }
+}
+
+fn is_authorized(request: &HttpRequest, state: &EngineState) -> bool {
+    let token = match &state.api_token {
+        Some(token) => token,
+        None => return true,
+    };
+    match request.headers().get(header::AUTHORIZATION) {
+        // Compare digests so that the time it takes doesn't
+        // reveal how much of the token was correct.
+        Some(value) => match value.to_str() {
+            Ok(value) => stable_hash(&[value]) == stable_hash(&[&format!("Bearer {}", token)]),
+            Err(_) => false,
+        },
+        None => false,
+    }
+}
+
+fn unauthorized() -> HttpResponse {
+    HttpResponse::Unauthorized()
+        .header(header::WWW_AUTHENTICATE, "Bearer")
+        .finish()
+}
+
+fn get_acknowledgement(request: AcknowledgementRequest) -> Result<Acknowledgement, &'static str> {
+    let now = Utc::now().timestamp();
+    if request.by.trim().is_empty() {
+        return Err("Who is acknowledging the build must be specified.");
+    }
+    if let Some(expires) = request.expires {
+        if expires <= now {
+            return Err("The expiry time must be in the future.");
+        }
+    }
+    Ok(Acknowledgement {
+        by: request.by,
+        note: request.note.filter(|n| !n.trim().is_empty()),
+        at: now,
+        expires_at: request.expires,
+    })
+}
+
+fn to_response(build: Option<Build>) -> HttpResponse {
+    let build = match build {
+        Option::Some(build) => build,
+        Option::None => return HttpResponse::NotFound().finish(),
+    };
+
+    // Serialize to JSON and return.
+    let json = serde_json::to_string(&BuildViewModel::from(&build)).unwrap();
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .body(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Configuration;
+    use crate::utils::text::TestVariableProvider;
+    use actix_web::test::TestRequest;
+    use test_case::test_case;
+
+    #[test_case(None, None, true ; "No token configured")]
+    #[test_case(Some("s3cr3t"), Some("Bearer s3cr3t"), true ; "Correct token")]
+    #[test_case(Some("s3cr3t"), Some("Bearer hunter2"), false ; "Wrong token")]
+    #[test_case(Some("s3cr3t"), Some("s3cr3t"), false ; "Not a bearer token")]
+    #[test_case(Some("s3cr3t"), None, false ; "Missing token")]
+    fn should_authorize_requests(token: Option<&str>, header: Option<&str>, expected: bool) {
+        // Given
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            match token {
+                Some(token) => format!(r#"{{ "apiToken": "{}", "collectors": [ ] }}"#, token),
+                None => r#"{ "collectors": [ ] }"#.to_string(),
+            },
+        )
+        .unwrap();
+        let state = EngineState::new(&config);
+        let request = match header {
+            Some(header) => TestRequest::default().header(header::AUTHORIZATION, header),
+            None => TestRequest::default(),
+        }
+        .to_http_request();
+
+        // When
+        let result = is_authorized(&request, &state);
+
+        // Then
+        assert_eq!(expected, result);
+    }
+}
@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 mod endpoints;
 mod models;
+mod query;
 
 static DEFAULT_SERVER_ADDRESS: &str = "127.0.0.1:15825";
 static DOCKER_SERVER_ADDRESS: &str = "0.0.0.0:15825";
@@ -40,9 +41,22 @@ pub fn start_and_block(
     HttpServer::new(move || {
         let app = App::new()
             .register_data(state.clone())
-            .wrap(Cors::new())
+            .wrap(
+                // Only allow other origins to read builds, so that a web page
+                // can't acknowledge or mute builds on behalf of its visitors.
+                Cors::new()
+                    .allowed_methods(vec!["GET"])
+                    .expose_headers(vec!["ETag", "X-Total-Count"]),
+            )
             .service(endpoints::server_info)
-            .service(endpoints::get_builds);
+            .service(endpoints::get_builds)
+            .service(endpoints::get_build)
+            .service(endpoints::acknowledge_build)
+            .service(endpoints::unacknowledge_build)
+            .service(endpoints::mute_build)
+            .service(endpoints::unmute_build)
+            .service(endpoints::get_views)
+            .service(endpoints::get_view_builds);
 
         if cfg!(feature = "docker") {
             // Serve static files from the ui directory.
@@ -1,6 +1,8 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::builds::{Build, BuildStatus};
+use crate::builds::{
+    Acknowledgement, Build, BuildPullRequest, BuildStage, BuildStatus, BuildTests,
+};
 
 #[derive(Serialize, Clone)]
 pub struct ServerInfoModel<'a> {
@@ -10,6 +12,7 @@ pub struct ServerInfoModel<'a> {
 #[derive(Serialize, Clone)]
 pub struct BuildViewModel {
     pub id: u64,
+    pub partition: u64,
     pub provider: String,
     pub collector: String,
     pub project: String,
@@ -23,6 +26,76 @@ pub struct BuildViewModel {
     pub finished: Option<i64>,
     pub url: String,
     pub status: BuildStatusViewModel,
+    pub origin: Option<String>,
+    pub commit: Option<String>,
+    #[serde(rename(serialize = "commitMessage"))]
+    pub commit_message: Option<String>,
+    pub author: Option<String>,
+    pub committer: Option<String>,
+    pub trigger: Option<String>,
+    pub queued: Option<i64>,
+    pub duration: Option<i64>,
+    pub tests: Option<BuildTestsViewModel>,
+    #[serde(rename(serialize = "pullRequest"))]
+    pub pull_request: Option<PullRequestViewModel>,
+    pub acknowledged: Option<AcknowledgementViewModel>,
+    pub muted: Option<AcknowledgementViewModel>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct BuildDetailsViewModel {
+    #[serde(flatten)]
+    pub build: BuildViewModel,
+    pub stages: Option<Vec<BuildStageViewModel>>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct BuildStageViewModel {
+    pub name: String,
+    pub status: BuildStatusViewModel,
+    pub started: Option<i64>,
+    pub finished: Option<i64>,
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct BuildTestsViewModel {
+    pub total: Option<u32>,
+    pub passed: Option<u32>,
+    pub failed: u32,
+    pub ignored: Option<u32>,
+    pub summary: String,
+    pub failures: Vec<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct PullRequestViewModel {
+    pub number: Option<u64>,
+    pub title: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct AcknowledgementViewModel {
+    pub by: String,
+    pub note: Option<String>,
+    pub at: i64,
+    pub expires: Option<i64>,
+}
+
+/// The body of a request to acknowledge or mute a build.
+#[derive(Deserialize)]
+pub struct AcknowledgementRequest {
+    pub by: String,
+    pub note: Option<String>,
+    pub expires: Option<i64>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ViewViewModel {
+    pub name: String,
+    pub status: BuildStatusViewModel,
+    pub builds: usize,
 }
 
 #[derive(Serialize, Clone)]
@@ -32,12 +105,16 @@ pub enum BuildStatusViewModel {
     Failed,
     Running,
     Canceled,
+    Queued,
+    Skipped,
+    Blocked,
 }
 
 impl From<&Build> for BuildViewModel {
     fn from(item: &Build) -> Self {
         BuildViewModel {
             id: item.id,
+            partition: item.partition,
             provider: format!("{:?}", item.provider),
             collector: item.collector.clone(),
             project: item.project_name.clone(),
@@ -49,6 +126,79 @@ impl From<&Build> for BuildViewModel {
             started: item.started_at,
             finished: item.finished_at,
             status: BuildStatusViewModel::from(&item.status),
+            origin: item.origin.clone(),
+            commit: item.commit.clone(),
+            commit_message: item.commit_message.clone(),
+            author: item.author.clone(),
+            committer: item.committer.clone(),
+            trigger: item.trigger.as_ref().map(|t| format!("{:?}", t)),
+            queued: item.queued_at,
+            duration: item.duration(),
+            tests: item.tests.as_ref().map(BuildTestsViewModel::from),
+            pull_request: item.pull_request.as_ref().map(PullRequestViewModel::from),
+            acknowledged: item
+                .acknowledged
+                .as_ref()
+                .map(AcknowledgementViewModel::from),
+            muted: item.muted.as_ref().map(AcknowledgementViewModel::from),
+        }
+    }
+}
+
+impl From<&Build> for BuildDetailsViewModel {
+    fn from(item: &Build) -> Self {
+        BuildDetailsViewModel {
+            build: BuildViewModel::from(item),
+            stages: item
+                .stages
+                .as_ref()
+                .map(|stages| stages.iter().map(BuildStageViewModel::from).collect()),
+        }
+    }
+}
+
+impl From<&BuildStage> for BuildStageViewModel {
+    fn from(item: &BuildStage) -> Self {
+        BuildStageViewModel {
+            name: item.name.clone(),
+            status: BuildStatusViewModel::from(&item.status),
+            started: item.started_at,
+            finished: item.finished_at,
+            url: item.url.clone(),
+        }
+    }
+}
+
+impl From<&BuildTests> for BuildTestsViewModel {
+    fn from(item: &BuildTests) -> Self {
+        BuildTestsViewModel {
+            total: item.total,
+            passed: item.passed,
+            failed: item.failed,
+            ignored: item.ignored,
+            summary: item.summary(),
+            failures: item.failures.clone(),
+        }
+    }
+}
+
+impl From<&BuildPullRequest> for PullRequestViewModel {
+    fn from(item: &BuildPullRequest) -> Self {
+        PullRequestViewModel {
+            number: item.number,
+            title: item.title.clone(),
+            url: item.url.clone(),
+        }
+    }
+}
+
+impl From<&Acknowledgement> for AcknowledgementViewModel {
+    fn from(item: &Acknowledgement) -> Self {
+        AcknowledgementViewModel {
+            by: item.by.clone(),
+            note: item.note.clone(),
+            at: item.at,
+            expires: item.expires_at,
         }
     }
 }
@@ -61,6 +211,9 @@ impl From<&BuildStatus> for BuildStatusViewModel {
             BuildStatus::Failed => BuildStatusViewModel::Failed,
             BuildStatus::Running => BuildStatusViewModel::Running,
             BuildStatus::Canceled => BuildStatusViewModel::Canceled,
+            BuildStatus::Queued => BuildStatusViewModel::Queued,
+            BuildStatus::Skipped => BuildStatusViewModel::Skipped,
+            BuildStatus::Blocked => BuildStatusViewModel::Blocked,
         }
     }
 }
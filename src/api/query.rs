@@ -0,0 +1,375 @@
+use serde::Deserialize;
+
+use crate::builds::{Build, BuildProvider, BuildStatus};
+use crate::utils::glob::Glob;
+
+/// The largest number of builds that can be requested per page.
+const MAX_LIMIT: usize = 1000;
+
+/// Query parameters used to filter, sort and paginate builds.
+///
+/// Parameters that accept several values take them as a comma
+/// separated list, and project, definition and branch are glob
+/// patterns where `*` matches any number of characters.
+///
+/// Builds are only sorted if `sort` is specified, and only
+/// paginated if `limit` is specified.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildQuery {
+    pub collector: Option<String>,
+    pub provider: Option<String>,
+    pub project: Option<String>,
+    pub definition: Option<String>,
+    pub branch: Option<String>,
+    pub status: Option<String>,
+    pub started_after: Option<i64>,
+    pub started_before: Option<i64>,
+    pub finished_after: Option<i64>,
+    pub finished_before: Option<i64>,
+    pub search: Option<String>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+/// A page of builds matching a query.
+pub struct BuildPage {
+    /// The number of builds matching the query, before pagination.
+    pub total: usize,
+    pub builds: Vec<Build>,
+}
+
+#[derive(Clone, Copy)]
+enum SortField {
+    Started,
+    Finished,
+    Project,
+    Definition,
+    Branch,
+    Status,
+}
+
+impl BuildQuery {
+    pub fn apply(&self, builds: Vec<Build>) -> Result<BuildPage, String> {
+        let collectors = get_list(&self.collector);
+        let providers = get_values(&self.provider, "provider", BuildProvider::from_name)?;
+        let statuses = get_values(&self.status, "status", BuildStatus::from_name)?;
+        let projects = get_globs(&self.project);
+        let definitions = get_globs(&self.definition);
+        let branches = get_globs(&self.branch);
+        let search = self.search.as_ref().map(|s| s.to_lowercase());
+        let sort = self.get_sort_field()?;
+        let descending = self.is_descending()?;
+        if let Some(limit) = self.limit {
+            if limit == 0 || limit > MAX_LIMIT {
+                return Err(format!("The limit must be between 1 and {}.", MAX_LIMIT));
+            }
+        }
+
+        let mut builds: Vec<Build> = builds
+            .into_iter()
+            .filter(|b| is_match(&collectors, |c| c == &b.collector))
+            .filter(|b| is_match(&providers, |p| p == &b.provider))
+            .filter(|b| is_match(&statuses, |s| s == &b.status))
+            .filter(|b| is_match(&projects, |g| g.is_match(&b.project_name)))
+            .filter(|b| is_match(&definitions, |g| g.is_match(&b.definition_name)))
+            .filter(|b| is_match(&branches, |g| g.is_match(&b.branch)))
+            .filter(|b| self.is_in_time_range(b))
+            .filter(|b| match &search {
+                Some(text) => contains_text(b, text),
+                None => true,
+            })
+            .collect();
+
+        if let Some(sort) = sort {
+            builds.sort_by(|a, b| {
+                let ordering = match sort {
+                    SortField::Started => a.started_at.cmp(&b.started_at),
+                    SortField::Finished => a.finished_at.cmp(&b.finished_at),
+                    SortField::Project => a.project_name.cmp(&b.project_name),
+                    SortField::Definition => a.definition_name.cmp(&b.definition_name),
+                    SortField::Branch => a.branch.cmp(&b.branch),
+                    SortField::Status => format!("{:?}", a.status).cmp(&format!("{:?}", b.status)),
+                }
+                // Fall back to the build id so that pages are stable.
+                .then_with(|| a.id.cmp(&b.id));
+                if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+
+        let total = builds.len();
+        let builds = builds
+            .into_iter()
+            .skip(self.offset.unwrap_or(0))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+
+        Ok(BuildPage { total, builds })
+    }
+
+    /// Gets the field to sort by, or `None` if the
+    /// builds should be kept in the order they were added.
+    fn get_sort_field(&self) -> Result<Option<SortField>, String> {
+        match self.sort.as_ref().map(|s| &s[..]) {
+            None => Ok(None),
+            Some("started") => Ok(Some(SortField::Started)),
+            Some("finished") => Ok(Some(SortField::Finished)),
+            Some("project") => Ok(Some(SortField::Project)),
+            Some("definition") => Ok(Some(SortField::Definition)),
+            Some("branch") => Ok(Some(SortField::Branch)),
+            Some("status") => Ok(Some(SortField::Status)),
+            Some(sort) => Err(format!("Cannot sort builds by '{}'.", sort)),
+        }
+    }
+
+    fn is_descending(&self) -> Result<bool, String> {
+        // The most recent builds come first when sorting by time.
+        match self.order.as_ref().map(|o| &o[..]) {
+            None | Some("desc") => Ok(true),
+            Some("asc") => Ok(false),
+            Some(order) => Err(format!("Unknown sort order '{}'.", order)),
+        }
+    }
+
+    fn is_in_time_range(&self, build: &Build) -> bool {
+        if matches!(self.started_after, Some(after) if build.started_at < after)
+            || matches!(self.started_before, Some(before) if build.started_at > before)
+        {
+            return false;
+        }
+        if self.finished_after.is_none() && self.finished_before.is_none() {
+            return true;
+        }
+        // Builds that haven't finished yet can't match a finished range.
+        match build.finished_at {
+            Some(finished) => {
+                !matches!(self.finished_after, Some(after) if finished < after)
+                    && !matches!(self.finished_before, Some(before) if finished > before)
+            }
+            None => false,
+        }
+    }
+}
+
+fn get_list(value: &Option<String>) -> Option<Vec<String>> {
+    value.as_ref().map(|value| {
+        value
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect()
+    })
+}
+
+fn get_values<T>(
+    value: &Option<String>,
+    name: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Option<Vec<T>>, String> {
+    match get_list(value) {
+        Some(items) => {
+            let mut result = Vec::new();
+            for item in items {
+                match parse(&item) {
+                    Some(value) => result.push(value),
+                    None => return Err(format!("Unknown {} '{}'.", name, item)),
+                }
+            }
+            Ok(Some(result))
+        }
+        None => Ok(None),
+    }
+}
+
+fn get_globs(value: &Option<String>) -> Option<Vec<Glob>> {
+    get_list(value).map(|patterns| patterns.iter().map(|p| Glob::new(p)).collect())
+}
+
+/// Filters that haven't been specified match everything.
+fn is_match<T>(filter: &Option<Vec<T>>, predicate: impl Fn(&T) -> bool) -> bool {
+    match filter {
+        Some(items) => items.iter().any(predicate),
+        None => true,
+    }
+}
+
+fn contains_text(build: &Build, text: &str) -> bool {
+    let fields = [
+        Some(&build.project_name),
+        Some(&build.definition_name),
+        Some(&build.branch),
+        Some(&build.build_number),
+        build.commit.as_ref(),
+        build.commit_message.as_ref(),
+        build.author.as_ref(),
+    ];
+    fields
+        .iter()
+        .flatten()
+        .any(|field| field.to_lowercase().contains(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildBuilder;
+
+    fn get_ids(page: &BuildPage) -> Vec<&str> {
+        page.builds.iter().map(|b| &b.build_id[..]).collect()
+    }
+
+    #[test]
+    fn should_keep_original_order_by_default() {
+        let builds = vec![
+            BuildBuilder::dummy().build_id("1").unwrap(),
+            BuildBuilder::dummy().build_id("2").unwrap(),
+            BuildBuilder::dummy().build_id("3").unwrap(),
+        ];
+        let page = BuildQuery::default().apply(builds).unwrap();
+        assert_eq!(3, page.total);
+        assert_eq!(vec!["1", "2", "3"], get_ids(&page));
+    }
+
+    #[test]
+    fn should_return_most_recent_builds_first_when_sorting_by_start_time() {
+        let builds = vec![
+            BuildBuilder::dummy().build_id("1").started_at(100).unwrap(),
+            BuildBuilder::dummy().build_id("2").started_at(300).unwrap(),
+            BuildBuilder::dummy().build_id("3").started_at(500).unwrap(),
+        ];
+        let query = BuildQuery {
+            sort: Some("started".to_string()),
+            ..BuildQuery::default()
+        };
+        let page = query.apply(builds).unwrap();
+        assert_eq!(vec!["3", "2", "1"], get_ids(&page));
+    }
+
+    #[test]
+    fn should_not_paginate_without_limit() {
+        let builds = (0..MAX_LIMIT + 1)
+            .map(|id| BuildBuilder::dummy().build_id(id.to_string()).unwrap())
+            .collect();
+        let page = BuildQuery::default().apply(builds).unwrap();
+        assert_eq!(MAX_LIMIT + 1, page.builds.len());
+    }
+
+    #[test]
+    fn should_filter_builds() {
+        let builds = vec![
+            BuildBuilder::dummy()
+                .build_id("1")
+                .project_name("Duck")
+                .branch("master")
+                .status(BuildStatus::Success)
+                .unwrap(),
+            BuildBuilder::dummy()
+                .build_id("2")
+                .project_name("Duck")
+                .branch("feature/foo")
+                .status(BuildStatus::Failed)
+                .unwrap(),
+            BuildBuilder::dummy()
+                .build_id("3")
+                .project_name("Goose")
+                .branch("feature/bar")
+                .status(BuildStatus::Running)
+                .unwrap(),
+        ];
+        let query = BuildQuery {
+            project: Some("Du*".to_string()),
+            branch: Some("feature/*".to_string()),
+            status: Some("Failed,Running".to_string()),
+            ..BuildQuery::default()
+        };
+        let page = query.apply(builds).unwrap();
+        assert_eq!(vec!["2"], get_ids(&page));
+    }
+
+    #[test]
+    fn should_filter_builds_by_finished_time() {
+        let builds = vec![
+            BuildBuilder::dummy()
+                .build_id("1")
+                .finished_at(Some(200))
+                .unwrap(),
+            BuildBuilder::dummy()
+                .build_id("2")
+                .finished_at(Some(400))
+                .unwrap(),
+            BuildBuilder::dummy()
+                .build_id("3")
+                .finished_at(None)
+                .unwrap(),
+        ];
+        let query = BuildQuery {
+            finished_after: Some(150),
+            ..BuildQuery::default()
+        };
+        let page = query.apply(builds).unwrap();
+        assert_eq!(vec!["1", "2"], get_ids(&page));
+    }
+
+    #[test]
+    fn should_search_builds() {
+        let builds = vec![
+            BuildBuilder::dummy().build_id("1").unwrap(),
+            BuildBuilder::dummy().build_id("2").unwrap(),
+            BuildBuilder::dummy()
+                .build_id("3")
+                .author(Some("Patrik".to_string()))
+                .unwrap(),
+        ];
+        let query = BuildQuery {
+            search: Some("patrik".to_string()),
+            ..BuildQuery::default()
+        };
+        let page = query.apply(builds).unwrap();
+        assert_eq!(vec!["3"], get_ids(&page));
+    }
+
+    #[test]
+    fn should_sort_and_paginate_builds() {
+        let builds = vec![
+            BuildBuilder::dummy()
+                .build_id("1")
+                .branch("master")
+                .unwrap(),
+            BuildBuilder::dummy()
+                .build_id("2")
+                .branch("feature/foo")
+                .unwrap(),
+            BuildBuilder::dummy()
+                .build_id("3")
+                .branch("feature/bar")
+                .unwrap(),
+        ];
+        let query = BuildQuery {
+            sort: Some("branch".to_string()),
+            order: Some("asc".to_string()),
+            offset: Some(1),
+            limit: Some(1),
+            ..BuildQuery::default()
+        };
+        let page = query.apply(builds).unwrap();
+        assert_eq!(3, page.total);
+        assert_eq!(vec!["2"], get_ids(&page));
+    }
+
+    #[test]
+    fn should_return_error_for_unknown_status() {
+        let builds = vec![BuildBuilder::dummy().build_id("1").unwrap()];
+        let query = BuildQuery {
+            status: Some("Broken".to_string()),
+            ..BuildQuery::default()
+        };
+        let result = query.apply(builds);
+        assert_eq!("Unknown status 'Broken'.", result.err().unwrap());
+    }
+}
@@ -1,5 +1,4 @@
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use crate::utils::hash;
 
 #[derive(Clone, Builder, Debug, PartialEq, Eq)]
 #[builder(field(private), build_fn(skip), setter(into), pattern = "immutable")] // TODO: Should not be immutable
@@ -21,6 +20,167 @@ pub struct Build {
     pub url: String,
     pub started_at: i64,
     pub finished_at: Option<i64>,
+    /// The collector that originally collected the build,
+    /// if it was collected from another Duck instance.
+    pub origin: Option<String>,
+    /// The revision (commit SHA) that was built.
+    pub commit: Option<String>,
+    pub commit_message: Option<String>,
+    pub author: Option<String>,
+    pub committer: Option<String>,
+    /// The reason the build was started.
+    pub trigger: Option<BuildTrigger>,
+    pub queued_at: Option<i64>,
+    /// The stages or jobs that make up the build, if known.
+    pub stages: Option<Vec<BuildStage>>,
+    /// The test results of the build, if known.
+    pub tests: Option<BuildTests>,
+    /// The pull request the build was made for, if any.
+    pub pull_request: Option<BuildPullRequest>,
+    /// Set if someone has said they are looking into the build.
+    #[builder(setter(skip))]
+    pub acknowledged: Option<Acknowledgement>,
+    /// Set if notifications about the build have been muted.
+    #[builder(setter(skip))]
+    pub muted: Option<Acknowledgement>,
+}
+
+/// A pull request that a build was made for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildPullRequest {
+    /// The pull request number, if known.
+    pub number: Option<u64>,
+    pub title: Option<String>,
+    pub url: Option<String>,
+}
+
+impl BuildPullRequest {
+    pub fn new(number: u64) -> Self {
+        BuildPullRequest {
+            number: Some(number),
+            title: None,
+            url: None,
+        }
+    }
+
+    /// Gets the pull request from branch names such as `refs/pull/123/merge`
+    /// or `pull/123`, which is how Azure DevOps and TeamCity report them.
+    pub fn from_branch(branch: &str) -> Option<Self> {
+        let rest = branch.trim_start_matches("refs/").strip_prefix("pull/")?;
+        let number = match rest.find('/') {
+            Some(index) => match &rest[index..] {
+                "/merge" | "/head" => &rest[..index],
+                _ => return None,
+            },
+            None => rest,
+        };
+        number.parse().ok().map(BuildPullRequest::new)
+    }
+}
+
+/// An acknowledgement or mute of a build definition (partition).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Acknowledgement {
+    pub by: String,
+    pub note: Option<String>,
+    pub at: i64,
+    pub expires_at: Option<i64>,
+}
+
+impl Acknowledgement {
+    #[cfg(test)]
+    pub fn dummy(by: &str) -> Self {
+        Acknowledgement {
+            by: by.to_string(),
+            note: Some("On it".to_string()),
+            at: 1578820921,
+            expires_at: None,
+        }
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        match self.expires_at {
+            Some(expires_at) => expires_at <= now,
+            None => false,
+        }
+    }
+}
+
+/// The maximum number of failing test names kept for a build.
+pub const MAX_FAILED_TESTS: usize = 5;
+
+/// A summary of the tests that were run as part of a build.
+///
+/// Not all providers know about every number, so only
+/// the number of failed tests is required.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildTests {
+    pub total: Option<u32>,
+    pub passed: Option<u32>,
+    pub failed: u32,
+    pub ignored: Option<u32>,
+    /// The names of the first failing tests.
+    pub failures: Vec<String>,
+}
+
+impl BuildTests {
+    /// Gets a human readable summary, such as "142 passed, 3 failed".
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(passed) = self.passed {
+            parts.push(format!("{} passed", passed));
+        }
+        parts.push(format!("{} failed", self.failed));
+        if let Some(ignored) = self.ignored {
+            if ignored > 0 {
+                parts.push(format!("{} ignored", ignored));
+            }
+        }
+        parts.join(", ")
+    }
+}
+
+/// A stage or job that is part of a build.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildStage {
+    pub name: String,
+    pub status: BuildStatus,
+    pub started_at: Option<i64>,
+    pub finished_at: Option<i64>,
+    pub url: Option<String>,
+}
+
+impl BuildStage {
+    #[cfg(test)]
+    pub fn dummy(name: &str, status: BuildStatus) -> Self {
+        BuildStage {
+            name: name.to_string(),
+            status,
+            started_at: Some(1578819921),
+            finished_at: Some(1578820921),
+            url: Some(format!("https://dummy/{}", name)),
+        }
+    }
+}
+
+impl Build {
+    /// Gets the number of seconds the build took,
+    /// or `None` if the build hasn't finished yet.
+    pub fn duration(&self) -> Option<i64> {
+        self.finished_at
+            .map(|finished_at| finished_at - self.started_at)
+    }
+
+    /// Gets all stages of the build that have failed.
+    pub fn get_failed_stages(&self) -> Vec<&BuildStage> {
+        match &self.stages {
+            Option::None => Vec::new(),
+            Option::Some(stages) => stages
+                .iter()
+                .filter(|s| s.status == BuildStatus::Failed)
+                .collect(),
+        }
+    }
 }
 
 impl BuildBuilder {
@@ -76,26 +236,39 @@ impl BuildBuilder {
         let url = Clone::clone(self.url.as_ref().ok_or("Url is missing")?);
         let started_at = Clone::clone(self.started_at.as_ref().ok_or("Start time is missing")?);
         let finished_at = Clone::clone(self.finished_at.as_ref().ok_or("Finish time is missing")?);
+        let origin = Clone::clone(self.origin.as_ref().unwrap_or(&None));
+        let commit = Clone::clone(self.commit.as_ref().unwrap_or(&None));
+        let commit_message = Clone::clone(self.commit_message.as_ref().unwrap_or(&None));
+        let author = Clone::clone(self.author.as_ref().unwrap_or(&None));
+        let committer = Clone::clone(self.committer.as_ref().unwrap_or(&None));
+        let trigger = Clone::clone(self.trigger.as_ref().unwrap_or(&None));
+        let queued_at = Clone::clone(self.queued_at.as_ref().unwrap_or(&None));
+        let stages = Clone::clone(self.stages.as_ref().unwrap_or(&None));
+        let tests = Clone::clone(self.tests.as_ref().unwrap_or(&None));
+        let pull_request = Clone::clone(self.pull_request.as_ref().unwrap_or(&None));
+
+        // Generate a hash that represents the build definition
+        // (partition) of the build, not the build itself. These are
+        // used as permanent references, so they must never change.
+        let mut fields = vec![
+            provider.name(),
+            &collector[..],
+            origin.as_ref().map(|o| &o[..]).unwrap_or_default(),
+            &project_id[..],
+            &definition_id[..],
+            &branch[..],
+        ];
+        // GitHub reports pull request builds with the pull request's
+        // head branch, so they need to be kept apart from branch builds.
+        // Other providers already use a branch such as `pull/123`.
+        if provider == BuildProvider::GitHub && pull_request.is_some() {
+            fields.push("pull");
+        }
+        let partition = hash::stable_hash(&fields);
 
         // Generate a hash that represents the build.
-        let mut hasher = DefaultHasher::new();
-        provider.hash(&mut hasher);
-        collector.hash(&mut hasher);
-        project_id.hash(&mut hasher);
-        definition_id.hash(&mut hasher);
-        branch.hash(&mut hasher);
-        build_id.hash(&mut hasher);
-        let id = hasher.finish();
-
-        // Generate a hash that represents the build
-        // definition (partition) of the build, not the build itself.
-        let mut hasher = DefaultHasher::new();
-        provider.hash(&mut hasher);
-        collector.hash(&mut hasher);
-        project_id.hash(&mut hasher);
-        definition_id.hash(&mut hasher);
-        branch.hash(&mut hasher);
-        let partition = hasher.finish();
+        fields.push(&build_id[..]);
+        let id = hash::stable_hash(&fields);
 
         Ok(Build {
             id,
@@ -113,6 +286,18 @@ impl BuildBuilder {
             url,
             started_at,
             finished_at,
+            origin,
+            commit,
+            commit_message,
+            author,
+            committer,
+            trigger,
+            queued_at,
+            stages,
+            tests,
+            pull_request,
+            acknowledged: None,
+            muted: None,
         })
     }
 
@@ -128,6 +313,62 @@ pub enum BuildProvider {
     AzureDevOps,
     GitHub,
     OctopusDeploy,
+    ArgoCd,
+    Command,
+    File,
+    Duck,
+}
+
+impl BuildProvider {
+    /// Gets the name used by the HTTP API.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuildProvider::TeamCity => "TeamCity",
+            BuildProvider::AzureDevOps => "AzureDevOps",
+            BuildProvider::GitHub => "GitHub",
+            BuildProvider::OctopusDeploy => "OctopusDeploy",
+            BuildProvider::ArgoCd => "ArgoCd",
+            BuildProvider::Command => "Command",
+            BuildProvider::File => "File",
+            BuildProvider::Duck => "Duck",
+        }
+    }
+
+    /// Parses a provider from the name used by the HTTP API.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "TeamCity" => Some(BuildProvider::TeamCity),
+            "AzureDevOps" => Some(BuildProvider::AzureDevOps),
+            "GitHub" => Some(BuildProvider::GitHub),
+            "OctopusDeploy" => Some(BuildProvider::OctopusDeploy),
+            "ArgoCd" => Some(BuildProvider::ArgoCd),
+            "Command" => Some(BuildProvider::Command),
+            "File" => Some(BuildProvider::File),
+            "Duck" => Some(BuildProvider::Duck),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BuildTrigger {
+    Push,
+    PullRequest,
+    Schedule,
+    Manual,
+}
+
+impl BuildTrigger {
+    /// Parses a trigger from the name used by the HTTP API.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Push" => Some(BuildTrigger::Push),
+            "PullRequest" => Some(BuildTrigger::PullRequest),
+            "Schedule" => Some(BuildTrigger::Schedule),
+            "Manual" => Some(BuildTrigger::Manual),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -137,4 +378,127 @@ pub enum BuildStatus {
     Failed,
     Running,
     Canceled,
+    /// The build is waiting to be started.
+    Queued,
+    /// The build was skipped and never ran.
+    Skipped,
+    /// The build is waiting for an approval or manual intervention.
+    Blocked,
+}
+
+impl BuildStatus {
+    /// Parses a status from the name used by the HTTP API.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Unknown" => Some(BuildStatus::Unknown),
+            "Success" => Some(BuildStatus::Success),
+            "Failed" => Some(BuildStatus::Failed),
+            "Running" => Some(BuildStatus::Running),
+            "Canceled" => Some(BuildStatus::Canceled),
+            "Queued" => Some(BuildStatus::Queued),
+            "Skipped" => Some(BuildStatus::Skipped),
+            "Blocked" => Some(BuildStatus::Blocked),
+            _ => None,
+        }
+    }
+
+    /// Whether or not the build has yet to reach a final status.
+    pub fn is_pending(&self) -> bool {
+        matches!(
+            self,
+            BuildStatus::Running | BuildStatus::Queued | BuildStatus::Blocked
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn should_summarize_tests() {
+        let tests = BuildTests {
+            total: Some(147),
+            passed: Some(142),
+            failed: 3,
+            ignored: Some(2),
+            failures: vec![],
+        };
+        assert_eq!("142 passed, 3 failed, 2 ignored", tests.summary());
+    }
+
+    #[test]
+    fn should_only_summarize_known_numbers() {
+        let tests = BuildTests {
+            failed: 3,
+            ..Default::default()
+        };
+        assert_eq!("3 failed", tests.summary());
+    }
+
+    #[test]
+    fn should_generate_stable_ids() {
+        let build = BuildBuilder::dummy().unwrap();
+        assert_eq!(
+            hash::stable_hash(&[
+                "TeamCity",
+                "collector",
+                "",
+                "project_id",
+                "definition_id",
+                "branch"
+            ]),
+            build.partition
+        );
+        assert_eq!(
+            hash::stable_hash(&[
+                "TeamCity",
+                "collector",
+                "",
+                "project_id",
+                "definition_id",
+                "branch",
+                "foo"
+            ]),
+            build.id
+        );
+    }
+
+    #[test]
+    fn should_keep_github_pull_request_builds_apart_from_branch_builds() {
+        let branch = BuildBuilder::dummy()
+            .provider(BuildProvider::GitHub)
+            .unwrap();
+        let pull_request = BuildBuilder::dummy()
+            .provider(BuildProvider::GitHub)
+            .pull_request(Some(BuildPullRequest::new(123)))
+            .unwrap();
+        assert_ne!(branch.partition, pull_request.partition);
+        assert_ne!(branch.id, pull_request.id);
+    }
+
+    #[test]
+    fn should_not_change_ids_of_other_pull_request_builds() {
+        let branch = BuildBuilder::dummy().branch("pull/123").unwrap();
+        let pull_request = BuildBuilder::dummy()
+            .branch("pull/123")
+            .pull_request(BuildPullRequest::from_branch("pull/123"))
+            .unwrap();
+        assert_eq!(branch.partition, pull_request.partition);
+        assert_eq!(branch.id, pull_request.id);
+    }
+
+    #[test_case("refs/pull/123/merge", Some(123) ; "Merge ref")]
+    #[test_case("refs/pull/123/head", Some(123) ; "Head ref")]
+    #[test_case("pull/123", Some(123) ; "TeamCity branch")]
+    #[test_case("pull/123/foo", None ; "Unknown suffix")]
+    #[test_case("refs/heads/pull/foo", None ; "Ordinary branch")]
+    #[test_case("master", None ; "Master")]
+    fn should_get_pull_request_from_branch(branch: &str, expected: Option<u64>) {
+        assert_eq!(
+            expected,
+            BuildPullRequest::from_branch(branch).and_then(|pr| pr.number)
+        );
+    }
 }
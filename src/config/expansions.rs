@@ -62,6 +62,16 @@ mod tests {
                         }
                     ]
                 }
+            },
+            {
+                "argocd": {
+                    "id": "${ARGOCD_ID}",
+                    "serverUrl": "https://${ARGOCD_HOST}",
+                    "credentials": {
+                        "token": "${ARGOCD_TOKEN}"
+                    },
+                    "applications": [ "${ARGOCD_APPLICATION}" ]
+                }
             }
         ],
         "observers": [
@@ -145,6 +155,10 @@ mod tests {
         variables.add("OCTOPUS_PROJECT_PREFIX", "Projects");
         variables.add("OCTOPUS_ENVIRONMENT_PREFIX", "Environments");
         variables.add("OCTOPUS_API_KEY", "SECRET-API-KEY");
+        variables.add("ARGOCD_ID", "argocd");
+        variables.add("ARGOCD_HOST", "argocd.example.com");
+        variables.add("ARGOCD_TOKEN", "SECRET-TOKEN");
+        variables.add("ARGOCD_APPLICATION", "guestbook");
         variables.add("HUE_ID", "hue");
         variables.add("HUE_BRIGHTNESS", "128");
         variables.add("HUE_HOST", "192.168.1.155");
@@ -227,6 +241,20 @@ mod tests {
         assert_eq!("Environments-2", octopus.projects[0].environments[1]);
     }
 
+    #[test]
+    fn should_expand_argocd_configuration() {
+        // Given, When
+        let config = read_config!(CONFIGURATION);
+
+        // Then
+        let argocd = find_config!(config.collectors, CollectorConfiguration::ArgoCd);
+
+        assert_eq!("argocd", argocd.id);
+        assert_eq!("https://argocd.example.com", argocd.server_url);
+        assert_eq!("SECRET-TOKEN", argocd.get_token());
+        assert_eq!("guestbook", argocd.applications[0]);
+    }
+
     #[test]
     fn should_expand_hue_configuration() {
         // Given, When
@@ -315,10 +343,19 @@ mod utilities {
         }
     }
 
+    impl ArgoCdConfiguration {
+        pub fn get_token(&self) -> &str {
+            match &self.credentials {
+                ArgoCdCredentials::Token(token) => token,
+            }
+        }
+    }
+
     impl SlackConfiguration {
         pub fn get_webhook_url(&self) -> &str {
             match &self.credentials {
                 SlackCredentials::Webhook { url } => url,
+                SlackCredentials::Token { .. } => panic!("Slack credentials are not a webhook"),
             }
         }
     }
@@ -327,6 +364,9 @@ mod utilities {
         pub fn get_webhook_url(&self) -> &str {
             match &self.credentials {
                 MattermostCredentials::Webhook { url } => url,
+                MattermostCredentials::Token { .. } => {
+                    panic!("Mattermost credentials are not a webhook")
+                }
             }
         }
     }
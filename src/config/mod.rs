@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use schemars::JsonSchema;
@@ -25,6 +26,15 @@ pub struct Configuration {
     /// # Observers
     #[serde(default)]
     pub observers: Option<Vec<ObserverConfiguration>>,
+    /// # Views
+    /// Named subsets of builds that can be used by observers and the HTTP API
+    #[serde(default)]
+    pub views: Option<Vec<ViewConfiguration>>,
+    /// # API token
+    /// When set, acknowledging and muting builds through the HTTP API
+    /// requires an `Authorization: Bearer <token>` header
+    #[serde(default, rename = "apiToken")]
+    pub api_token: Option<String>,
 }
 
 pub trait Validate {
@@ -76,8 +86,13 @@ impl Configuration {
             .map(|i| match i {
                 CollectorConfiguration::TeamCity(c) => c.id.clone(),
                 CollectorConfiguration::Azure(c) => c.id.clone(),
+                CollectorConfiguration::AzureReleases(c) => c.id.clone(),
                 CollectorConfiguration::OctopusDeploy(c) => c.id.clone(),
                 CollectorConfiguration::GitHub(c) => c.id.clone(),
+                CollectorConfiguration::ArgoCd(c) => c.id.clone(),
+                CollectorConfiguration::Command(c) => c.id.clone(),
+                CollectorConfiguration::File(c) => c.id.clone(),
+                CollectorConfiguration::Duck(c) => c.id.clone(),
             })
             .collect();
         // Get all observer id:s
@@ -87,8 +102,16 @@ impl Configuration {
                 for observer in observers.iter() {
                     match observer {
                         ObserverConfiguration::Hue(c) => result.push(c.id.clone()),
+                        ObserverConfiguration::Lifx(c) => result.push(c.id.clone()),
+                        ObserverConfiguration::Wled(c) => result.push(c.id.clone()),
                         ObserverConfiguration::Slack(c) => result.push(c.id.clone()),
                         ObserverConfiguration::Mattermost(c) => result.push(c.id.clone()),
+                        ObserverConfiguration::Discord(c) => result.push(c.id.clone()),
+                        ObserverConfiguration::Email(c) => result.push(c.id.clone()),
+                        ObserverConfiguration::Webhook(c) => result.push(c.id.clone()),
+                        ObserverConfiguration::Mqtt(c) => result.push(c.id.clone()),
+                        ObserverConfiguration::Command(c) => result.push(c.id.clone()),
+                        ObserverConfiguration::Incident(c) => result.push(c.id.clone()),
                     };
                 }
             }
@@ -97,6 +120,40 @@ impl Configuration {
     }
 }
 
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ViewConfiguration {
+    /// # The view name
+    /// Used to refer to the view from observers and the HTTP API
+    pub name: String,
+    /// # The collectors to include builds from
+    #[serde(default)]
+    pub collectors: Option<Vec<String>>,
+    /// # The providers to include builds from
+    /// Such as TeamCity, AzureDevOps or GitHub
+    #[serde(default)]
+    pub providers: Option<Vec<String>>,
+    /// # Project name patterns
+    /// Glob patterns where * matches any number of characters and ? a single one
+    #[serde(default)]
+    pub projects: Option<Vec<String>>,
+    /// # Build definition name patterns
+    /// Glob patterns where * matches any number of characters and ? a single one
+    #[serde(default)]
+    pub definitions: Option<Vec<String>>,
+    /// # Branch patterns
+    /// Glob patterns where * matches any number of characters and ? a single one
+    #[serde(default)]
+    pub branches: Option<Vec<String>>,
+    /// # The build statuses to include
+    /// Such as Success, Failed or Running
+    #[serde(default)]
+    pub statuses: Option<Vec<String>>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+}
+
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct Interval(pub u32);
 impl Default for Interval {
@@ -115,6 +172,10 @@ pub enum CollectorConfiguration {
     /// Gets builds from Azure DevOps
     #[serde(rename = "azure")]
     Azure(AzureDevOpsConfiguration),
+    /// # Azure DevOps releases collector
+    /// Gets deployments from Azure DevOps release pipelines
+    #[serde(rename = "azure_releases")]
+    AzureReleases(AzureDevOpsReleasesConfiguration),
     /// # GitHub collector
     /// Gets builds from GitHub Actions
     #[serde(rename = "github")]
@@ -123,6 +184,104 @@ pub enum CollectorConfiguration {
     /// Gets deployments from Octopus Deploy
     #[serde(rename = "octopus")]
     OctopusDeploy(OctopusDeployConfiguration),
+    /// # Argo CD collector
+    /// Gets application sync and health status from Argo CD
+    #[serde(rename = "argocd")]
+    ArgoCd(ArgoCdConfiguration),
+    /// # Command collector
+    /// Gets a build result by running a local command
+    #[serde(rename = "command")]
+    Command(CommandConfiguration),
+    /// # File collector
+    /// Gets builds from JSON documents on disk
+    #[serde(rename = "file")]
+    File(FileConfiguration),
+    /// # Duck collector
+    /// Gets builds from another Duck instance
+    #[serde(rename = "duck")]
+    Duck(DuckConfiguration),
+}
+
+impl CollectorConfiguration {
+    pub fn get_id(&self) -> &str {
+        match self {
+            CollectorConfiguration::TeamCity(c) => &c.id,
+            CollectorConfiguration::Azure(c) => &c.id,
+            CollectorConfiguration::AzureReleases(c) => &c.id,
+            CollectorConfiguration::GitHub(c) => &c.id,
+            CollectorConfiguration::OctopusDeploy(c) => &c.id,
+            CollectorConfiguration::ArgoCd(c) => &c.id,
+            CollectorConfiguration::Command(c) => &c.id,
+            CollectorConfiguration::File(c) => &c.id,
+            CollectorConfiguration::Duck(c) => &c.id,
+        }
+    }
+
+    pub fn get_pull_request_mode(&self) -> PullRequestMode {
+        let mode = match self {
+            CollectorConfiguration::TeamCity(c) => &c.pull_requests,
+            CollectorConfiguration::Azure(c) => &c.pull_requests,
+            CollectorConfiguration::AzureReleases(c) => &c.pull_requests,
+            CollectorConfiguration::GitHub(c) => &c.pull_requests,
+            CollectorConfiguration::OctopusDeploy(c) => &c.pull_requests,
+            CollectorConfiguration::ArgoCd(c) => &c.pull_requests,
+            CollectorConfiguration::Command(c) => &c.pull_requests,
+            CollectorConfiguration::File(c) => &c.pull_requests,
+            CollectorConfiguration::Duck(c) => &c.pull_requests,
+        };
+        mode.clone().unwrap_or_default()
+    }
+
+    pub fn get_branch_filter(&self) -> Option<&BranchFilterConfiguration> {
+        match self {
+            CollectorConfiguration::TeamCity(c) => c.branch_filter.as_ref(),
+            CollectorConfiguration::Azure(c) => c.branch_filter.as_ref(),
+            CollectorConfiguration::AzureReleases(c) => c.branch_filter.as_ref(),
+            CollectorConfiguration::GitHub(c) => c.branch_filter.as_ref(),
+            CollectorConfiguration::OctopusDeploy(c) => c.branch_filter.as_ref(),
+            CollectorConfiguration::ArgoCd(c) => c.branch_filter.as_ref(),
+            CollectorConfiguration::Command(c) => c.branch_filter.as_ref(),
+            CollectorConfiguration::File(c) => c.branch_filter.as_ref(),
+            CollectorConfiguration::Duck(c) => c.branch_filter.as_ref(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub enum PullRequestMode {
+    /// # Include
+    /// Pull request builds are treated like any other build
+    #[serde(rename = "include")]
+    Include,
+    /// # Exclude
+    /// Pull request builds are ignored
+    #[serde(rename = "exclude")]
+    Exclude,
+    /// # Separate
+    /// Pull request builds are reported, but aggregated separately from other builds
+    #[serde(rename = "separate")]
+    Separate,
+}
+
+impl Default for PullRequestMode {
+    fn default() -> Self {
+        PullRequestMode::Include
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct BranchFilterConfiguration {
+    /// # Branch patterns to include
+    /// Glob patterns, or regular expressions between slashes such as /^release\/\d+$/
+    #[serde(default)]
+    pub include: Option<Vec<String>>,
+    /// # Branch patterns to exclude
+    /// Glob patterns, or regular expressions between slashes such as /^release\/\d+$/
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// # Remove the refs/heads/ prefix from branch names
+    #[serde(default)]
+    pub normalize: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
@@ -132,6 +291,14 @@ pub struct TeamCityConfiguration {
     /// # Determines whether or not this collector is enabled
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// # Filters the branches of the builds
+    /// Applied to the builds reported by the collector
+    #[serde(rename = "branchFilter", default)]
+    pub branch_filter: Option<BranchFilterConfiguration>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
     /// # The TeamCity server URL
     #[serde(rename = "serverUrl")]
     pub server_url: String,
@@ -165,6 +332,14 @@ pub struct AzureDevOpsConfiguration {
     /// # Determines whether or not this collector is enabled
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// # Filters the branches of the builds
+    /// Applied to the builds reported by the collector
+    #[serde(rename = "branchFilter", default)]
+    pub branch_filter: Option<BranchFilterConfiguration>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
     /// # The Azure DevOps organization
     pub organization: String,
     /// # The Azure DevOps project
@@ -189,6 +364,39 @@ pub enum AzureDevOpsCredentials {
     PersonalAccessToken(String),
 }
 
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct AzureDevOpsReleasesConfiguration {
+    /// # The Azure DevOps releases collector ID
+    pub id: String,
+    /// # Determines whether or not this collector is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # Filters the branches of the builds
+    /// Applied to the builds reported by the collector
+    #[serde(rename = "branchFilter", default)]
+    pub branch_filter: Option<BranchFilterConfiguration>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The Azure DevOps organization
+    pub organization: String,
+    /// # The Azure DevOps project
+    pub project: String,
+    /// # The Azure DevOps credentials
+    pub credentials: AzureDevOpsCredentials,
+    /// # The release definitions to include
+    pub definitions: Vec<String>,
+    /// # The environments to include
+    /// If omitted, all environments in the release definitions are included
+    #[serde(default)]
+    pub environments: Option<Vec<String>>,
+    /// # The number of recent deployments to look at per release definition
+    /// Environments without a deployment among these are not reported. Defaults to 50
+    #[serde(default)]
+    pub deployments: Option<u32>,
+}
+
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
 pub struct GitHubConfiguration {
     /// # The GitHub collector ID
@@ -196,6 +404,14 @@ pub struct GitHubConfiguration {
     /// # Determines whether or not this collector is enabled
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// # Filters the branches of the builds
+    /// Applied to the builds reported by the collector
+    #[serde(rename = "branchFilter", default)]
+    pub branch_filter: Option<BranchFilterConfiguration>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
     /// # The GitHub owner
     pub owner: String,
     /// # The GitHub repository
@@ -226,6 +442,14 @@ pub struct OctopusDeployConfiguration {
     /// # Determines whether or not this collector is enabled
     #[serde(default)]
     pub enabled: Option<bool>,
+    /// # Filters the branches of the builds
+    /// Applied to the builds reported by the collector
+    #[serde(rename = "branchFilter", default)]
+    pub branch_filter: Option<BranchFilterConfiguration>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
     /// # The Octopus Deploy server URL
     #[serde(rename = "serverUrl")]
     pub server_url: String,
@@ -252,33 +476,222 @@ pub enum OctopusDeployCredentials {
     ApiKey(String),
 }
 
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct ArgoCdConfiguration {
+    /// # The Argo CD collector ID
+    pub id: String,
+    /// # Determines whether or not this collector is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # Filters the branches of the builds
+    /// Applied to the builds reported by the collector
+    #[serde(rename = "branchFilter", default)]
+    pub branch_filter: Option<BranchFilterConfiguration>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The Argo CD server URL
+    #[serde(rename = "serverUrl")]
+    pub server_url: String,
+    /// # The Argo CD credentials
+    pub credentials: ArgoCdCredentials,
+    /// # The Argo CD applications to include
+    pub applications: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub enum ArgoCdCredentials {
+    /// # API token
+    /// Authenticate using an Argo CD API token
+    #[serde(rename = "token")]
+    Token(String),
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CommandConfiguration {
+    /// # The command collector ID
+    pub id: String,
+    /// # Determines whether or not this collector is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # Filters the branches of the builds
+    /// Applied to the builds reported by the collector
+    #[serde(rename = "branchFilter", default)]
+    pub branch_filter: Option<BranchFilterConfiguration>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The project name to display
+    #[serde(default)]
+    pub project: Option<String>,
+    /// # The build name to display
+    pub name: String,
+    /// # The executable to run
+    pub command: String,
+    /// # The arguments to pass to the executable
+    #[serde(default)]
+    pub arguments: Option<Vec<String>>,
+    /// # The working directory of the executable
+    #[serde(rename = "workingDirectory", default)]
+    pub working_directory: Option<String>,
+    /// # Additional environment variables for the executable
+    #[serde(default)]
+    pub environment: Option<HashMap<String, String>>,
+    /// # The timeout in seconds
+    /// The executable is killed and the build is considered failed if it runs longer than this
+    #[serde(default)]
+    pub timeout: Option<u32>,
+    /// # How the output of the executable is interpreted
+    #[serde(default)]
+    pub output: Option<CommandOutputFormat>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, PartialEq)]
+pub enum CommandOutputFormat {
+    /// # Exit code
+    /// An exit code of zero is a successful build, anything else is a failed build
+    #[serde(rename = "exitCode")]
+    ExitCode,
+    /// # JSON
+    /// The executable writes a JSON document describing the build to stdout
+    #[serde(rename = "json")]
+    Json,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct FileConfiguration {
+    /// # The file collector ID
+    pub id: String,
+    /// # Determines whether or not this collector is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # Filters the branches of the builds
+    /// Applied to the builds reported by the collector
+    #[serde(rename = "branchFilter", default)]
+    pub branch_filter: Option<BranchFilterConfiguration>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The files or directories to read builds from
+    /// All .json, .jsonl and .ndjson files in a directory are read
+    pub paths: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct DuckConfiguration {
+    /// # The Duck collector ID
+    pub id: String,
+    /// # Determines whether or not this collector is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # Filters the branches of the builds
+    /// Applied to the builds reported by the collector
+    #[serde(rename = "branchFilter", default)]
+    pub branch_filter: Option<BranchFilterConfiguration>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The URL of the remote Duck server
+    #[serde(rename = "serverUrl")]
+    pub server_url: String,
+    /// # The credentials for the remote Duck server
+    /// Only needed if the remote server is behind an authenticating proxy
+    #[serde(default)]
+    pub credentials: Option<DuckCredentials>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub enum DuckCredentials {
+    /// # Anonymous
+    /// Do not authenticate
+    #[serde(rename = "anonymous")]
+    Anonymous,
+    /// # Basic authentication
+    /// Authenticate using basic authentication
+    #[serde(rename = "basic")]
+    Basic {
+        /// # The username to use
+        username: String,
+        /// # The password to use
+        password: String,
+    },
+    /// # Bearer token
+    /// Authenticate using a bearer token
+    #[serde(rename = "token")]
+    Token(String),
+}
+
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
 pub enum ObserverConfiguration {
     /// # Philips Hue observer
     #[serde(rename = "hue")]
     Hue(HueConfiguration),
+    /// # LIFX observer
+    #[serde(rename = "lifx")]
+    Lifx(LifxConfiguration),
+    /// # WLED observer
+    #[serde(rename = "wled")]
+    Wled(WledConfiguration),
     /// # Slack observer
     #[serde(rename = "slack")]
     Slack(SlackConfiguration),
     /// # Mattermost observer
     #[serde(rename = "mattermost")]
     Mattermost(MattermostConfiguration),
+    /// # Discord observer
+    #[serde(rename = "discord")]
+    Discord(DiscordConfiguration),
+    /// # Email observer
+    #[serde(rename = "email")]
+    Email(EmailConfiguration),
+    /// # Webhook observer
+    #[serde(rename = "webhook")]
+    Webhook(WebhookConfiguration),
+    /// # MQTT observer
+    #[serde(rename = "mqtt")]
+    Mqtt(MqttConfiguration),
+    /// # Command observer
+    #[serde(rename = "command")]
+    Command(CommandObserverConfiguration),
+    /// # Incident observer
+    #[serde(rename = "incident")]
+    Incident(IncidentConfiguration),
 }
 
 impl ObserverConfiguration {
     pub fn get_id(&self) -> &str {
         match self {
             ObserverConfiguration::Hue(c) => &c.id,
+            ObserverConfiguration::Lifx(c) => &c.id,
+            ObserverConfiguration::Wled(c) => &c.id,
             ObserverConfiguration::Slack(c) => &c.id,
             ObserverConfiguration::Mattermost(c) => &c.id,
+            ObserverConfiguration::Discord(c) => &c.id,
+            ObserverConfiguration::Email(c) => &c.id,
+            ObserverConfiguration::Webhook(c) => &c.id,
+            ObserverConfiguration::Mqtt(c) => &c.id,
+            ObserverConfiguration::Command(c) => &c.id,
+            ObserverConfiguration::Incident(c) => &c.id,
         }
     }
 
     pub fn is_enabled(&self) -> bool {
         if let Some(enabled) = match self {
             ObserverConfiguration::Hue(c) => c.enabled,
+            ObserverConfiguration::Lifx(c) => c.enabled,
+            ObserverConfiguration::Wled(c) => c.enabled,
             ObserverConfiguration::Slack(c) => c.enabled,
             ObserverConfiguration::Mattermost(c) => c.enabled,
+            ObserverConfiguration::Discord(c) => c.enabled,
+            ObserverConfiguration::Email(c) => c.enabled,
+            ObserverConfiguration::Webhook(c) => c.enabled,
+            ObserverConfiguration::Mqtt(c) => c.enabled,
+            ObserverConfiguration::Command(c) => c.enabled,
+            ObserverConfiguration::Incident(c) => c.enabled,
         } {
             return enabled;
         }
@@ -288,10 +701,35 @@ impl ObserverConfiguration {
     pub fn get_collector_references(&self) -> Option<Vec<String>> {
         match self {
             ObserverConfiguration::Hue(c) => c.collectors.clone(),
+            ObserverConfiguration::Lifx(c) => c.collectors.clone(),
+            ObserverConfiguration::Wled(c) => c.collectors.clone(),
             ObserverConfiguration::Slack(c) => c.collectors.clone(),
             ObserverConfiguration::Mattermost(c) => c.collectors.clone(),
+            ObserverConfiguration::Discord(c) => c.collectors.clone(),
+            ObserverConfiguration::Email(c) => c.collectors.clone(),
+            ObserverConfiguration::Webhook(c) => c.collectors.clone(),
+            ObserverConfiguration::Mqtt(c) => c.collectors.clone(),
+            ObserverConfiguration::Command(c) => c.collectors.clone(),
+            ObserverConfiguration::Incident(c) => c.collectors.clone(),
         }
     }
+
+    pub fn get_view_reference(&self) -> Option<&str> {
+        let view = match self {
+            ObserverConfiguration::Hue(c) => &c.view,
+            ObserverConfiguration::Lifx(c) => &c.view,
+            ObserverConfiguration::Wled(c) => &c.view,
+            ObserverConfiguration::Slack(c) => &c.view,
+            ObserverConfiguration::Mattermost(c) => &c.view,
+            ObserverConfiguration::Discord(c) => &c.view,
+            ObserverConfiguration::Email(c) => &c.view,
+            ObserverConfiguration::Webhook(c) => &c.view,
+            ObserverConfiguration::Mqtt(c) => &c.view,
+            ObserverConfiguration::Command(c) => &c.view,
+            ObserverConfiguration::Incident(c) => &c.view,
+        };
+        view.as_ref().map(|v| &v[..])
+    }
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
@@ -304,6 +742,14 @@ pub struct HueConfiguration {
     /// # The collectors to include events from
     #[serde(default)]
     pub collectors: Option<Vec<String>>,
+    /// # The view to include events from
+    /// Can't be combined with collectors
+    #[serde(default)]
+    pub view: Option<String>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
     /// # The brightness of the lamps
     #[serde(default)]
     pub brightness: Option<u8>,
@@ -313,7 +759,243 @@ pub struct HueConfiguration {
     /// # The Philips Hue username
     pub username: String,
     /// # The lights that should be controlled by this observer
+    #[serde(default)]
     pub lights: Vec<String>,
+    /// # The light groups (rooms or zones) that should be controlled by this observer
+    #[serde(default)]
+    pub groups: Option<Vec<String>>,
+    /// # The light state to use for each build status
+    #[serde(default)]
+    pub states: Option<HueStatesConfiguration>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct HueStatesConfiguration {
+    /// # The light state when builds are successful
+    #[serde(default)]
+    pub success: Option<HueStateConfiguration>,
+    /// # The light state when builds have failed
+    #[serde(default)]
+    pub failed: Option<HueStateConfiguration>,
+    /// # The light state when builds are running
+    #[serde(default)]
+    pub running: Option<HueStateConfiguration>,
+    /// # The light state when builds were canceled
+    #[serde(default)]
+    pub canceled: Option<HueStateConfiguration>,
+    /// # The light state when the build status is unknown
+    #[serde(default)]
+    pub unknown: Option<HueStateConfiguration>,
+    /// # The light state when builds are queued
+    /// Defaults to the running state
+    #[serde(default)]
+    pub queued: Option<HueStateConfiguration>,
+    /// # The light state when builds are waiting for approval
+    #[serde(default)]
+    pub blocked: Option<HueStateConfiguration>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct HueStateConfiguration {
+    /// # The color as a RGB hex value (e.g. #00FF00)
+    #[serde(default)]
+    pub color: Option<String>,
+    /// # The brightness of the lamps
+    #[serde(default)]
+    pub brightness: Option<u8>,
+    /// # The alert mode
+    #[serde(default)]
+    pub alert: Option<HueAlert>,
+    /// # The effect mode
+    #[serde(default)]
+    pub effect: Option<HueEffect>,
+    /// # The name or ID of a scene to activate
+    /// When set, the scene is activated instead of setting the color
+    #[serde(default)]
+    pub scene: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
+pub enum HueAlert {
+    /// # No alert
+    #[serde(rename = "none")]
+    None,
+    /// # Flash once
+    #[serde(rename = "select")]
+    Select,
+    /// # Flash for 15 seconds
+    #[serde(rename = "lselect")]
+    LongSelect,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
+pub enum HueEffect {
+    /// # No effect
+    #[serde(rename = "none")]
+    None,
+    /// # Cycle through all colors
+    #[serde(rename = "colorloop")]
+    ColorLoop,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct LifxConfiguration {
+    /// # The LIFX observer ID
+    pub id: String,
+    /// # Determines whether or not this observer is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # The collectors to include events from
+    #[serde(default)]
+    pub collectors: Option<Vec<String>>,
+    /// # The view to include events from
+    /// Can't be combined with collectors
+    #[serde(default)]
+    pub view: Option<String>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The LIFX HTTP API URL
+    /// Defaults to https://api.lifx.com
+    #[serde(default)]
+    #[serde(rename = "serverUrl")]
+    pub server_url: Option<String>,
+    /// # The LIFX personal access token
+    pub token: String,
+    /// # The selector for the lights that should be controlled by this observer
+    /// Defaults to all lights
+    #[serde(default)]
+    pub selector: Option<String>,
+    /// # The brightness of the lamps
+    #[serde(default)]
+    pub brightness: Option<u8>,
+    /// # The light state to use for each build status
+    #[serde(default)]
+    pub states: Option<LifxStatesConfiguration>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct LifxStatesConfiguration {
+    /// # The light state when builds are successful
+    #[serde(default)]
+    pub success: Option<LifxStateConfiguration>,
+    /// # The light state when builds have failed
+    #[serde(default)]
+    pub failed: Option<LifxStateConfiguration>,
+    /// # The light state when builds are running
+    #[serde(default)]
+    pub running: Option<LifxStateConfiguration>,
+    /// # The light state when builds were canceled
+    #[serde(default)]
+    pub canceled: Option<LifxStateConfiguration>,
+    /// # The light state when the build status is unknown
+    #[serde(default)]
+    pub unknown: Option<LifxStateConfiguration>,
+    /// # The light state when builds are queued
+    /// Defaults to the running state
+    #[serde(default)]
+    pub queued: Option<LifxStateConfiguration>,
+    /// # The light state when builds are waiting for approval
+    #[serde(default)]
+    pub blocked: Option<LifxStateConfiguration>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct LifxStateConfiguration {
+    /// # The color as a RGB hex value (e.g. #00FF00)
+    #[serde(default)]
+    pub color: Option<String>,
+    /// # The brightness of the lamps
+    #[serde(default)]
+    pub brightness: Option<u8>,
+    /// # The effect
+    #[serde(default)]
+    pub effect: Option<LifxEffect>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq)]
+pub enum LifxEffect {
+    /// # No effect
+    #[serde(rename = "none")]
+    None,
+    /// # Slowly fade the color in and out
+    #[serde(rename = "breathe")]
+    Breathe,
+    /// # Quickly flash the color
+    #[serde(rename = "pulse")]
+    Pulse,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct WledConfiguration {
+    /// # The WLED observer ID
+    pub id: String,
+    /// # Determines whether or not this observer is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # The collectors to include events from
+    #[serde(default)]
+    pub collectors: Option<Vec<String>>,
+    /// # The view to include events from
+    /// Can't be combined with collectors
+    #[serde(default)]
+    pub view: Option<String>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The URL of the WLED device
+    pub url: String,
+    /// # The brightness of the LEDs
+    #[serde(default)]
+    pub brightness: Option<u8>,
+    /// # The light state to use for each build status
+    #[serde(default)]
+    pub states: Option<WledStatesConfiguration>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct WledStatesConfiguration {
+    /// # The light state when builds are successful
+    #[serde(default)]
+    pub success: Option<WledStateConfiguration>,
+    /// # The light state when builds have failed
+    #[serde(default)]
+    pub failed: Option<WledStateConfiguration>,
+    /// # The light state when builds are running
+    #[serde(default)]
+    pub running: Option<WledStateConfiguration>,
+    /// # The light state when builds were canceled
+    #[serde(default)]
+    pub canceled: Option<WledStateConfiguration>,
+    /// # The light state when the build status is unknown
+    #[serde(default)]
+    pub unknown: Option<WledStateConfiguration>,
+    /// # The light state when builds are queued
+    /// Defaults to the running state
+    #[serde(default)]
+    pub queued: Option<WledStateConfiguration>,
+    /// # The light state when builds are waiting for approval
+    #[serde(default)]
+    pub blocked: Option<WledStateConfiguration>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct WledStateConfiguration {
+    /// # The color as a RGB hex value (e.g. #00FF00)
+    #[serde(default)]
+    pub color: Option<String>,
+    /// # The brightness of the LEDs
+    #[serde(default)]
+    pub brightness: Option<u8>,
+    /// # The WLED effect ID
+    /// Defaults to 0 (solid)
+    #[serde(default)]
+    pub effect: Option<u8>,
+    /// # The effect speed
+    #[serde(default)]
+    pub speed: Option<u8>,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
@@ -326,9 +1008,18 @@ pub struct SlackConfiguration {
     /// # The collectors to include events from
     #[serde(default)]
     pub collectors: Option<Vec<String>>,
+    /// # The view to include events from
+    /// Can't be combined with collectors
+    #[serde(default)]
+    pub view: Option<String>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
     /// # The Slack credentials
     pub credentials: SlackCredentials,
     /// # The Slack channel to send messages to
+    /// Required when using a token
     #[serde(default)]
     pub channel: Option<String>,
 }
@@ -339,6 +1030,11 @@ pub enum SlackCredentials {
     /// Send messages directly to a webhook
     #[serde(rename = "webhook")]
     Webhook { url: String },
+    /// # Token
+    /// Send messages using the Slack Web API and a bot token.
+    /// Messages about failed builds are updated when the build is fixed.
+    #[serde(rename = "token")]
+    Token { token: String },
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Clone)]
@@ -350,7 +1046,16 @@ pub struct MattermostConfiguration {
     /// # The collectors to include events from
     #[serde(default)]
     pub collectors: Option<Vec<String>>,
+    /// # The view to include events from
+    /// Can't be combined with collectors
+    #[serde(default)]
+    pub view: Option<String>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
     /// # The Mattermost channel to send messages to
+    /// Must be a channel ID when using a token
     #[serde(default)]
     pub channel: Option<String>,
     /// # The Mattermost credentials
@@ -363,4 +1068,352 @@ pub enum MattermostCredentials {
     /// Send messages directly to a webhook
     #[serde(rename = "webhook")]
     Webhook { url: String },
+    /// # Token
+    /// Post messages using the Mattermost REST API and a bot or personal access token.
+    /// Posts about failed builds are updated when the build is fixed.
+    #[serde(rename = "token")]
+    Token {
+        /// # The Mattermost server URL
+        url: String,
+        /// # The access token
+        token: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct DiscordConfiguration {
+    /// # The Discord observer ID
+    pub id: String,
+    /// # Determines whether or not this observer is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # The collectors to include events from
+    #[serde(default)]
+    pub collectors: Option<Vec<String>>,
+    /// # The view to include events from
+    /// Can't be combined with collectors
+    #[serde(default)]
+    pub view: Option<String>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The Discord credentials
+    pub credentials: DiscordCredentials,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub enum DiscordCredentials {
+    /// # Webhook
+    /// Send messages directly to a webhook
+    #[serde(rename = "webhook")]
+    Webhook { url: String },
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct EmailConfiguration {
+    /// # The email observer ID
+    pub id: String,
+    /// # Determines whether or not this observer is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # The collectors to include events from
+    #[serde(default)]
+    pub collectors: Option<Vec<String>>,
+    /// # The view to include events from
+    /// Can't be combined with collectors
+    #[serde(default)]
+    pub view: Option<String>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The SMTP server
+    pub server: SmtpServerConfiguration,
+    /// # The address to send emails from
+    pub from: String,
+    /// # The addresses to send emails to
+    /// Used for builds from collectors without their own recipients
+    #[serde(default)]
+    pub to: Option<Vec<String>>,
+    /// # Recipients per collector
+    /// Maps a collector ID to the addresses that should receive emails for it
+    #[serde(default)]
+    pub recipients: Option<HashMap<String, Vec<String>>>,
+    /// # The number of seconds to wait for more changes before sending an email
+    /// Defaults to 30 seconds
+    #[serde(default)]
+    pub window: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct SmtpServerConfiguration {
+    /// # The SMTP server host
+    pub host: String,
+    /// # The SMTP server port
+    /// Defaults to 587 for STARTTLS, 465 for TLS and 25 otherwise
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// # The connection security
+    /// Defaults to STARTTLS
+    #[serde(default)]
+    pub security: Option<SmtpSecurity>,
+    /// # The SMTP credentials
+    #[serde(default)]
+    pub credentials: Option<SmtpCredentials>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, PartialEq)]
+pub enum SmtpSecurity {
+    /// # None
+    /// Do not encrypt the connection
+    #[serde(rename = "none")]
+    None,
+    /// # STARTTLS
+    /// Upgrade the connection using STARTTLS
+    #[serde(rename = "starttls")]
+    StartTls,
+    /// # TLS
+    /// Connect using TLS
+    #[serde(rename = "tls")]
+    Tls,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct SmtpCredentials {
+    /// # The username to use
+    pub username: String,
+    /// # The password to use
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct WebhookConfiguration {
+    /// # The webhook observer ID
+    pub id: String,
+    /// # Determines whether or not this observer is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # The collectors to include events from
+    #[serde(default)]
+    pub collectors: Option<Vec<String>>,
+    /// # The view to include events from
+    /// Can't be combined with collectors
+    #[serde(default)]
+    pub view: Option<String>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The URL to post events to
+    pub url: String,
+    /// # Additional headers to send with every request
+    #[serde(default)]
+    pub headers: Option<HashMap<String, String>>,
+    /// # The secret used to sign payloads
+    /// The HMAC-SHA256 signature is sent in the X-Duck-Signature header
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// # The events to send
+    /// Defaults to all events
+    #[serde(default)]
+    pub events: Option<Vec<WebhookEvent>>,
+    /// # The number of times to retry a failed request
+    /// Defaults to 3
+    #[serde(default)]
+    pub retries: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WebhookEvent {
+    /// # The overall status changed
+    #[serde(rename = "duckStatusChanged")]
+    DuckStatusChanged,
+    /// # A build was updated
+    #[serde(rename = "buildUpdated")]
+    BuildUpdated,
+    /// # A build went from success to failed or vice versa
+    #[serde(rename = "buildStatusChanged")]
+    BuildStatusChanged,
+    /// # Someone is looking into a build
+    #[serde(rename = "buildAcknowledged")]
+    BuildAcknowledged,
+    /// # Duck is shutting down
+    #[serde(rename = "shuttingDown")]
+    ShuttingDown,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct MqttConfiguration {
+    /// # The MQTT observer ID
+    pub id: String,
+    /// # Determines whether or not this observer is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # The collectors to include events from
+    #[serde(default)]
+    pub collectors: Option<Vec<String>>,
+    /// # The view to include events from
+    /// Can't be combined with collectors
+    #[serde(default)]
+    pub view: Option<String>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The MQTT broker
+    pub broker: MqttBrokerConfiguration,
+    /// # The topics to publish to
+    #[serde(default)]
+    pub topics: Option<MqttTopicsConfiguration>,
+    /// # The quality of service (0, 1 or 2)
+    /// Defaults to 1
+    #[serde(default)]
+    pub qos: Option<u8>,
+    /// # Determines whether or not messages should be retained by the broker
+    /// Defaults to true
+    #[serde(default)]
+    pub retain: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct MqttBrokerConfiguration {
+    /// # The MQTT broker host
+    pub host: String,
+    /// # The MQTT broker port
+    /// Defaults to 1883, or 8883 when using TLS
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// # Determines whether or not to connect using TLS
+    #[serde(default)]
+    pub tls: Option<bool>,
+    /// # The client ID to use
+    /// Defaults to duck-<observer id>
+    #[serde(default)]
+    #[serde(rename = "clientId")]
+    pub client_id: Option<String>,
+    /// # The MQTT credentials
+    #[serde(default)]
+    pub credentials: Option<MqttCredentials>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct MqttCredentials {
+    /// # The username to use
+    pub username: String,
+    /// # The password to use
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct MqttTopicsConfiguration {
+    /// # The topic to publish the overall status to
+    /// Defaults to duck/status
+    #[serde(default)]
+    pub status: Option<String>,
+    /// # The topic to publish build status changes to
+    /// Supports {collector}, {project}, {definition} and {branch}.
+    /// Defaults to duck/builds/{collector}/{project}/{definition}/{branch}
+    #[serde(default)]
+    pub builds: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CommandObserverConfiguration {
+    /// # The command observer ID
+    pub id: String,
+    /// # Determines whether or not this observer is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # The collectors to include events from
+    #[serde(default)]
+    pub collectors: Option<Vec<String>>,
+    /// # The view to include events from
+    /// Can't be combined with collectors
+    #[serde(default)]
+    pub view: Option<String>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The executable to run
+    /// The event is written as JSON to stdin, using the same format as webhook payloads
+    pub command: String,
+    /// # The arguments to pass to the executable
+    #[serde(default)]
+    pub arguments: Option<Vec<String>>,
+    /// # The working directory of the executable
+    #[serde(rename = "workingDirectory", default)]
+    pub working_directory: Option<String>,
+    /// # Additional environment variables for the executable
+    #[serde(default)]
+    pub environment: Option<HashMap<String, String>>,
+    /// # The timeout in seconds
+    /// The executable is killed if it runs longer than this. Defaults to 30
+    #[serde(default)]
+    pub timeout: Option<u32>,
+    /// # The maximum number of executables running at the same time
+    /// Defaults to 1
+    #[serde(default)]
+    pub concurrency: Option<u32>,
+    /// # The events to run the executable for
+    /// Defaults to all events
+    #[serde(default)]
+    pub events: Option<Vec<WebhookEvent>>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub struct IncidentConfiguration {
+    /// # The incident observer ID
+    pub id: String,
+    /// # Determines whether or not this observer is enabled
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// # The collectors to include events from
+    #[serde(default)]
+    pub collectors: Option<Vec<String>>,
+    /// # The view to include events from
+    /// Can't be combined with collectors
+    #[serde(default)]
+    pub view: Option<String>,
+    /// # How pull request builds are handled
+    /// Defaults to include
+    #[serde(rename = "pullRequests", default)]
+    pub pull_requests: Option<PullRequestMode>,
+    /// # The build definitions to open incidents for
+    /// Defaults to all definitions
+    #[serde(default)]
+    pub definitions: Option<Vec<String>>,
+    /// # The incident management service
+    pub service: IncidentService,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
+pub enum IncidentService {
+    /// # PagerDuty
+    /// Trigger and resolve incidents using the PagerDuty Events API v2
+    #[serde(rename = "pagerduty")]
+    PagerDuty {
+        /// # The integration (routing) key
+        #[serde(rename = "routingKey")]
+        routing_key: String,
+        /// # The Events API URL
+        /// Defaults to https://events.pagerduty.com
+        #[serde(default)]
+        #[serde(rename = "serverUrl")]
+        server_url: Option<String>,
+    },
+    /// # Opsgenie
+    /// Create and close alerts using the Opsgenie Alert API
+    #[serde(rename = "opsgenie")]
+    Opsgenie {
+        /// # The API key
+        #[serde(rename = "apiKey")]
+        api_key: String,
+        /// # The Opsgenie API URL
+        /// Defaults to https://api.opsgenie.com (use https://api.eu.opsgenie.com for EU accounts)
+        #[serde(default)]
+        #[serde(rename = "serverUrl")]
+        server_url: Option<String>,
+    },
 }
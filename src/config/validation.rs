@@ -1,9 +1,11 @@
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use log::warn;
 
 use super::{CollectorConfiguration, Configuration, Validate};
+use crate::builds::{BuildProvider, BuildStatus};
+use crate::providers::collectors::branches::BranchPattern;
 use crate::utils::DuckResult;
 
 impl Validate for Configuration {
@@ -14,6 +16,9 @@ impl Validate for Configuration {
 
         validate_ids(&self)?;
         validate_collector_references(&self)?;
+        validate_views(self)?;
+        validate_branch_filters(self)?;
+        validate_api_token(self)?;
 
         Ok(())
     }
@@ -37,7 +42,142 @@ fn validate_ids(configuration: &Configuration) -> DuckResult<()> {
 }
 
 fn validate_collector_references(configuration: &Configuration) -> DuckResult<()> {
-    // Build a list of all collectors and whether or not they are enabled.
+    let collectors = get_collectors(configuration);
+
+    // Validate referenced collectors.
+    if let Some(observers) = &configuration.observers {
+        for observer in observers.iter() {
+            if let Some(references) = observer.get_collector_references() {
+                for reference in references {
+                    if !collectors.contains_key(&reference) {
+                        // The referenced collector does not exist.
+                        return Err(format_err!(
+                            "The observer '{}' is dependent on collector '{}' which do not exist.",
+                            observer.get_id(),
+                            reference
+                        ));
+                    } else if observer.is_enabled() {
+                        // Is the referenced collector disabled?
+                        // This is not an error, but we should want about it.
+                        if let Some(enabled) = collectors.get(&reference) {
+                            if !enabled {
+                                warn!(
+                                    "The observer '{}' is dependent on disabled collector '{}'.",
+                                    observer.get_id(),
+                                    reference
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_views(configuration: &Configuration) -> DuckResult<()> {
+    let collectors = get_collectors(configuration);
+    let views = configuration.views.clone().unwrap_or_default();
+
+    let mut names = HashSet::<&str>::new();
+    let valid_name_pattern = Regex::new(r"^[a-zA-Z0-9_]+$")?;
+    for view in views.iter() {
+        if !valid_name_pattern.is_match(&view.name) {
+            return Err(format_err!("The view name '{}' is invalid.", view.name));
+        }
+        if !names.insert(&view.name) {
+            return Err(format_err!(
+                "Found duplicate view '{}' in configuration.",
+                view.name
+            ));
+        }
+        for collector in view.collectors.iter().flatten() {
+            if !collectors.contains_key(collector) {
+                return Err(format_err!(
+                    "The view '{}' is dependent on collector '{}' which do not exist.",
+                    view.name,
+                    collector
+                ));
+            }
+        }
+        for provider in view.providers.iter().flatten() {
+            if BuildProvider::from_name(provider).is_none() {
+                return Err(format_err!(
+                    "The view '{}' contains unknown provider '{}'.",
+                    view.name,
+                    provider
+                ));
+            }
+        }
+        for status in view.statuses.iter().flatten() {
+            if BuildStatus::from_name(status).is_none() {
+                return Err(format_err!(
+                    "The view '{}' contains unknown status '{}'.",
+                    view.name,
+                    status
+                ));
+            }
+        }
+    }
+
+    // Validate referenced views.
+    if let Some(observers) = &configuration.observers {
+        for observer in observers.iter() {
+            if let Some(view) = observer.get_view_reference() {
+                if observer.get_collector_references().is_some() {
+                    return Err(format_err!(
+                        "The observer '{}' can't use both a view and collectors.",
+                        observer.get_id()
+                    ));
+                }
+                if !names.contains(view) {
+                    return Err(format_err!(
+                        "The observer '{}' is dependent on view '{}' which do not exist.",
+                        observer.get_id(),
+                        view
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_branch_filters(configuration: &Configuration) -> DuckResult<()> {
+    for collector in configuration.collectors.iter() {
+        if let Some(filter) = collector.get_branch_filter() {
+            let patterns = filter.include.iter().chain(filter.exclude.iter()).flatten();
+            for pattern in patterns {
+                if let Err(e) = BranchPattern::parse(pattern) {
+                    return Err(format_err!(
+                        "The collector '{}' has an invalid branch pattern '{}'. {}",
+                        collector.get_id(),
+                        pattern,
+                        e
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_api_token(configuration: &Configuration) -> DuckResult<()> {
+    if let Some(token) = &configuration.api_token {
+        if token.trim().is_empty() {
+            return Err(format_err!("The API token cannot be empty."));
+        }
+    }
+
+    Ok(())
+}
+
+/// Gets all collectors and whether or not they are enabled.
+fn get_collectors(configuration: &Configuration) -> HashMap<String, bool> {
     let mut collectors: HashMap<String, bool> = HashMap::new();
     for collector in configuration.collectors.iter() {
         match collector {
@@ -59,6 +199,15 @@ fn validate_collector_references(configuration: &Configuration) -> DuckResult<()
                     },
                 );
             }
+            CollectorConfiguration::AzureReleases(c) => {
+                collectors.insert(
+                    c.id.clone(),
+                    match c.enabled {
+                        None => true,
+                        Some(enabled) => enabled,
+                    },
+                );
+            }
             CollectorConfiguration::GitHub(c) => {
                 collectors.insert(
                     c.id.clone(),
@@ -77,40 +226,46 @@ fn validate_collector_references(configuration: &Configuration) -> DuckResult<()
                     },
                 );
             }
-        }
-    }
-
-    // Validate referenced collectors.
-    if let Some(observers) = &configuration.observers {
-        for observer in observers.iter() {
-            if let Some(references) = observer.get_collector_references() {
-                for reference in references {
-                    if !collectors.contains_key(&reference) {
-                        // The referenced collector does not exist.
-                        return Err(format_err!(
-                            "The observer '{}' is dependent on collector '{}' which do not exist.",
-                            observer.get_id(),
-                            reference
-                        ));
-                    } else if observer.is_enabled() {
-                        // Is the referenced collector disabled?
-                        // This is not an error, but we should want about it.
-                        if let Some(enabled) = collectors.get(&reference) {
-                            if !enabled {
-                                warn!(
-                                    "The observer '{}' is dependent on disabled collector '{}'.",
-                                    observer.get_id(),
-                                    reference
-                                );
-                            }
-                        }
-                    }
-                }
+            CollectorConfiguration::ArgoCd(c) => {
+                collectors.insert(
+                    c.id.clone(),
+                    match c.enabled {
+                        None => true,
+                        Some(enabled) => enabled,
+                    },
+                );
+            }
+            CollectorConfiguration::Command(c) => {
+                collectors.insert(
+                    c.id.clone(),
+                    match c.enabled {
+                        None => true,
+                        Some(enabled) => enabled,
+                    },
+                );
+            }
+            CollectorConfiguration::File(c) => {
+                collectors.insert(
+                    c.id.clone(),
+                    match c.enabled {
+                        None => true,
+                        Some(enabled) => enabled,
+                    },
+                );
+            }
+            CollectorConfiguration::Duck(c) => {
+                collectors.insert(
+                    c.id.clone(),
+                    match c.enabled {
+                        None => true,
+                        Some(enabled) => enabled,
+                    },
+                );
             }
         }
     }
 
-    Ok(())
+    collectors
 }
 
 #[cfg(test)]
@@ -220,4 +375,163 @@ mod tests {
         .unwrap();
         config.validate().unwrap();
     }
+
+    #[test]
+    #[should_panic(
+        expected = "The view 'failing' is dependent on collector 'baz' which do not exist."
+    )]
+    fn should_return_error_if_a_view_is_dependent_on_non_existing_collector() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "teamcity": {
+                            "id": "foo",
+                            "serverUrl": "https://build1.example.com",
+                            "credentials": "guest",
+                            "builds": [ "Foo" ]
+                        }
+                    }
+                ],
+                "views": [
+                    {
+                        "name": "failing",
+                        "collectors": [ "baz" ]
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "The view 'failing' contains unknown status 'Broken'.")]
+    fn should_return_error_if_a_view_contains_unknown_status() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "views": [
+                    {
+                        "name": "failing",
+                        "statuses": [ "Failed", "Broken" ]
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "The observer 'bar' is dependent on view 'baz' which do not exist.")]
+    fn should_return_error_if_an_observer_is_dependent_on_non_existing_view() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "hue": {
+                            "id": "bar",
+                            "view": "baz",
+                            "hubUrl": "https://localhost:5000",
+                            "username": "SOME-SECRET-USERNAME",
+                            "lights": [ "3" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "The observer 'bar' can't use both a view and collectors.")]
+    fn should_return_error_if_an_observer_has_both_view_and_collectors() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "teamcity": {
+                            "id": "foo",
+                            "serverUrl": "https://build1.example.com",
+                            "credentials": "guest",
+                            "builds": [ "Foo" ]
+                        }
+                    }
+                ],
+                "views": [ { "name": "baz" } ],
+                "observers": [
+                    {
+                        "hue": {
+                            "id": "bar",
+                            "view": "baz",
+                            "collectors": [ "foo" ],
+                            "hubUrl": "https://localhost:5000",
+                            "username": "SOME-SECRET-USERNAME",
+                            "lights": [ "3" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "The collector 'foo' has an invalid branch pattern '/release-(/'.")]
+    fn should_return_error_if_a_collector_has_invalid_branch_pattern() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "teamcity": {
+                            "id": "foo",
+                            "serverUrl": "https://build1.example.com",
+                            "credentials": "guest",
+                            "builds": [ "Foo" ],
+                            "branchFilter": {
+                                "include": [ "master", "/release-(/" ]
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "The API token cannot be empty.")]
+    fn should_return_error_if_api_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            {
+                "apiToken": " ",
+                "collectors": [ ]
+            }
+        "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+    }
 }
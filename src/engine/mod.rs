@@ -4,7 +4,7 @@ use std::sync::Arc;
 use std::thread::JoinHandle;
 
 use crate::builds::{Build, BuildStatus};
-use crate::config::Configuration;
+use crate::config::{Configuration, PullRequestMode};
 use crate::providers::collectors::*;
 use crate::providers::observers::*;
 use crate::providers::*;
@@ -12,10 +12,12 @@ use crate::utils::DuckResult;
 
 use self::state::{BuildUpdateResult, EngineState};
 
+use chrono::Utc;
 use log::{debug, error, info};
 use waithandle::{EventWaitHandle, WaitHandle};
 
 pub mod state;
+pub mod views;
 
 pub struct Engine<'a> {
     config: &'a Configuration,
@@ -43,6 +45,10 @@ pub enum EngineEvent {
     BuildUpdated(Box<Build>),
     /// Build status changed from Success->Failure or vice versa.
     BuildStatusChanged(Box<Build>),
+    /// Someone is looking into the build.
+    BuildAcknowledged(Box<Build>),
+    /// An acknowledgement or mute was changed or expired.
+    AcknowledgementsChanged,
     /// Duck is shutting down.
     ShuttingDown,
 }
@@ -63,6 +69,7 @@ impl<'a> Engine<'a> {
     pub fn run(&self) -> DuckResult<EngineHandle> {
         let handle = Arc::new(EventWaitHandle::new());
         let (sender, receiver) = channel::<EngineEvent>();
+        self.state.set_sender(sender.clone());
 
         // Create all collectors.
         let collectors = self.providers.get_collectors(self.config)?;
@@ -112,6 +119,16 @@ fn run_collectors(
 
             let mut build_hashes = std::collections::HashSet::<u64>::new();
             if let Err(e) = collector.collect(handle.clone(), &mut |build: Build| {
+                // Skip builds for branches we're not interested in.
+                let build = match collector.info().branches.apply(build) {
+                    Some(build) => build,
+                    None => return,
+                };
+                if build.pull_request.is_some()
+                    && collector.info().pull_requests == PullRequestMode::Exclude
+                {
+                    return;
+                }
                 build_hashes.insert(build.id);
                 match state.builds.update(&build) {
                     BuildUpdateResult::Added | BuildUpdateResult::BuildUpdated => {
@@ -147,6 +164,14 @@ fn run_collectors(
             state.builds.retain_builds(&collector.info(), build_hashes);
         }
 
+        // Builds that are no longer acknowledged or
+        // muted might change the overall status.
+        if state.builds.remove_expired(Utc::now().timestamp()) {
+            if let Err(e) = sender.send(EngineEvent::AcknowledgementsChanged) {
+                error!("Failed to send acknowledgement event. {}", e);
+            }
+        }
+
         // Wait for a little while
         if handle
             .wait(std::time::Duration::from_secs(interval))
@@ -202,58 +227,56 @@ fn run_observers(
         let command = result.unwrap();
         match command {
             EngineEvent::BuildUpdated(build) => {
-                // Did the build status change?
-                let status = state.builds.current_status();
-                let overall_status_changed = if overall_status != status {
-                    overall_status = status;
-                    true
-                } else {
-                    false
-                };
-
-                // Did the overall build status change for any observers?
-                for observer in &observers {
-                    // Only interested in specific collectors?
-                    if let Some(collectors) = &observer.info().collectors {
-                        let previous_status = observer_status
-                            .entry(&observer.info().id)
-                            .or_insert(BuildStatus::Unknown);
-                        let current_status = state.builds.current_status_for_collectors(collectors);
-                        if *previous_status != current_status
-                            && *previous_status != BuildStatus::Unknown
-                        {
-                            // Status changed so send this to the observer.
-                            propagate_to_observer(
-                                observer,
-                                Observation::DuckStatusChanged(current_status.clone()),
-                            );
-                            *previous_status = current_status;
-                        }
-                    } else {
-                        // Not interested in specific collectors.
-                        // So did the overall build status change?
-                        if overall_status_changed {
-                            // Notify the observer.
-                            propagate_to_observer(
-                                observer,
-                                Observation::DuckStatusChanged(overall_status.clone()),
-                            );
-                        }
-                    }
-                }
+                propagate_status_changes(
+                    &state,
+                    &observers,
+                    &mut observer_status,
+                    &mut overall_status,
+                );
 
-                // Send the BuildUpdated event to observers.
-                propagate_to_observers(&observers, &mut || Observation::BuildUpdated(&build));
+                // Send the BuildUpdated event to observers. Muted builds
+                // are sent as well, so that observers can keep track of them,
+                // but observers that notify people should skip them.
+                let build = state.builds.decorate(&build);
+                propagate_to_observers(&state, &observers, &mut || {
+                    Observation::BuildUpdated(&build)
+                });
             }
             EngineEvent::BuildStatusChanged(build) => {
+                let build = state.builds.decorate(&build);
                 // Send the BuildUpdated event to observers.
-                propagate_to_observers(&observers, &mut || Observation::BuildUpdated(&build));
+                propagate_to_observers(&state, &observers, &mut || {
+                    Observation::BuildUpdated(&build)
+                });
                 // Send the BuildStatusChanged event to observers.
-                propagate_to_observers(&observers, &mut || Observation::BuildStatusChanged(&build));
+                propagate_to_observers(&state, &observers, &mut || {
+                    Observation::BuildStatusChanged(&build)
+                });
+            }
+            EngineEvent::BuildAcknowledged(build) => {
+                propagate_status_changes(
+                    &state,
+                    &observers,
+                    &mut observer_status,
+                    &mut overall_status,
+                );
+
+                // Send the BuildAcknowledged event to observers.
+                propagate_to_observers(&state, &observers, &mut || {
+                    Observation::BuildAcknowledged(&build)
+                });
+            }
+            EngineEvent::AcknowledgementsChanged => {
+                propagate_status_changes(
+                    &state,
+                    &observers,
+                    &mut observer_status,
+                    &mut overall_status,
+                );
             }
             EngineEvent::ShuttingDown => {
                 // Send the ShuttingDown event to observers.
-                propagate_to_observers(&observers, &mut || Observation::ShuttingDown);
+                propagate_to_observers(&state, &observers, &mut || Observation::ShuttingDown);
                 stopped = true;
             }
         }
@@ -262,7 +285,74 @@ fn run_observers(
     Ok(())
 }
 
+/// Notifies observers if the overall status, or the
+/// status for the collectors they care about, changed.
+fn propagate_status_changes<'a>(
+    state: &EngineState,
+    observers: &'a [Box<dyn Observer>],
+    observer_status: &mut HashMap<&'a str, BuildStatus>,
+    overall_status: &mut BuildStatus,
+) {
+    // Did the build status change?
+    let status = state.builds.current_status();
+    let overall_status_changed = if *overall_status != status {
+        *overall_status = status;
+        true
+    } else {
+        false
+    };
+
+    // Did the overall build status change for any observers?
+    for observer in observers {
+        // Only interested in a view, specific collectors or pull requests?
+        let info = observer.info();
+        let current_status = match (&info.view, &info.collectors) {
+            (Some(view), _) => state.get_view(view).map(|view| {
+                state
+                    .builds
+                    .current_status_for_view(view, &info.pull_requests)
+            }),
+            (None, Some(collectors)) => Some(
+                state
+                    .builds
+                    .current_status_for_collectors(collectors, &info.pull_requests),
+            ),
+            (None, None) if info.pull_requests != PullRequestMode::Include => Some(
+                state
+                    .builds
+                    .current_status_where(&info.pull_requests, |_| true),
+            ),
+            (None, None) => None,
+        };
+
+        if let Some(current_status) = current_status {
+            let previous_status = observer_status
+                .entry(&observer.info().id)
+                .or_insert(BuildStatus::Unknown);
+            if *previous_status != current_status && *previous_status != BuildStatus::Unknown {
+                // Status changed so send this to the observer.
+                propagate_to_observer(
+                    observer,
+                    Observation::DuckStatusChanged(current_status.clone()),
+                );
+                *previous_status = current_status;
+            }
+        } else {
+            // Not interested in specific collectors.
+            // So did the overall build status change?
+            if overall_status_changed {
+                // Notify the observer.
+                propagate_to_observer(
+                    observer,
+                    Observation::DuckStatusChanged(overall_status.clone()),
+                );
+            }
+        }
+    }
+}
+
 fn propagate_to_observers<'a>(
+    state: &EngineState,
     observers: &[Box<dyn Observer>],
     observation: &mut dyn Fn() -> Observation<'a>,
 ) {
@@ -274,12 +364,28 @@ fn propagate_to_observers<'a>(
         if let ObservationOrigin::Collector(collector) = observation.get_origin() {
             if let Some(collectors) = &observer.info().collectors {
                 if !collectors.contains(collector) {
-                    // The observer is not interested in the origin.
-                    return;
+                    // The observer is not interested in the origin,
+                    // but the remaining observers might be.
+                    continue;
                 }
             }
         }
 
+        // Is the observer only interested in some pull request builds?
+        if let Some(build) = observation.get_build() {
+            match (&observer.info().pull_requests, build.pull_request.is_some()) {
+                (PullRequestMode::Exclude, true) | (PullRequestMode::Separate, false) => continue,
+                _ => {}
+            }
+        }
+
+        // Is the observer only interested in builds in a view?
+        if let (Some(view), Some(build)) = (&observer.info().view, observation.get_build()) {
+            if !matches!(state.get_view(view), Some(v) if v.matches(build)) {
+                continue;
+            }
+        }
+
         propagate_to_observer(observer, observation);
     }
 }
@@ -293,3 +399,135 @@ fn propagate_to_observer(observer: &Box<dyn Observer>, observation: Observation)
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::{Acknowledgement, BuildBuilder};
+    use crate::config::PullRequestMode;
+    use crate::utils::text::TestVariableProvider;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    struct MockObserver {
+        info: ObserverInfo,
+        observed: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Observer for MockObserver {
+        fn info(&self) -> &ObserverInfo {
+            &self.info
+        }
+
+        fn observe(&self, observation: Observation) -> DuckResult<()> {
+            if let Some(build) = observation.get_build() {
+                let muted = if build.muted.is_some() {
+                    " (muted)"
+                } else {
+                    ""
+                };
+                self.observed
+                    .lock()
+                    .unwrap()
+                    .push(format!("{}:{}{}", self.info.id, build.collector, muted));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn should_notify_all_observers_interested_in_collector() {
+        // Given
+        let config =
+            Configuration::from_json(&TestVariableProvider::new(), r#"{ "collectors": [ ] }"#)
+                .unwrap();
+        let state = EngineState::new(&config);
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        let observers: Vec<Box<dyn Observer>> = vec![
+            Box::new(MockObserver {
+                info: ObserverInfo {
+                    id: "first".to_string(),
+                    enabled: true,
+                    collectors: Some(
+                        vec!["teamcity".to_string()]
+                            .into_iter()
+                            .collect::<HashSet<_>>(),
+                    ),
+                    view: None,
+                    pull_requests: PullRequestMode::Include,
+                },
+                observed: observed.clone(),
+            }),
+            Box::new(MockObserver {
+                info: ObserverInfo {
+                    id: "second".to_string(),
+                    enabled: true,
+                    collectors: Some(
+                        vec!["github".to_string()]
+                            .into_iter()
+                            .collect::<HashSet<_>>(),
+                    ),
+                    view: None,
+                    pull_requests: PullRequestMode::Include,
+                },
+                observed: observed.clone(),
+            }),
+        ];
+
+        // When
+        for collector in &["teamcity", "github"] {
+            let build = BuildBuilder::dummy().collector(*collector).unwrap();
+            propagate_to_observers(&state, &observers, &mut || {
+                Observation::BuildUpdated(&build)
+            });
+        }
+
+        // Then
+        assert_eq!(
+            vec!["first:teamcity".to_string(), "second:github".to_string()],
+            *observed.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_send_status_changes_for_muted_builds() {
+        // Given
+        let config =
+            Configuration::from_json(&TestVariableProvider::new(), r#"{ "collectors": [ ] }"#)
+                .unwrap();
+        let state = Arc::new(EngineState::new(&config));
+        let build = BuildBuilder::dummy().unwrap();
+        state.builds.update(&build);
+        state
+            .builds
+            .mute(build.partition, Acknowledgement::dummy("patrik"));
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        let observers: Vec<Box<dyn Observer>> = vec![Box::new(MockObserver {
+            info: ObserverInfo {
+                id: "observer".to_string(),
+                enabled: true,
+                collectors: None,
+                view: None,
+                pull_requests: PullRequestMode::Include,
+            },
+            observed: observed.clone(),
+        })];
+        let (sender, receiver) = channel::<EngineEvent>();
+        sender
+            .send(EngineEvent::BuildStatusChanged(Box::new(build)))
+            .unwrap();
+        sender.send(EngineEvent::ShuttingDown).unwrap();
+
+        // When
+        run_observers(state, observers, receiver).unwrap();
+
+        // Then
+        assert_eq!(
+            vec![
+                "observer:collector (muted)".to_string(),
+                "observer:collector (muted)".to_string()
+            ],
+            *observed.lock().unwrap()
+        );
+    }
+}
@@ -1,28 +1,99 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::sync::mpsc::Sender;
 use std::sync::Mutex;
 
-use crate::builds::{Build, BuildStatus};
-use crate::config::Configuration;
+use chrono::Utc;
+use log::error;
+
+use crate::builds::{Acknowledgement, Build, BuildProvider, BuildStatus};
+use crate::config::{Configuration, PullRequestMode};
 use crate::providers::collectors::CollectorInfo;
 
+use super::views::View;
+use super::EngineEvent;
+
 pub struct EngineState {
     pub title: String,
+    pub api_token: Option<String>,
     pub builds: BuildRepository,
+    pub views: Vec<View>,
+    events: Mutex<Option<Sender<EngineEvent>>>,
 }
 
 impl EngineState {
     pub fn new(config: &Configuration) -> Self {
         return EngineState {
             title: config.get_title().to_string(),
-            builds: BuildRepository::new(),
+            api_token: config.api_token.clone(),
+            builds: BuildRepository::new().with_separate_pull_requests(
+                config
+                    .collectors
+                    .iter()
+                    .filter(|c| c.get_pull_request_mode() == PullRequestMode::Separate)
+                    .map(|c| c.get_id().to_string())
+                    .collect(),
+            ),
+            views: match &config.views {
+                Some(views) => views.iter().map(View::new).collect(),
+                None => Vec::new(),
+            },
+            events: Mutex::new(None),
         };
     }
+
+    pub fn get_view(&self, name: &str) -> Option<&View> {
+        self.views.iter().find(|v| v.name == name)
+    }
+
+    /// Sets the channel used to notify observers about
+    /// changes that are made outside of the collectors.
+    pub fn set_sender(&self, sender: Sender<EngineEvent>) {
+        *self.events.lock().unwrap() = Some(sender);
+    }
+
+    pub fn acknowledge(&self, partition: u64, acknowledgement: Acknowledgement) -> Option<Build> {
+        let build = self.builds.acknowledge(partition, acknowledgement)?;
+        self.send(EngineEvent::BuildAcknowledged(Box::new(build.clone())));
+        Some(build)
+    }
+
+    pub fn unacknowledge(&self, partition: u64) -> Option<Build> {
+        let build = self.builds.unacknowledge(partition)?;
+        self.send(EngineEvent::AcknowledgementsChanged);
+        Some(build)
+    }
+
+    pub fn mute(&self, partition: u64, acknowledgement: Acknowledgement) -> Option<Build> {
+        let build = self.builds.mute(partition, acknowledgement)?;
+        self.send(EngineEvent::AcknowledgementsChanged);
+        Some(build)
+    }
+
+    pub fn unmute(&self, partition: u64) -> Option<Build> {
+        let build = self.builds.unmute(partition)?;
+        self.send(EngineEvent::AcknowledgementsChanged);
+        Some(build)
+    }
+
+    fn send(&self, event: EngineEvent) {
+        if let Some(sender) = &*self.events.lock().unwrap() {
+            if let Err(e) = sender.send(event) {
+                error!("Failed to send acknowledgement event. {}", e);
+            }
+        }
+    }
 }
 
 pub struct BuildRepository {
     builds: Mutex<Vec<Build>>,
     statuses: Mutex<HashMap<u64, BuildStatus>>,
+    /// Acknowledgements keyed by build partition.
+    acknowledged: Mutex<HashMap<u64, Acknowledgement>>,
+    /// Mutes keyed by build partition.
+    muted: Mutex<HashMap<u64, Acknowledgement>>,
+    /// Collectors whose pull request builds are aggregated separately.
+    separate_pull_requests: HashSet<String>,
 }
 
 #[derive(PartialEq)]
@@ -38,11 +109,101 @@ impl BuildRepository {
         Self {
             builds: Mutex::new(Vec::new()),
             statuses: Mutex::new(HashMap::new()),
+            acknowledged: Mutex::new(HashMap::new()),
+            muted: Mutex::new(HashMap::new()),
+            separate_pull_requests: HashSet::new(),
         }
     }
 
+    pub fn with_separate_pull_requests(mut self, collectors: HashSet<String>) -> Self {
+        self.separate_pull_requests = collectors;
+        self
+    }
+
     pub fn all(&self) -> Vec<Build> {
-        self.builds.lock().unwrap().clone()
+        let builds = self.builds.lock().unwrap();
+        builds.iter().map(|b| self.decorate(b)).collect()
+    }
+
+    pub fn get(&self, id: u64) -> Option<Build> {
+        let builds = self.builds.lock().unwrap();
+        builds.iter().find(|b| b.id == id).map(|b| self.decorate(b))
+    }
+
+    /// Acknowledges the partition and returns its latest build,
+    /// or `None` if there are no builds in the partition.
+    pub fn acknowledge(&self, partition: u64, acknowledgement: Acknowledgement) -> Option<Build> {
+        self.set(&self.acknowledged, partition, Some(acknowledgement))
+    }
+
+    pub fn unacknowledge(&self, partition: u64) -> Option<Build> {
+        self.set(&self.acknowledged, partition, None)
+    }
+
+    /// Mutes the partition and returns its latest build,
+    /// or `None` if there are no builds in the partition.
+    pub fn mute(&self, partition: u64, acknowledgement: Acknowledgement) -> Option<Build> {
+        self.set(&self.muted, partition, Some(acknowledgement))
+    }
+
+    pub fn unmute(&self, partition: u64) -> Option<Build> {
+        self.set(&self.muted, partition, None)
+    }
+
+    /// Removes acknowledgements and mutes that have expired,
+    /// and returns true if there were any.
+    pub fn remove_expired(&self, now: i64) -> bool {
+        let mut removed = false;
+        for map in [&self.acknowledged, &self.muted].iter() {
+            let mut map = map.lock().unwrap();
+            let count = map.len();
+            map.retain(|_, a| !a.is_expired(now));
+            removed |= map.len() != count;
+        }
+        removed
+    }
+
+    fn set(
+        &self,
+        map: &Mutex<HashMap<u64, Acknowledgement>>,
+        partition: u64,
+        acknowledgement: Option<Acknowledgement>,
+    ) -> Option<Build> {
+        let builds = self.builds.lock().unwrap();
+        let build = builds.iter().rev().find(|b| b.partition == partition)?;
+        match acknowledgement {
+            Some(acknowledgement) => map.lock().unwrap().insert(partition, acknowledgement),
+            None => map.lock().unwrap().remove(&partition),
+        };
+        Some(self.decorate(build))
+    }
+
+    /// Adds acknowledgements and mutes to a build.
+    pub fn decorate(&self, build: &Build) -> Build {
+        let now = Utc::now().timestamp();
+        let find = |map: &Mutex<HashMap<u64, Acknowledgement>>| {
+            map.lock()
+                .unwrap()
+                .get(&build.partition)
+                .filter(|a| !a.is_expired(now))
+                .cloned()
+        };
+
+        let mut build = build.clone();
+        build.acknowledged = find(&self.acknowledged);
+        build.muted = find(&self.muted);
+        build
+    }
+
+    /// Gets whether or not a build should be left out of the overall status.
+    fn is_silenced(&self, build: &Build, now: i64) -> bool {
+        let silenced = |map: &Mutex<HashMap<u64, Acknowledgement>>| {
+            matches!(
+                map.lock().unwrap().get(&build.partition),
+                Some(a) if !a.is_expired(now)
+            )
+        };
+        silenced(&self.acknowledged) || silenced(&self.muted)
     }
 
     #[allow(clippy::block_in_if_condition_stmt)] // Clippy does not like what fmt does...
@@ -72,15 +233,26 @@ impl BuildRepository {
         if !statuses.contains_key(&build.partition) {
             statuses.insert(build.partition, build.status.clone());
         } else if let Some(val) = statuses.get_mut(&build.partition) {
-            if build.status != BuildStatus::Running && *val != build.status {
+            // Pending and skipped builds never change the absolute status.
+            if !build.status.is_pending()
+                && build.status != BuildStatus::Skipped
+                && *val != build.status
+            {
                 result = BuildUpdateResult::BuildStatusChanged;
                 *val = build.status.clone();
+
+                // The build has been fixed, so nobody
+                // is looking into it anymore.
+                if build.status == BuildStatus::Success {
+                    self.acknowledged.lock().unwrap().remove(&build.partition);
+                }
             }
         }
 
         // Remove the build from the list
         builds.retain(|b| {
             !(b.collector == build.collector
+                && b.origin == build.origin
                 && b.project_id == build.project_id
                 && b.definition_id == build.definition_id
                 && b.build_id == build.build_id)
@@ -97,17 +269,24 @@ impl BuildRepository {
         build_ids: std::collections::HashSet<u64>,
     ) {
         // Remove all builds for the collector that was not
-        // part of the provided list.
+        // part of the provided list. Builds from other Duck
+        // instances keep their original provider.
         let mut builds = self.builds.lock().unwrap();
         builds.retain(|b| {
-            return !(b.provider == collector_info.provider
+            return !((b.provider == collector_info.provider
+                || collector_info.provider == BuildProvider::Duck)
                 && b.collector == collector_info.id
                 && !build_ids.contains(&b.id));
         });
 
-        // Only keep statuses that have corresponding builds.
+        // Only keep statuses, acknowledgements and
+        // mutes that have corresponding builds.
         let mut statuses = self.statuses.lock().unwrap();
         statuses.retain(|id, _| builds.iter().any(|b| &b.partition == id));
+        for map in [&self.acknowledged, &self.muted].iter() {
+            let mut map = map.lock().unwrap();
+            map.retain(|id, _| builds.iter().any(|b| &b.partition == id));
+        }
     }
 
     pub fn current_status(&self) -> BuildStatus {
@@ -115,38 +294,86 @@ impl BuildRepository {
         if results.len() == 0 {
             return BuildStatus::Unknown;
         }
-        if results.iter().any(|b| b.status == BuildStatus::Running) {
-            return BuildStatus::Running;
-        } else if results.iter().any(|b| b.status == BuildStatus::Failed) {
-            return BuildStatus::Failed;
-        }
-        BuildStatus::Success
+        let now = Utc::now().timestamp();
+        get_overall_status(results.iter().filter(|b| {
+            !self.is_silenced(b, now) && self.is_aggregated(b, &PullRequestMode::Include)
+        }))
+    }
+
+    pub fn current_status_for_collectors(
+        &self,
+        collectors: &HashSet<String>,
+        pull_requests: &PullRequestMode,
+    ) -> BuildStatus {
+        self.current_status_where(pull_requests, |b| collectors.contains(&b.collector))
+    }
+
+    pub fn current_status_for_view(
+        &self,
+        view: &View,
+        pull_requests: &PullRequestMode,
+    ) -> BuildStatus {
+        self.current_status_where(pull_requests, |b| view.matches(b))
     }
 
-    pub fn current_status_for_collectors(&self, collectors: &HashSet<String>) -> BuildStatus {
+    pub fn current_status_where(
+        &self,
+        pull_requests: &PullRequestMode,
+        predicate: impl Fn(&Build) -> bool,
+    ) -> BuildStatus {
         let results = self.builds.lock().unwrap();
-        if !results.iter().any(|b| collectors.contains(&b.collector)) {
+        let predicate = |b: &Build| predicate(b) && self.is_aggregated(b, pull_requests);
+        if !results.iter().any(&predicate) {
             return BuildStatus::Unknown;
         }
-        if results
-            .iter()
-            .any(|b| collectors.contains(&b.collector) && b.status == BuildStatus::Running)
-        {
-            return BuildStatus::Running;
-        } else if results
-            .iter()
-            .any(|b| collectors.contains(&b.collector) && b.status == BuildStatus::Failed)
-        {
-            return BuildStatus::Failed;
+        let now = Utc::now().timestamp();
+        get_overall_status(
+            results
+                .iter()
+                .filter(|b| predicate(b) && !self.is_silenced(b, now)),
+        )
+    }
+
+    /// Gets whether or not the build is part of a status that
+    /// handles pull request builds in the specified way.
+    fn is_aggregated(&self, build: &Build, pull_requests: &PullRequestMode) -> bool {
+        match (pull_requests, build.pull_request.is_some()) {
+            (PullRequestMode::Include, true) => {
+                !self.separate_pull_requests.contains(&build.collector)
+            }
+            (PullRequestMode::Exclude, true) | (PullRequestMode::Separate, false) => false,
+            _ => true,
+        }
+    }
+}
+
+/// Gets the overall status for a set of builds.
+///
+/// Running builds take precedence, followed by blocked,
+/// queued and failed builds. Skipped and canceled builds
+/// never affect the overall status.
+fn get_overall_status<'a>(builds: impl Iterator<Item = &'a Build>) -> BuildStatus {
+    let precedence = [
+        BuildStatus::Running,
+        BuildStatus::Blocked,
+        BuildStatus::Queued,
+        BuildStatus::Failed,
+    ];
+    let statuses: Vec<&BuildStatus> = builds.map(|b| &b.status).collect();
+    for status in precedence.iter() {
+        if statuses.contains(&status) {
+            return status.clone();
         }
-        BuildStatus::Success
     }
+    BuildStatus::Success
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::builds::BuildBuilder;
+    use crate::builds::{BuildBuilder, BuildPullRequest};
+    use crate::config::ViewConfiguration;
+    use test_case::test_case;
 
     #[test]
     fn should_have_successful_as_current_state_if_there_are_no_builds() {
@@ -279,6 +506,298 @@ mod tests {
         let mut collectors = HashSet::<String>::new();
         collectors.insert("collector2".to_string());
 
-        assert!(state.current_status_for_collectors(&collectors) == BuildStatus::Failed);
+        assert!(
+            state.current_status_for_collectors(&collectors, &PullRequestMode::Include)
+                == BuildStatus::Failed
+        );
+    }
+
+    #[test]
+    fn should_return_correct_state_for_view() {
+        let state = BuildRepository::new();
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project1")
+                .branch("master")
+                .status(BuildStatus::Success)
+                .unwrap(),
+        );
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project2")
+                .branch("feature/foo")
+                .status(BuildStatus::Failed)
+                .unwrap(),
+        );
+
+        let view = View::new(&ViewConfiguration {
+            name: "master".to_string(),
+            collectors: None,
+            providers: None,
+            projects: None,
+            definitions: None,
+            branches: Some(vec!["master".to_string()]),
+            statuses: None,
+            pull_requests: None,
+        });
+
+        assert!(
+            state.current_status_for_view(&view, &PullRequestMode::Include) == BuildStatus::Success
+        );
+        assert!(state.current_status() == BuildStatus::Failed);
+    }
+
+    #[test]
+    fn should_aggregate_pull_request_builds_separately() {
+        let mut separate = HashSet::new();
+        separate.insert("collector2".to_string());
+        let state = BuildRepository::new().with_separate_pull_requests(separate);
+        state.update(
+            &BuildBuilder::dummy()
+                .collector("collector1")
+                .branch("master")
+                .status(BuildStatus::Success)
+                .unwrap(),
+        );
+        state.update(
+            &BuildBuilder::dummy()
+                .collector("collector2")
+                .branch("refs/pull/12/merge")
+                .pull_request(BuildPullRequest::from_branch("refs/pull/12/merge"))
+                .status(BuildStatus::Failed)
+                .unwrap(),
+        );
+
+        assert!(state.current_status() == BuildStatus::Success);
+        assert!(
+            state.current_status_where(&PullRequestMode::Exclude, |_| true) == BuildStatus::Success
+        );
+        assert!(
+            state.current_status_where(&PullRequestMode::Separate, |_| true) == BuildStatus::Failed
+        );
+    }
+
+    #[test]
+    fn should_set_state_to_blocked_if_builds_are_blocked_and_queued() {
+        let state = BuildRepository::new();
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project1")
+                .status(BuildStatus::Queued)
+                .unwrap(),
+        );
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project2")
+                .status(BuildStatus::Blocked)
+                .unwrap(),
+        );
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project3")
+                .status(BuildStatus::Failed)
+                .unwrap(),
+        );
+        assert!(state.current_status() == BuildStatus::Blocked);
+    }
+
+    #[test]
+    fn should_set_state_to_queued_even_if_there_are_failed_builds() {
+        let state = BuildRepository::new();
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project1")
+                .status(BuildStatus::Failed)
+                .unwrap(),
+        );
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project2")
+                .status(BuildStatus::Queued)
+                .unwrap(),
+        );
+        assert!(state.current_status() == BuildStatus::Queued);
+    }
+
+    #[test]
+    fn should_not_let_skipped_builds_affect_state() {
+        let state = BuildRepository::new();
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project1")
+                .status(BuildStatus::Success)
+                .unwrap(),
+        );
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project2")
+                .status(BuildStatus::Skipped)
+                .unwrap(),
+        );
+        assert!(state.current_status() == BuildStatus::Success);
+    }
+
+    #[test]
+    fn should_not_treat_pending_or_skipped_builds_as_status_changes() {
+        let state = BuildRepository::new();
+        state.update(
+            &BuildBuilder::dummy()
+                .build_id("1")
+                .status(BuildStatus::Success)
+                .unwrap(),
+        );
+
+        for (id, status) in [
+            ("2", BuildStatus::Queued),
+            ("3", BuildStatus::Blocked),
+            ("4", BuildStatus::Skipped),
+        ]
+        .iter()
+        {
+            let result = state.update(
+                &BuildBuilder::dummy()
+                    .build_id(*id)
+                    .status(status.clone())
+                    .unwrap(),
+            );
+            assert!(result == BuildUpdateResult::BuildUpdated);
+        }
+
+        let result = state.update(
+            &BuildBuilder::dummy()
+                .build_id("5")
+                .status(BuildStatus::Failed)
+                .unwrap(),
+        );
+        assert!(result == BuildUpdateResult::BuildStatusChanged);
+    }
+
+    #[test]
+    fn should_get_build_by_id() {
+        let state = BuildRepository::new();
+        let build = BuildBuilder::dummy().build_id("1").unwrap();
+        state.update(&build);
+        state.update(&BuildBuilder::dummy().build_id("2").unwrap());
+
+        assert_eq!(Some(build.clone()), state.get(build.id));
+        assert_eq!(None, BuildRepository::new().get(build.id));
+    }
+
+    #[test]
+    fn should_not_let_acknowledged_builds_affect_state() {
+        let state = BuildRepository::new();
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project1")
+                .status(BuildStatus::Success)
+                .unwrap(),
+        );
+        let build = BuildBuilder::dummy()
+            .project_id("project2")
+            .status(BuildStatus::Failed)
+            .unwrap();
+        state.update(&build);
+
+        let acknowledged = state
+            .acknowledge(build.partition, Acknowledgement::dummy("Patrik"))
+            .unwrap();
+        assert_eq!(
+            Some(Acknowledgement::dummy("Patrik")),
+            acknowledged.acknowledged
+        );
+        assert_eq!(acknowledged, state.get(build.id).unwrap());
+        assert!(state.current_status() == BuildStatus::Success);
+
+        state.unacknowledge(build.partition);
+        assert!(state.current_status() == BuildStatus::Failed);
+    }
+
+    #[test]
+    fn should_not_acknowledge_unknown_partitions() {
+        let state = BuildRepository::new();
+        let build = BuildBuilder::dummy()
+            .project_id("project2")
+            .status(BuildStatus::Failed)
+            .unwrap();
+        state.update(&build);
+
+        assert_eq!(
+            None,
+            state.acknowledge(build.partition + 1, Acknowledgement::dummy("Patrik"))
+        );
+    }
+
+    #[test]
+    fn should_remove_acknowledgement_when_build_is_fixed() {
+        let state = BuildRepository::new();
+        let build = BuildBuilder::dummy()
+            .project_id("project2")
+            .status(BuildStatus::Failed)
+            .unwrap();
+        state.update(&build);
+        state.acknowledge(build.partition, Acknowledgement::dummy("Patrik"));
+
+        let fixed = BuildBuilder::dummy()
+            .build_id("2")
+            .project_id("project2")
+            .status(BuildStatus::Success)
+            .unwrap();
+        state.update(&fixed);
+
+        assert_eq!(None, state.get(fixed.id).unwrap().acknowledged);
+    }
+
+    #[test]
+    fn should_remove_expired_acknowledgements() {
+        let state = BuildRepository::new();
+        let build = BuildBuilder::dummy()
+            .project_id("project2")
+            .status(BuildStatus::Failed)
+            .unwrap();
+        state.update(&build);
+        let mut acknowledgement = Acknowledgement::dummy("Patrik");
+        acknowledgement.expires_at = Some(1578830000);
+        state.acknowledge(build.partition, acknowledgement);
+
+        assert!(!state.remove_expired(1578820000));
+        assert!(state.remove_expired(1578830000));
+        assert!(!state.remove_expired(1578830000));
+    }
+
+    #[test]
+    fn should_not_let_muted_builds_affect_state() {
+        let state = BuildRepository::new();
+        state.update(
+            &BuildBuilder::dummy()
+                .project_id("project1")
+                .status(BuildStatus::Success)
+                .unwrap(),
+        );
+        let build = BuildBuilder::dummy()
+            .project_id("project2")
+            .status(BuildStatus::Failed)
+            .unwrap();
+        state.update(&build);
+
+        state.mute(build.partition, Acknowledgement::dummy("Patrik"));
+        assert!(state.decorate(&build).muted.is_some());
+        assert!(state.current_status() == BuildStatus::Success);
+
+        state.unmute(build.partition);
+        assert!(state.decorate(&build).muted.is_none());
+        assert!(state.current_status() == BuildStatus::Failed);
+    }
+
+    #[test_case(vec![BuildStatus::Success, BuildStatus::Failed], BuildStatus::Failed ; "Failed over success")]
+    #[test_case(vec![BuildStatus::Failed, BuildStatus::Queued], BuildStatus::Queued ; "Queued over failed")]
+    #[test_case(vec![BuildStatus::Queued, BuildStatus::Blocked], BuildStatus::Blocked ; "Blocked over queued")]
+    #[test_case(vec![BuildStatus::Blocked, BuildStatus::Running, BuildStatus::Failed], BuildStatus::Running ; "Running over everything")]
+    #[test_case(vec![BuildStatus::Success, BuildStatus::Canceled, BuildStatus::Skipped], BuildStatus::Success ; "Canceled and skipped are ignored")]
+    fn should_get_overall_status_by_precedence(statuses: Vec<BuildStatus>, expected: BuildStatus) {
+        let builds: Vec<Build> = statuses
+            .into_iter()
+            .map(|status| BuildBuilder::dummy().status(status).unwrap())
+            .collect();
+        assert_eq!(expected, get_overall_status(builds.iter()));
     }
 }
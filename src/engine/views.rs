@@ -0,0 +1,155 @@
+use crate::builds::{Build, BuildProvider, BuildStatus};
+use crate::config::{PullRequestMode, ViewConfiguration};
+use crate::utils::glob::Glob;
+
+/// A named subset of all builds.
+pub struct View {
+    pub name: String,
+    collectors: Option<Vec<String>>,
+    providers: Option<Vec<BuildProvider>>,
+    projects: Option<Vec<Glob>>,
+    definitions: Option<Vec<Glob>>,
+    branches: Option<Vec<Glob>>,
+    statuses: Option<Vec<BuildStatus>>,
+    pub pull_requests: PullRequestMode,
+}
+
+impl View {
+    pub fn new(config: &ViewConfiguration) -> Self {
+        // Unknown providers and statuses have already
+        // been rejected when validating the configuration.
+        View {
+            name: config.name.clone(),
+            collectors: config.collectors.clone(),
+            providers: config.providers.as_ref().map(|providers| {
+                providers
+                    .iter()
+                    .filter_map(|p| BuildProvider::from_name(p))
+                    .collect()
+            }),
+            projects: get_globs(&config.projects),
+            definitions: get_globs(&config.definitions),
+            branches: get_globs(&config.branches),
+            statuses: config.statuses.as_ref().map(|statuses| {
+                statuses
+                    .iter()
+                    .filter_map(|s| BuildStatus::from_name(s))
+                    .collect()
+            }),
+            pull_requests: config.pull_requests.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Gets whether or not the build is part of the view.
+    pub fn matches(&self, build: &Build) -> bool {
+        is_match(&self.collectors, |c| c == &build.collector)
+            && is_match(&self.providers, |p| p == &build.provider)
+            && is_match(&self.projects, |g| g.is_match(&build.project_name))
+            && is_match(&self.definitions, |g| g.is_match(&build.definition_name))
+            && is_match(&self.branches, |g| g.is_match(&build.branch))
+            && is_match(&self.statuses, |s| s == &build.status)
+            && !matches!(
+                (&self.pull_requests, build.pull_request.is_some()),
+                (PullRequestMode::Exclude, true) | (PullRequestMode::Separate, false)
+            )
+    }
+}
+
+fn get_globs(patterns: &Option<Vec<String>>) -> Option<Vec<Glob>> {
+    patterns
+        .as_ref()
+        .map(|patterns| patterns.iter().map(|p| Glob::new(p)).collect())
+}
+
+/// Filters that haven't been specified match everything.
+fn is_match<T>(filter: &Option<Vec<T>>, predicate: impl Fn(&T) -> bool) -> bool {
+    match filter {
+        Some(items) => items.iter().any(predicate),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::{BuildBuilder, BuildPullRequest};
+
+    #[test]
+    fn should_match_everything_if_there_are_no_filters() {
+        let view = View::new(&ViewConfiguration {
+            name: "team1".to_string(),
+            collectors: None,
+            providers: None,
+            projects: None,
+            definitions: None,
+            branches: None,
+            statuses: None,
+            pull_requests: None,
+        });
+        assert!(view.matches(&BuildBuilder::dummy().unwrap()));
+    }
+
+    #[test]
+    fn should_match_builds_matching_all_filters() {
+        let view = View::new(&ViewConfiguration {
+            name: "team1".to_string(),
+            collectors: Some(vec!["collector".to_string(), "other".to_string()]),
+            providers: Some(vec!["TeamCity".to_string()]),
+            projects: Some(vec!["project_*".to_string()]),
+            definitions: None,
+            branches: Some(vec!["master".to_string(), "bran?h".to_string()]),
+            statuses: Some(vec!["Failed".to_string()]),
+            pull_requests: None,
+        });
+
+        assert!(view.matches(&BuildBuilder::dummy().status(BuildStatus::Failed).unwrap()));
+        assert!(!view.matches(&BuildBuilder::dummy().status(BuildStatus::Success).unwrap()));
+        assert!(!view.matches(
+            &BuildBuilder::dummy()
+                .status(BuildStatus::Failed)
+                .branch("develop")
+                .unwrap()
+        ));
+        assert!(!view.matches(
+            &BuildBuilder::dummy()
+                .status(BuildStatus::Failed)
+                .provider(BuildProvider::GitHub)
+                .unwrap()
+        ));
+    }
+
+    #[test]
+    fn should_match_pull_request_builds_according_to_mode() {
+        let branch = BuildBuilder::dummy().unwrap();
+        let pull_request = BuildBuilder::dummy()
+            .branch("pull/12")
+            .pull_request(BuildPullRequest::from_branch("pull/12"))
+            .unwrap();
+
+        let exclude = View::new(&ViewConfiguration {
+            name: "team1".to_string(),
+            collectors: None,
+            providers: None,
+            projects: None,
+            definitions: None,
+            branches: None,
+            statuses: None,
+            pull_requests: Some(PullRequestMode::Exclude),
+        });
+        let separate = View::new(&ViewConfiguration {
+            name: "team1".to_string(),
+            collectors: None,
+            providers: None,
+            projects: None,
+            definitions: None,
+            branches: None,
+            statuses: None,
+            pull_requests: Some(PullRequestMode::Separate),
+        });
+
+        assert!(exclude.matches(&branch));
+        assert!(!exclude.matches(&pull_request));
+        assert!(!separate.matches(&branch));
+        assert!(separate.matches(&pull_request));
+    }
+}
@@ -0,0 +1,175 @@
+use log::trace;
+use url::Url;
+
+use crate::builds::BuildStatus;
+use crate::config::{ArgoCdConfiguration, ArgoCdCredentials};
+use crate::utils::date;
+use crate::utils::http::*;
+use crate::utils::DuckResult;
+
+pub struct ArgoCdClient {
+    pub url: Url,
+    credentials: ArgoCdCredentials,
+}
+
+impl ArgoCdClient {
+    pub fn new(config: &ArgoCdConfiguration) -> Self {
+        Self {
+            url: Url::parse(&config.server_url[..]).unwrap(),
+            credentials: config.credentials.clone(),
+        }
+    }
+
+    pub fn get_applications(&self, client: &impl HttpClient) -> DuckResult<ArgoCdResponse> {
+        let url = format!("{url}api/v1/applications", url = self.url);
+
+        trace!("Sending request to: {}", url);
+        let mut builder = HttpRequestBuilder::get(&url);
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+        self.credentials.authenticate(&mut builder);
+
+        let mut response = client.send(&builder)?;
+
+        trace!("Received response: {}", response.status());
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Received non 200 HTTP status code. ({})",
+                response.status()
+            ));
+        }
+
+        response.deserialize_json()
+    }
+}
+
+impl ArgoCdCredentials {
+    fn authenticate(&self, builder: &mut HttpRequestBuilder) {
+        match self {
+            ArgoCdCredentials::Token(token) => {
+                builder.add_header("Authorization", &format!("Bearer {}", token)[..]);
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ArgoCdResponse {
+    #[serde(default)]
+    pub items: Option<Vec<ArgoCdApplication>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ArgoCdApplication {
+    pub metadata: ArgoCdMetadata,
+    pub spec: ArgoCdSpec,
+    pub status: ArgoCdStatus,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ArgoCdMetadata {
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ArgoCdSpec {
+    pub project: String,
+    pub source: ArgoCdSource,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ArgoCdSource {
+    #[serde(alias = "targetRevision", default)]
+    pub target_revision: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ArgoCdStatus {
+    pub sync: ArgoCdSyncStatus,
+    pub health: ArgoCdHealthStatus,
+    #[serde(alias = "operationState", default)]
+    pub operation: Option<ArgoCdOperationState>,
+    #[serde(alias = "reconciledAt", default)]
+    pub reconciled_at: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ArgoCdSyncStatus {
+    pub status: String,
+    #[serde(default)]
+    pub revision: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ArgoCdHealthStatus {
+    pub status: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ArgoCdOperationState {
+    pub phase: String,
+    #[serde(alias = "startedAt")]
+    pub started_at: String,
+    #[serde(alias = "finishedAt", default)]
+    pub finished_at: Option<String>,
+}
+
+impl ArgoCdApplication {
+    pub fn get_status(&self) -> BuildStatus {
+        // An ongoing sync operation always takes precedence.
+        if let Some(operation) = &self.status.operation {
+            match &operation.phase[..] {
+                "Running" | "Terminating" => return BuildStatus::Running,
+                "Failed" | "Error" => return BuildStatus::Failed,
+                _ => {}
+            }
+        }
+
+        match &self.status.health.status[..] {
+            "Progressing" => BuildStatus::Running,
+            "Degraded" | "Missing" => BuildStatus::Failed,
+            "Suspended" => BuildStatus::Blocked,
+            "Healthy" => match &self.status.sync.status[..] {
+                "Synced" => BuildStatus::Success,
+                "OutOfSync" => BuildStatus::Failed,
+                _ => BuildStatus::Unknown,
+            },
+            _ => BuildStatus::Unknown,
+        }
+    }
+
+    pub fn get_revision(&self) -> &str {
+        match &self.status.sync.revision {
+            Some(revision) => revision,
+            None => "unknown",
+        }
+    }
+
+    pub fn get_started_timestamp(&self) -> DuckResult<i64> {
+        let started_at = match &self.status.operation {
+            Some(operation) => Some(&operation.started_at),
+            None => self.status.reconciled_at.as_ref(),
+        };
+        match started_at {
+            Some(value) => date::to_timestamp(value, date::ARGOCD_FORMAT),
+            None => Err(format_err!(
+                "Argo CD application '{}' has no start time.",
+                self.metadata.name
+            )),
+        }
+    }
+
+    pub fn get_finished_timestamp(&self) -> DuckResult<Option<i64>> {
+        if self.get_status().is_pending() {
+            return Ok(None);
+        }
+        let finished_at = match &self.status.operation {
+            Some(operation) => operation.finished_at.as_ref(),
+            None => self.status.reconciled_at.as_ref(),
+        };
+        match finished_at {
+            Some(value) => Ok(Some(date::to_timestamp(value, date::ARGOCD_FORMAT)?)),
+            None => Ok(None),
+        }
+    }
+}
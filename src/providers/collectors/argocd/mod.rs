@@ -0,0 +1,352 @@
+use std::sync::Arc;
+
+use log::warn;
+use waithandle::EventWaitHandle;
+
+use crate::builds::{Build, BuildBuilder, BuildProvider};
+use crate::config::ArgoCdConfiguration;
+use crate::providers::collectors::{BranchFilter, Collector, CollectorInfo};
+use crate::utils::http::HttpClient;
+use crate::utils::DuckResult;
+
+use self::client::ArgoCdClient;
+
+mod client;
+mod validation;
+
+pub struct ArgoCdCollector<T: HttpClient + Default> {
+    client: ArgoCdClient,
+    http: T,
+    applications: Vec<String>,
+    info: CollectorInfo,
+}
+
+impl<T: HttpClient + Default> ArgoCdCollector<T> {
+    pub fn new(config: &ArgoCdConfiguration) -> Self {
+        return ArgoCdCollector {
+            client: ArgoCdClient::new(config),
+            http: Default::default(),
+            applications: config.applications.clone(),
+            info: CollectorInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    Option::None => true,
+                    Option::Some(e) => e,
+                },
+                provider: BuildProvider::ArgoCd,
+                branches: BranchFilter::new(config.branch_filter.as_ref()),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+        };
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+}
+
+impl<T: HttpClient + Default> Collector for ArgoCdCollector<T> {
+    fn info(&self) -> &CollectorInfo {
+        &self.info
+    }
+
+    fn collect(
+        &self,
+        _handle: Arc<EventWaitHandle>,
+        callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        let response = self.client.get_applications(&self.http)?;
+        let applications = response.items.unwrap_or_default();
+
+        for name in self.applications.iter() {
+            // Get the application from the result.
+            let application = match applications.iter().find(|a| &a.metadata.name == name) {
+                Some(a) => a,
+                None => {
+                    warn!("Argo CD application '{}' does not exist.", name);
+                    continue;
+                }
+            };
+
+            // Applications that have never been synced or
+            // reconciled don't have anything to show yet.
+            let started_at = match application.get_started_timestamp() {
+                Ok(started_at) => started_at,
+                Err(e) => {
+                    warn!("Could not get start time for '{}'. {}", name, e);
+                    continue;
+                }
+            };
+
+            let revision = application.get_revision();
+            callback(
+                BuildBuilder::new()
+                    .build_id(revision)
+                    .provider(BuildProvider::ArgoCd)
+                    .collector(&self.info.id)
+                    .project_id(&application.spec.project)
+                    .project_name(&application.spec.project)
+                    .definition_id(&application.metadata.name)
+                    .definition_name(&application.metadata.name)
+                    .build_number(revision.chars().take(7).collect::<String>())
+                    .status(application.get_status())
+                    .url(
+                        self.client
+                            .url
+                            .join(&format!("applications/{}", application.metadata.name))?
+                            .to_string(),
+                    )
+                    .started_at(started_at)
+                    .finished_at(application.get_finished_timestamp()?)
+                    .branch(match &application.spec.source.target_revision {
+                        Some(target) => &target[..],
+                        None => "HEAD",
+                    })
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildStatus;
+    use crate::config::*;
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+
+    #[test]
+    fn should_get_correct_data() {
+        // Given
+        let argocd = ArgoCdCollector::<MockHttpClient>::new(&ArgoCdConfiguration {
+            id: "argocd".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://argocd.example.com".to_owned(),
+            credentials: ArgoCdCredentials::Token("SECRET".to_owned()),
+            applications: vec!["guestbook".to_owned()],
+        });
+
+        let client = argocd.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://argocd.example.com/api/v1/applications",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/applications.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        argocd
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!(
+            "53e28ff20cc530b9ada2173fbbd64d48338583ba",
+            result[0].build_id
+        );
+        assert_eq!(BuildProvider::ArgoCd, result[0].provider);
+        assert_eq!("argocd", result[0].collector);
+        assert_eq!("default", result[0].project_id);
+        assert_eq!("default", result[0].project_name);
+        assert_eq!("guestbook", result[0].definition_id);
+        assert_eq!("guestbook", result[0].definition_name);
+        assert_eq!("53e28ff", result[0].build_number);
+        assert_eq!(BuildStatus::Success, result[0].status);
+        assert_eq!("master", result[0].branch);
+        assert_eq!(
+            "https://argocd.example.com/applications/guestbook",
+            result[0].url
+        );
+        assert_eq!(1580589796, result[0].started_at);
+        assert_eq!(1580589826, result[0].finished_at.unwrap());
+    }
+
+    #[test]
+    fn should_send_api_token() {
+        // Given
+        let argocd = ArgoCdCollector::<MockHttpClient>::new(&ArgoCdConfiguration {
+            id: "argocd".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://argocd.example.com".to_owned(),
+            credentials: ArgoCdCredentials::Token("SECRET".to_owned()),
+            applications: vec!["guestbook".to_owned()],
+        });
+
+        let client = argocd.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://argocd.example.com/api/v1/applications",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/applications.json")),
+        );
+
+        // When
+        argocd
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |_: Build| {},
+            )
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(
+            "Bearer SECRET",
+            requests[0].headers.get("Authorization").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_skip_unknown_applications() {
+        // Given
+        let argocd = ArgoCdCollector::<MockHttpClient>::new(&ArgoCdConfiguration {
+            id: "argocd".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://argocd.example.com".to_owned(),
+            credentials: ArgoCdCredentials::Token("SECRET".to_owned()),
+            applications: vec!["guestbook".to_owned(), "missing".to_owned()],
+        });
+
+        let client = argocd.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://argocd.example.com/api/v1/applications",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/applications.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        argocd
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("guestbook", result[0].definition_id);
+    }
+
+    #[test]
+    fn should_map_statuses() {
+        // Given
+        let argocd = ArgoCdCollector::<MockHttpClient>::new(&ArgoCdConfiguration {
+            id: "argocd".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://argocd.example.com".to_owned(),
+            credentials: ArgoCdCredentials::Token("SECRET".to_owned()),
+            applications: vec![
+                "syncing".to_owned(),
+                "degraded".to_owned(),
+                "drifted".to_owned(),
+            ],
+        });
+
+        let client = argocd.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://argocd.example.com/api/v1/applications",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/applications.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        argocd
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(3, result.len());
+        assert_eq!(BuildStatus::Running, result[0].status);
+        assert_eq!(None, result[0].finished_at);
+        assert_eq!(BuildStatus::Failed, result[1].status);
+        assert_eq!(BuildStatus::Failed, result[2].status);
+    }
+
+    #[test]
+    fn should_skip_applications_without_start_time() {
+        // Given
+        let argocd = ArgoCdCollector::<MockHttpClient>::new(&ArgoCdConfiguration {
+            id: "argocd".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://argocd.example.com".to_owned(),
+            credentials: ArgoCdCredentials::Token("SECRET".to_owned()),
+            applications: vec!["pending".to_owned(), "guestbook".to_owned()],
+        });
+
+        let client = argocd.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://argocd.example.com/api/v1/applications",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/applications.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        argocd
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("guestbook", result[0].definition_id);
+    }
+}
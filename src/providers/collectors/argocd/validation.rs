@@ -0,0 +1,138 @@
+use url::Url;
+
+use crate::config::{ArgoCdConfiguration, ArgoCdCredentials, Validate};
+use crate::utils::DuckResult;
+
+impl Validate for ArgoCdConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        self.credentials.validate()?;
+
+        if self.id.is_empty() {
+            return Err(format_err!("Argo CD collector have no ID."));
+        }
+        if let Err(e) = Url::parse(&self.server_url[..]) {
+            return Err(format_err!("Argo CD server URL is invalid: {}", e));
+        }
+
+        if self.applications.is_empty() {
+            return Err(format_err!(
+                "Argo CD collector '{}' have no configured applications.",
+                self.id
+            ));
+        }
+        for application in self.applications.iter() {
+            if application.is_empty() {
+                return Err(format_err!(
+                    "An Argo CD application in collector '{}' is empty.",
+                    self.id
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Validate for ArgoCdCredentials {
+    fn validate(&self) -> DuckResult<()> {
+        match self {
+            ArgoCdCredentials::Token(token) => {
+                if token.is_empty() {
+                    return Err(format_err!("Argo CD API token is empty."));
+                }
+            }
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers::DuckProviderCollection;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "Argo CD server URL is invalid: relative URL without a base")]
+    fn should_return_error_if_server_url_is_empty_or_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "argocd": {
+                            "id": "argocd",
+                            "serverUrl": "",
+                            "credentials": {
+                                "token": "MY-SECRET-TOKEN"
+                            },
+                            "applications": [ "guestbook" ]
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Argo CD API token is empty.")]
+    fn should_return_error_if_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "argocd": {
+                            "id": "argocd",
+                            "serverUrl": "https://argocd.example.com",
+                            "credentials": {
+                                "token": ""
+                            },
+                            "applications": [ "guestbook" ]
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "have no configured applications.")]
+    fn should_return_error_if_there_are_no_applications() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "argocd": {
+                            "id": "argocd",
+                            "serverUrl": "https://argocd.example.com",
+                            "credentials": {
+                                "token": "MY-SECRET-TOKEN"
+                            },
+                            "applications": [ ]
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+}
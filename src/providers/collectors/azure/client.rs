@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use log::trace;
 use reqwest::header::ACCEPT;
 use reqwest::{Client, ClientBuilder, RequestBuilder};
@@ -39,6 +41,49 @@ impl AzureDevOpsClient {
         Ok(result)
     }
 
+    pub fn get_timeline(&self, build: u64) -> DuckResult<AzureTimeline> {
+        let mut response = self.send_get_request(format!(
+            "https://dev.azure.com/{organization}/{project}/_apis/build/builds/{build}/timeline?api-version=5.1",
+            organization = self.organization,
+            project = self.project,
+            build = build,
+        ))?;
+
+        let result: AzureTimeline = response.json()?;
+        Ok(result)
+    }
+
+    pub fn get_test_runs(&self, build: u64) -> DuckResult<AzureTestRunResponse> {
+        let mut response = self.send_get_request(format!(
+            "https://dev.azure.com/{organization}/{project}/_apis/test/runs?api-version=5.1\
+             &buildUri=vstfs%3A%2F%2F%2FBuild%2FBuild%2F{build}",
+            organization = self.organization,
+            project = self.project,
+            build = build,
+        ))?;
+
+        let result: AzureTestRunResponse = response.json()?;
+        Ok(result)
+    }
+
+    pub fn get_failed_tests(&self, run: u64, count: usize) -> DuckResult<Vec<String>> {
+        let mut response = self.send_get_request(format!(
+            "https://dev.azure.com/{organization}/{project}/_apis/test/Runs/{run}/results?api-version=5.1\
+             &outcomes=Failed&$top={count}",
+            organization = self.organization,
+            project = self.project,
+            run = run,
+            count = count,
+        ))?;
+
+        let result: AzureTestResultResponse = response.json()?;
+        Ok(result
+            .value
+            .into_iter()
+            .map(|r| r.test_case_title)
+            .collect())
+    }
+
     fn send_get_request(&self, url: String) -> DuckResult<reqwest::Response> {
         trace!("Sending request to: {}", url);
         let response = self.client.get(&url).header(ACCEPT, "application/json");
@@ -89,6 +134,22 @@ pub struct AzureBuild {
     pub branch: String,
     #[serde(alias = "_links")]
     pub links: AzureLinks,
+    #[serde(default, alias = "sourceVersion")]
+    pub source_version: Option<String>,
+    #[serde(default, alias = "requestedFor")]
+    pub requested_for: Option<AzureIdentity>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default, alias = "queueTime")]
+    pub queue_time: Option<String>,
+    #[serde(default, alias = "triggerInfo")]
+    pub trigger_info: Option<HashMap<String, String>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureIdentity {
+    #[serde(alias = "displayName")]
+    pub display_name: String,
 }
 
 #[derive(Deserialize, Debug)]
@@ -112,3 +173,56 @@ pub struct AzureBuildDefinition {
     pub id: u64,
     pub name: String,
 }
+
+#[derive(Deserialize, Debug)]
+pub struct AzureTimeline {
+    #[serde(default)]
+    pub records: Vec<AzureTimelineRecord>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureTimelineRecord {
+    pub id: String,
+    #[serde(alias = "type")]
+    pub record_type: String,
+    pub name: String,
+    #[serde(default)]
+    pub order: Option<i64>,
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub result: Option<String>,
+    #[serde(default, alias = "startTime")]
+    pub start_time: Option<String>,
+    #[serde(default, alias = "finishTime")]
+    pub finish_time: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureTestRunResponse {
+    pub value: Vec<AzureTestRun>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureTestRun {
+    pub id: u64,
+    #[serde(default, alias = "totalTests")]
+    pub total_tests: u32,
+    #[serde(default, alias = "passedTests")]
+    pub passed_tests: u32,
+    #[serde(default, alias = "unanalyzedTests")]
+    pub failed_tests: u32,
+    #[serde(default, alias = "notApplicableTests")]
+    pub ignored_tests: u32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureTestResultResponse {
+    pub value: Vec<AzureTestResult>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureTestResult {
+    #[serde(alias = "testCaseTitle")]
+    pub test_case_title: String,
+}
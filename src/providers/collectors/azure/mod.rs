@@ -1,10 +1,14 @@
 use std::sync::Arc;
 
+use log::warn;
 use waithandle::{EventWaitHandle, WaitHandle};
 
-use crate::builds::{Build, BuildBuilder, BuildProvider, BuildStatus};
+use crate::builds::{
+    Build, BuildBuilder, BuildProvider, BuildPullRequest, BuildStage, BuildStatus, BuildTests,
+    BuildTrigger, MAX_FAILED_TESTS,
+};
 use crate::config::AzureDevOpsConfiguration;
-use crate::providers::collectors::{Collector, CollectorInfo};
+use crate::providers::collectors::{BranchFilter, Collector, CollectorInfo};
 use crate::utils::{date, DuckResult};
 
 use self::client::*;
@@ -32,11 +36,87 @@ impl AzureDevOpsCollector {
                     Option::Some(e) => e,
                 },
                 provider: BuildProvider::AzureDevOps,
+                branches: BranchFilter::new(config.branch_filter.as_ref()),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
             },
         };
     }
 }
 
+impl AzureDevOpsCollector {
+    fn get_tests(&self, build: &AzureBuild) -> Option<BuildTests> {
+        let runs = match self.client.get_test_runs(build.id) {
+            Ok(runs) => runs.value,
+            Err(e) => {
+                warn!("Could not get test runs for build {}. {}", build.id, e);
+                return None;
+            }
+        };
+        if runs.is_empty() {
+            return None;
+        }
+
+        let (mut total, mut passed, mut failed, mut ignored) = (0, 0, 0, 0);
+        let mut failures = Vec::new();
+        for run in runs.iter() {
+            total += run.total_tests;
+            passed += run.passed_tests;
+            failed += run.failed_tests;
+            ignored += run.ignored_tests;
+
+            // Get the names of the first failing tests.
+            let remaining = MAX_FAILED_TESTS - failures.len();
+            if run.failed_tests > 0 && remaining > 0 {
+                match self.client.get_failed_tests(run.id, remaining) {
+                    Ok(names) => failures.extend(names),
+                    Err(e) => warn!("Could not get failed tests for run {}. {}", run.id, e),
+                }
+            }
+        }
+
+        Some(BuildTests {
+            total: Some(total),
+            passed: Some(passed),
+            failed,
+            ignored: Some(ignored),
+            failures,
+        })
+    }
+
+    fn get_stages(&self, build: &AzureBuild) -> Option<Vec<BuildStage>> {
+        let timeline = match self.client.get_timeline(build.id) {
+            Ok(timeline) => timeline,
+            Err(e) => {
+                warn!("Could not get timeline for build {}. {}", build.id, e);
+                return None;
+            }
+        };
+
+        // Prefer stages for multi-stage pipelines, and jobs for everything else.
+        let record_type = if timeline.records.iter().any(|r| r.record_type == "Stage") {
+            "Stage"
+        } else {
+            "Job"
+        };
+
+        let mut records: Vec<&AzureTimelineRecord> = timeline
+            .records
+            .iter()
+            .filter(|r| r.record_type == record_type)
+            .collect();
+        records.sort_by_key(|r| r.order);
+
+        let mut stages = Vec::new();
+        for record in records {
+            match record.get_stage(&build.links.web.href) {
+                Ok(stage) => stages.push(stage),
+                Err(e) => warn!("Could not parse timeline record '{}'. {}", record.name, e),
+            }
+        }
+        Some(stages)
+    }
+}
+
 impl Collector for AzureDevOpsCollector {
     fn info(&self) -> &CollectorInfo {
         &self.info
@@ -78,6 +158,24 @@ impl Collector for AzureDevOpsCollector {
                             )?),
                         })
                         .branch(&build.branch)
+                        .commit(build.source_version.clone())
+                        .author(
+                            build
+                                .requested_for
+                                .as_ref()
+                                .map(|identity| identity.display_name.clone()),
+                        )
+                        .trigger(build.get_build_trigger())
+                        .pull_request(build.get_pull_request())
+                        .queued_at(match &build.queue_time {
+                            Option::None => None,
+                            Option::Some(value) => Option::Some(date::to_timestamp(
+                                &value[..],
+                                date::AZURE_DEVOPS_FORMAT,
+                            )?),
+                        })
+                        .stages(self.get_stages(build))
+                        .tests(self.get_tests(build))
                         .build()
                         .unwrap(),
                 );
@@ -95,10 +193,13 @@ impl Collector for AzureDevOpsCollector {
 
 impl AzureBuild {
     pub fn get_build_status(&self) -> BuildStatus {
+        if self.status == "notStarted" || self.status == "postponed" {
+            return BuildStatus::Queued;
+        }
         if self.result.is_none() {
             return BuildStatus::Running;
         } else {
-            if self.status == "inProgress" || self.status == "notStarted" {
+            if self.status == "inProgress" {
                 return BuildStatus::Running;
             }
             match self.result.as_ref().unwrap().as_ref() {
@@ -108,4 +209,105 @@ impl AzureBuild {
             }
         }
     }
+
+    pub fn get_build_trigger(&self) -> Option<BuildTrigger> {
+        match self.reason.as_ref().map(|r| &r[..]) {
+            Some("individualCI") | Some("batchedCI") => Some(BuildTrigger::Push),
+            Some("pullRequest") => Some(BuildTrigger::PullRequest),
+            Some("schedule") => Some(BuildTrigger::Schedule),
+            Some("manual") | Some("userCreated") => Some(BuildTrigger::Manual),
+            _ => None,
+        }
+    }
+
+    /// Pull request builds are made for refs/pull/{number}/merge,
+    /// and builds of GitHub repositories also know the title.
+    pub fn get_pull_request(&self) -> Option<BuildPullRequest> {
+        let mut pull_request = BuildPullRequest::from_branch(&self.branch)?;
+        if let Some(info) = &self.trigger_info {
+            pull_request.title = info.get("pr.title").cloned();
+        }
+        Some(pull_request)
+    }
+}
+
+impl AzureTimelineRecord {
+    pub fn get_stage(&self, url: &str) -> DuckResult<BuildStage> {
+        Ok(BuildStage {
+            name: self.name.clone(),
+            status: self.get_status(),
+            started_at: match &self.start_time {
+                Option::None => None,
+                Option::Some(value) => {
+                    Some(date::to_timestamp(&value[..], date::AZURE_DEVOPS_FORMAT)?)
+                }
+            },
+            finished_at: match &self.finish_time {
+                Option::None => None,
+                Option::Some(value) => {
+                    Some(date::to_timestamp(&value[..], date::AZURE_DEVOPS_FORMAT)?)
+                }
+            },
+            url: Some(format!("{}&view=logs&j={}", url, self.id)),
+        })
+    }
+
+    pub fn get_status(&self) -> BuildStatus {
+        match self.state.as_ref().map(|s| &s[..]) {
+            Some("pending") => BuildStatus::Queued,
+            Some("inProgress") => BuildStatus::Running,
+            Some("completed") => match self.result.as_ref().map(|r| &r[..]) {
+                Some("succeeded") | Some("succeededWithIssues") => BuildStatus::Success,
+                Some("failed") => BuildStatus::Failed,
+                Some("canceled") | Some("abandoned") => BuildStatus::Canceled,
+                Some("skipped") => BuildStatus::Skipped,
+                _ => BuildStatus::Unknown,
+            },
+            _ => BuildStatus::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("notStarted", None, BuildStatus::Queued ; "Not started")]
+    #[test_case("postponed", None, BuildStatus::Queued ; "Postponed")]
+    #[test_case("inProgress", None, BuildStatus::Running ; "In progress")]
+    #[test_case("completed", Some("succeeded"), BuildStatus::Success ; "Succeeded")]
+    #[test_case("completed", Some("canceled"), BuildStatus::Canceled ; "Canceled")]
+    #[test_case("completed", Some("failed"), BuildStatus::Failed ; "Failed")]
+    #[test_case("completed", Some("partiallySucceeded"), BuildStatus::Failed ; "Partially succeeded")]
+    fn should_get_build_status(status: &str, result: Option<&str>, expected: BuildStatus) {
+        let build = AzureBuild {
+            id: 1,
+            build_number: "20200129.1".to_string(),
+            project: AzureProject {
+                id: "project".to_string(),
+                name: "Project".to_string(),
+            },
+            definition: AzureBuildDefinition {
+                id: 1,
+                name: "Definition".to_string(),
+            },
+            status: status.to_string(),
+            result: result.map(|r| r.to_string()),
+            start_time: "2020-01-29T13:49:54.512Z".to_string(),
+            finish_time: None,
+            branch: "refs/heads/master".to_string(),
+            links: AzureLinks {
+                web: AzureWebLink {
+                    href: "https://dev.azure.com".to_string(),
+                },
+            },
+            source_version: None,
+            requested_for: None,
+            reason: None,
+            queue_time: None,
+            trigger_info: None,
+        };
+        assert_eq!(expected, build.get_build_status());
+    }
 }
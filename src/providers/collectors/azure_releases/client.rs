@@ -0,0 +1,146 @@
+use log::trace;
+
+use crate::builds::BuildStatus;
+use crate::config::{AzureDevOpsCredentials, AzureDevOpsReleasesConfiguration};
+use crate::utils::http::*;
+use crate::utils::DuckResult;
+
+pub struct AzureDevOpsReleasesClient {
+    pub organization: String,
+    pub project: String,
+    deployments: u32,
+    credentials: AzureDevOpsCredentials,
+}
+
+impl AzureDevOpsReleasesClient {
+    pub fn new(config: &AzureDevOpsReleasesConfiguration) -> Self {
+        Self {
+            organization: config.organization.clone(),
+            project: config.project.clone(),
+            deployments: config.deployments.unwrap_or(50),
+            credentials: config.credentials.clone(),
+        }
+    }
+
+    pub fn get_deployments(
+        &self,
+        client: &impl HttpClient,
+        definition: &str,
+    ) -> DuckResult<AzureDeploymentsResponse> {
+        let url = format!(
+            "https://vsrm.dev.azure.com/{organization}/{project}/_apis/release/deployments\
+             ?api-version=5.1&definitionId={definition}&latestAttemptsOnly=true\
+             &queryOrder=descending&$top={deployments}",
+            organization = self.organization,
+            project = self.project,
+            definition = definition,
+            deployments = self.deployments
+        );
+
+        trace!("Sending request to: {}", url);
+        let mut builder = HttpRequestBuilder::get(&url);
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+        authenticate(&self.credentials, &mut builder);
+
+        let mut response = client.send(&builder)?;
+
+        trace!("Received response: {}", response.status());
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Received non 200 HTTP status code. ({})",
+                response.status()
+            ));
+        }
+
+        response.deserialize_json()
+    }
+}
+
+fn authenticate(credentials: &AzureDevOpsCredentials, builder: &mut HttpRequestBuilder) {
+    match credentials {
+        AzureDevOpsCredentials::Anonymous => {}
+        AzureDevOpsCredentials::PersonalAccessToken(token) => {
+            builder.basic_auth("", Some(token));
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureDeploymentsResponse {
+    pub value: Vec<AzureDeployment>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureDeployment {
+    pub id: u64,
+    pub release: AzureRelease,
+    #[serde(alias = "releaseDefinition")]
+    pub definition: AzureReleaseDefinition,
+    #[serde(alias = "releaseEnvironment")]
+    pub environment: AzureReleaseEnvironment,
+    #[serde(alias = "definitionEnvironmentId")]
+    pub definition_environment_id: u64,
+    #[serde(alias = "deploymentStatus")]
+    pub deployment_status: String,
+    #[serde(alias = "operationStatus")]
+    pub operation_status: String,
+    #[serde(alias = "queuedOn")]
+    pub queued_on: String,
+    #[serde(alias = "startedOn", default)]
+    pub started_on: Option<String>,
+    #[serde(alias = "completedOn", default)]
+    pub completed_on: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureRelease {
+    pub name: String,
+    #[serde(alias = "_links")]
+    pub links: AzureReleaseLinks,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureReleaseLinks {
+    pub web: AzureReleaseWebLink,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureReleaseWebLink {
+    pub href: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureReleaseDefinition {
+    pub id: u64,
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AzureReleaseEnvironment {
+    pub name: String,
+}
+
+impl AzureDeployment {
+    pub fn get_status(&self) -> BuildStatus {
+        if let "Canceled" | "PhaseCanceled" | "Cancelling" = &self.operation_status[..] {
+            return BuildStatus::Canceled;
+        }
+        match &self.deployment_status[..] {
+            "succeeded" => BuildStatus::Success,
+            "failed" | "partiallySucceeded" => BuildStatus::Failed,
+            "inProgress" => BuildStatus::Running,
+            "notDeployed" => match &self.operation_status[..] {
+                "Rejected" | "GateFailed" | "PhaseFailed" => BuildStatus::Failed,
+                "Undefined" => BuildStatus::Unknown,
+                "Queued" | "Scheduled" | "Deferred" | "QueuedForAgent" | "QueuedForPipeline" => {
+                    BuildStatus::Queued
+                }
+                // Waiting for approval, manual intervention or gates.
+                "Pending" | "ManualInterventionPending" | "EvaluatingGates" => BuildStatus::Blocked,
+                _ => BuildStatus::Running,
+            },
+            _ => BuildStatus::Unknown,
+        }
+    }
+}
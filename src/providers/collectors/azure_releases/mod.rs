@@ -0,0 +1,395 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use waithandle::{EventWaitHandle, WaitHandle};
+
+use crate::builds::{Build, BuildBuilder, BuildProvider};
+use crate::config::AzureDevOpsReleasesConfiguration;
+use crate::providers::collectors::{BranchFilter, Collector, CollectorInfo};
+use crate::utils::http::HttpClient;
+use crate::utils::{date, DuckResult};
+
+use self::client::*;
+
+mod client;
+mod validation;
+
+pub struct AzureDevOpsReleasesCollector<T: HttpClient + Default> {
+    client: AzureDevOpsReleasesClient,
+    http: T,
+    definitions: Vec<String>,
+    environments: Option<Vec<String>>,
+    info: CollectorInfo,
+}
+
+impl<T: HttpClient + Default> AzureDevOpsReleasesCollector<T> {
+    pub fn new(config: &AzureDevOpsReleasesConfiguration) -> Self {
+        return AzureDevOpsReleasesCollector {
+            client: AzureDevOpsReleasesClient::new(config),
+            http: Default::default(),
+            definitions: config.definitions.clone(),
+            environments: config.environments.clone(),
+            info: CollectorInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    Option::None => true,
+                    Option::Some(e) => e,
+                },
+                provider: BuildProvider::AzureDevOps,
+                branches: BranchFilter::new(config.branch_filter.as_ref()),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+        };
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+
+    fn is_included(&self, deployment: &AzureDeployment) -> bool {
+        match &self.environments {
+            Option::None => true,
+            Option::Some(environments) => environments.iter().any(|e| {
+                e == &deployment.environment.name
+                    || e == &deployment.definition_environment_id.to_string()
+            }),
+        }
+    }
+}
+
+impl<T: HttpClient + Default> Collector for AzureDevOpsReleasesCollector<T> {
+    fn info(&self) -> &CollectorInfo {
+        &self.info
+    }
+
+    fn collect(
+        &self,
+        handle: Arc<EventWaitHandle>,
+        callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        for definition in self.definitions.iter() {
+            if handle.check().unwrap() {
+                return Ok(());
+            }
+
+            // Deployments are returned with the most recent first,
+            // so the first one we see for an environment is the latest.
+            let response = self.client.get_deployments(&self.http, definition)?;
+            let mut environments = HashSet::<u64>::new();
+            for deployment in response.value.iter() {
+                if !self.is_included(deployment)
+                    || environments.contains(&deployment.definition_environment_id)
+                {
+                    continue;
+                }
+                environments.insert(deployment.definition_environment_id);
+
+                let status = deployment.get_status();
+                callback(
+                    BuildBuilder::new()
+                        .build_id(deployment.id.to_string())
+                        .provider(BuildProvider::AzureDevOps)
+                        .collector(&self.info.id)
+                        .project_id(deployment.definition.id.to_string())
+                        .project_name(&deployment.definition.name)
+                        .definition_id(deployment.definition_environment_id.to_string())
+                        .definition_name(&deployment.environment.name)
+                        .build_number(&deployment.release.name)
+                        .url(&deployment.release.links.web.href)
+                        .started_at(date::to_timestamp(
+                            match &deployment.started_on {
+                                Option::None => &deployment.queued_on[..],
+                                Option::Some(value) => &value[..],
+                            },
+                            date::AZURE_DEVOPS_FORMAT,
+                        )?)
+                        .finished_at(match &deployment.completed_on {
+                            Option::Some(value) if !status.is_pending() => Option::Some(
+                                date::to_timestamp(&value[..], date::AZURE_DEVOPS_FORMAT)?,
+                            ),
+                            _ => None,
+                        })
+                        .status(status)
+                        .branch(&deployment.environment.name)
+                        .build()
+                        .unwrap(),
+                );
+            }
+
+            // Wait for a litle time between calls.
+            if handle.wait(std::time::Duration::from_millis(300)).unwrap() {
+                return Ok(());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildStatus;
+    use crate::config::*;
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+    use test_case::test_case;
+
+    #[test]
+    fn should_get_latest_deployment_per_environment() {
+        // Given
+        let azure = AzureDevOpsReleasesCollector::<MockHttpClient>::new(
+            &AzureDevOpsReleasesConfiguration {
+                id: "azure_releases".to_owned(),
+                enabled: Some(true),
+                branch_filter: None,
+                pull_requests: None,
+                organization: "duck".to_owned(),
+                project: "builds".to_owned(),
+                credentials: AzureDevOpsCredentials::Anonymous,
+                definitions: vec!["1".to_owned()],
+                environments: None,
+                deployments: None,
+            },
+        );
+
+        let client = azure.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://vsrm.dev.azure.com/duck/builds/_apis/release/deployments\
+                 ?api-version=5.1&definitionId=1&latestAttemptsOnly=true\
+                 &queryOrder=descending&$top=50",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/deployments.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        azure
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(2, result.len());
+
+        assert_eq!("12", result[0].build_id);
+        assert_eq!(BuildProvider::AzureDevOps, result[0].provider);
+        assert_eq!("azure_releases", result[0].collector);
+        assert_eq!("1", result[0].project_id);
+        assert_eq!("Duck", result[0].project_name);
+        assert_eq!("3", result[0].definition_id);
+        assert_eq!("Production", result[0].definition_name);
+        assert_eq!("Release-4", result[0].build_number);
+        assert_eq!(BuildStatus::Running, result[0].status);
+        assert_eq!(
+            "https://dev.azure.com/duck/builds/_release?releaseId=4",
+            result[0].url
+        );
+        assert_eq!(1578819921, result[0].started_at);
+        assert_eq!(None, result[0].finished_at);
+
+        assert_eq!("11", result[1].build_id);
+        assert_eq!("Staging", result[1].definition_name);
+        assert_eq!(BuildStatus::Success, result[1].status);
+        assert_eq!(1578820921, result[1].finished_at.unwrap());
+    }
+
+    #[test]
+    fn should_only_include_configured_environments() {
+        // Given
+        let azure = AzureDevOpsReleasesCollector::<MockHttpClient>::new(
+            &AzureDevOpsReleasesConfiguration {
+                id: "azure_releases".to_owned(),
+                enabled: Some(true),
+                branch_filter: None,
+                pull_requests: None,
+                organization: "duck".to_owned(),
+                project: "builds".to_owned(),
+                credentials: AzureDevOpsCredentials::Anonymous,
+                definitions: vec!["1".to_owned()],
+                environments: Some(vec!["Staging".to_owned()]),
+                deployments: None,
+            },
+        );
+
+        let client = azure.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://vsrm.dev.azure.com/duck/builds/_apis/release/deployments\
+                 ?api-version=5.1&definitionId=1&latestAttemptsOnly=true\
+                 &queryOrder=descending&$top=50",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/deployments.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        azure
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("Staging", result[0].definition_name);
+    }
+
+    #[test]
+    fn should_request_configured_number_of_deployments() {
+        // Given
+        let azure = AzureDevOpsReleasesCollector::<MockHttpClient>::new(
+            &AzureDevOpsReleasesConfiguration {
+                id: "azure_releases".to_owned(),
+                enabled: Some(true),
+                branch_filter: None,
+                pull_requests: None,
+                organization: "duck".to_owned(),
+                project: "builds".to_owned(),
+                credentials: AzureDevOpsCredentials::Anonymous,
+                definitions: vec!["1".to_owned()],
+                environments: None,
+                deployments: Some(200),
+            },
+        );
+
+        let client = azure.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://vsrm.dev.azure.com/duck/builds/_apis/release/deployments\
+                 ?api-version=5.1&definitionId=1&latestAttemptsOnly=true\
+                 &queryOrder=descending&$top=200",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/deployments.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        azure
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert!(requests[0].url.ends_with("&$top=200"));
+    }
+
+    #[test]
+    fn should_not_set_finished_time_for_pending_deployments() {
+        // Given
+        let azure = AzureDevOpsReleasesCollector::<MockHttpClient>::new(
+            &AzureDevOpsReleasesConfiguration {
+                id: "azure_releases".to_owned(),
+                enabled: Some(true),
+                branch_filter: None,
+                pull_requests: None,
+                organization: "duck".to_owned(),
+                project: "builds".to_owned(),
+                credentials: AzureDevOpsCredentials::Anonymous,
+                definitions: vec!["1".to_owned()],
+                environments: None,
+                deployments: None,
+            },
+        );
+
+        let client = azure.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://vsrm.dev.azure.com/duck/builds/_apis/release/deployments\
+                 ?api-version=5.1&definitionId=1&latestAttemptsOnly=true\
+                 &queryOrder=descending&$top=50",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(
+                json!({
+                    "count": 1,
+                    "value": [{
+                        "id": 1,
+                        "release": { "id": 1, "name": "Release-1", "_links": { "web": { "href": "" } } },
+                        "releaseDefinition": { "id": 1, "name": "Duck" },
+                        "releaseEnvironment": { "id": 1, "name": "Production" },
+                        "definitionEnvironmentId": 1,
+                        "deploymentStatus": "notDeployed",
+                        "operationStatus": "Pending",
+                        "queuedOn": "2020-01-12T09:05:21.0733795Z",
+                        "completedOn": "2020-01-12T09:22:01.0733795Z"
+                    }]
+                })
+                .to_string(),
+            ),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        azure
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!(BuildStatus::Blocked, result[0].status);
+        assert_eq!(None, result[0].finished_at);
+    }
+
+    #[test_case("succeeded", "Approved", BuildStatus::Success ; "Succeeded")]
+    #[test_case("partiallySucceeded", "PhasePartiallySucceeded", BuildStatus::Failed ; "Partially succeeded")]
+    #[test_case("failed", "PhaseFailed", BuildStatus::Failed ; "Failed")]
+    #[test_case("inProgress", "PhaseInProgress", BuildStatus::Running ; "In progress")]
+    #[test_case("notDeployed", "Pending", BuildStatus::Blocked ; "Waiting for approval")]
+    #[test_case("notDeployed", "Queued", BuildStatus::Queued ; "Queued")]
+    #[test_case("notDeployed", "Rejected", BuildStatus::Failed ; "Rejected")]
+    #[test_case("notDeployed", "Canceled", BuildStatus::Canceled ; "Canceled")]
+    #[test_case("undefined", "Undefined", BuildStatus::Unknown ; "Undefined")]
+    fn should_map_deployment_status(deployment: &str, operation: &str, expected: BuildStatus) {
+        // Given
+        let deployment: AzureDeployment = serde_json::from_value(json!({
+            "id": 1,
+            "release": { "id": 1, "name": "Release-1", "_links": { "web": { "href": "" } } },
+            "releaseDefinition": { "id": 1, "name": "Duck" },
+            "releaseEnvironment": { "id": 1, "name": "Production" },
+            "definitionEnvironmentId": 1,
+            "deploymentStatus": deployment,
+            "operationStatus": operation,
+            "queuedOn": "2020-01-12T09:05:21.0733795Z"
+        }))
+        .unwrap();
+
+        // When, Then
+        assert_eq!(expected, deployment.get_status());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::config::{AzureDevOpsReleasesConfiguration, Validate};
+use crate::utils::DuckResult;
+
+impl Validate for AzureDevOpsReleasesConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        self.credentials.validate()?;
+        if self.id.is_empty() {
+            return Err(format_err!("Azure DevOps releases collector have no ID."));
+        }
+        if self.organization.is_empty() {
+            return Err(format_err!("Azure DevOps organization is empty."));
+        }
+        if self.project.is_empty() {
+            return Err(format_err!("Azure DevOps project is empty."));
+        }
+        if self.definitions.is_empty() {
+            return Err(format_err!(
+                "Azure DevOps configuration have not specified any release definitions."
+            ));
+        }
+        if let Some(environments) = &self.environments {
+            if environments.iter().any(|e| e.is_empty()) {
+                return Err(format_err!(
+                    "Azure DevOps configuration contains an empty environment."
+                ));
+            }
+        }
+        if self.deployments == Some(0) {
+            return Err(format_err!(
+                "Azure DevOps releases collector must look at one or more deployments."
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::*;
+    use crate::providers::DuckProviderCollection;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "Azure DevOps organization is empty.")]
+    fn should_return_error_if_azure_devops_organization_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "azure_releases": {
+                            "id": "foo",
+                            "organization": "",
+                            "project": "Cake",
+                            "credentials": "anonymous",
+                            "definitions": [ "1" ]
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Azure DevOps configuration have not specified any release definitions."
+    )]
+    fn should_return_error_if_azure_devops_definitions_are_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "azure_releases": {
+                            "id": "foo",
+                            "organization": "cake-build",
+                            "project": "Cake",
+                            "credentials": "anonymous",
+                            "definitions": [ ]
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Azure DevOps configuration contains an empty environment.")]
+    fn should_return_error_if_azure_devops_environment_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "azure_releases": {
+                            "id": "foo",
+                            "organization": "cake-build",
+                            "project": "Cake",
+                            "credentials": "anonymous",
+                            "definitions": [ "1" ],
+                            "environments": [ "Production", "" ]
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Azure DevOps releases collector must look at one or more deployments."
+    )]
+    fn should_return_error_if_azure_devops_deployments_is_zero() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "azure_releases": {
+                            "id": "foo",
+                            "organization": "cake-build",
+                            "project": "Cake",
+                            "credentials": "anonymous",
+                            "definitions": [ "1" ],
+                            "deployments": 0
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+}
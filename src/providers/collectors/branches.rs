@@ -0,0 +1,127 @@
+use regex::Regex;
+
+use crate::builds::Build;
+use crate::config::BranchFilterConfiguration;
+use crate::utils::glob::Glob;
+use crate::utils::DuckResult;
+
+const BRANCH_PREFIX: &str = "refs/heads/";
+
+/// Decides which branches a collector reports builds for.
+#[derive(Default)]
+pub struct BranchFilter {
+    include: Option<Vec<BranchPattern>>,
+    exclude: Vec<BranchPattern>,
+    normalize: bool,
+}
+
+/// A glob pattern, or a regular expression between slashes.
+pub enum BranchPattern {
+    Glob(Glob),
+    Regex(Regex),
+}
+
+impl BranchPattern {
+    pub fn parse(pattern: &str) -> DuckResult<Self> {
+        if pattern.len() > 1 && pattern.starts_with('/') && pattern.ends_with('/') {
+            return Ok(BranchPattern::Regex(Regex::new(
+                &pattern[1..pattern.len() - 1],
+            )?));
+        }
+        Ok(BranchPattern::Glob(Glob::new(pattern)))
+    }
+
+    pub fn is_match(&self, branch: &str) -> bool {
+        match self {
+            BranchPattern::Glob(glob) => glob.is_match(branch),
+            BranchPattern::Regex(regex) => regex.is_match(branch),
+        }
+    }
+}
+
+impl BranchFilter {
+    pub fn new(config: Option<&BranchFilterConfiguration>) -> Self {
+        match config {
+            Some(config) => BranchFilter {
+                include: config.include.as_ref().map(|p| get_patterns(p)),
+                exclude: get_patterns(config.exclude.as_ref().unwrap_or(&vec![])),
+                normalize: config.normalize.unwrap_or(false),
+            },
+            None => BranchFilter::default(),
+        }
+    }
+
+    /// Returns the build if its branch should be reported,
+    /// with the branch name normalized if configured to.
+    pub fn apply(&self, mut build: Build) -> Option<Build> {
+        if self.normalize && build.branch.starts_with(BRANCH_PREFIX) {
+            // The build id and partition are calculated from the
+            // branch as reported by the provider, so they don't change.
+            build.branch = build.branch[BRANCH_PREFIX.len()..].to_string();
+        }
+        if let Some(include) = &self.include {
+            if !include.iter().any(|p| p.is_match(&build.branch)) {
+                return None;
+            }
+        }
+        if self.exclude.iter().any(|p| p.is_match(&build.branch)) {
+            return None;
+        }
+        Some(build)
+    }
+}
+
+fn get_patterns(patterns: &[String]) -> Vec<BranchPattern> {
+    // Invalid patterns have already been rejected
+    // when validating the configuration.
+    patterns
+        .iter()
+        .filter_map(|p| BranchPattern::parse(p).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildBuilder;
+    use test_case::test_case;
+
+    #[test_case("master", true ; "Included by glob")]
+    #[test_case("release/12", true ; "Included by regex")]
+    #[test_case("release/next", false ; "Not included by regex")]
+    #[test_case("feature/foo", false ; "Not included")]
+    #[test_case("refs/heads/master", true ; "Included after normalization")]
+    #[test_case("master-old", false ; "Excluded")]
+    fn should_filter_branches(branch: &str, expected: bool) {
+        let filter = BranchFilter::new(Some(&BranchFilterConfiguration {
+            include: Some(vec!["master*".to_string(), r"/^release/\d+$/".to_string()]),
+            exclude: Some(vec!["*-old".to_string()]),
+            normalize: Some(true),
+        }));
+        let build = BuildBuilder::dummy().branch(branch).unwrap();
+        assert_eq!(expected, filter.apply(build).is_some());
+    }
+
+    #[test]
+    fn should_normalize_branch_without_changing_id() {
+        let filter = BranchFilter::new(Some(&BranchFilterConfiguration {
+            include: None,
+            exclude: None,
+            normalize: Some(true),
+        }));
+        let build = BuildBuilder::dummy().branch("refs/heads/develop").unwrap();
+        let id = build.id;
+
+        let result = filter.apply(build).unwrap();
+
+        assert_eq!("develop", result.branch);
+        assert_eq!(id, result.id);
+    }
+
+    #[test]
+    fn should_include_everything_by_default() {
+        let filter = BranchFilter::new(None);
+        let build = BuildBuilder::dummy().branch("refs/heads/develop").unwrap();
+        assert_eq!("refs/heads/develop", filter.apply(build).unwrap().branch);
+    }
+}
@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use log::{trace, warn};
+use waithandle::EventWaitHandle;
+
+use crate::builds::{Build, BuildBuilder, BuildProvider};
+use crate::config::{CommandConfiguration, CommandOutputFormat};
+use crate::providers::collectors::document::BuildDocument;
+use crate::providers::collectors::{BranchFilter, Collector, CollectorInfo};
+use crate::utils::hash;
+use crate::utils::process::{self, ProcessOutput, ProcessRequest};
+use crate::utils::DuckResult;
+
+mod validation;
+
+static DEFAULT_TIMEOUT: u32 = 30;
+
+pub struct CommandCollector {
+    project: String,
+    name: String,
+    command: String,
+    arguments: Vec<String>,
+    working_directory: Option<String>,
+    environment: Option<HashMap<String, String>>,
+    timeout: Duration,
+    output: CommandOutputFormat,
+    info: CollectorInfo,
+    /// The last reported build, and a hash of the output it was created from.
+    last: Mutex<Option<(u64, Build)>>,
+}
+
+impl CommandCollector {
+    pub fn new(config: &CommandConfiguration) -> Self {
+        CommandCollector {
+            project: match &config.project {
+                Option::None => config.id.clone(),
+                Option::Some(project) => project.clone(),
+            },
+            name: config.name.clone(),
+            command: config.command.clone(),
+            arguments: config.arguments.clone().unwrap_or_default(),
+            working_directory: config.working_directory.clone(),
+            environment: config.environment.clone(),
+            timeout: Duration::from_secs(u64::from(match config.timeout {
+                Option::None => DEFAULT_TIMEOUT,
+                Option::Some(timeout) => timeout,
+            })),
+            output: match &config.output {
+                Option::None => CommandOutputFormat::ExitCode,
+                Option::Some(output) => output.clone(),
+            },
+            info: CollectorInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    Option::None => true,
+                    Option::Some(e) => e,
+                },
+                provider: BuildProvider::Command,
+                branches: BranchFilter::new(config.branch_filter.as_ref()),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+            last: Mutex::new(None),
+        }
+    }
+
+    fn get_document(&self, output: &ProcessOutput) -> BuildDocument {
+        if output.timed_out {
+            warn!(
+                "[{}] The command '{}' timed out.",
+                self.info.id, self.command
+            );
+            return BuildDocument::from_status("failed");
+        }
+
+        if self.output == CommandOutputFormat::Json {
+            match serde_json::from_str::<BuildDocument>(&output.stdout) {
+                Ok(document) => return document,
+                Err(e) => warn!(
+                    "[{}] Could not parse output from '{}'. {}",
+                    self.info.id, self.command, e
+                ),
+            }
+        }
+
+        BuildDocument::from_status(if output.is_success() {
+            "success"
+        } else {
+            "failed"
+        })
+    }
+}
+
+impl Collector for CommandCollector {
+    fn info(&self) -> &CollectorInfo {
+        &self.info
+    }
+
+    fn collect(
+        &self,
+        handle: Arc<EventWaitHandle>,
+        callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        let started_at = chrono::Utc::now().timestamp();
+        let output = process::run(&ProcessRequest {
+            program: &self.command,
+            arguments: &self.arguments,
+            working_directory: self.working_directory.as_ref().map(|d| &d[..]),
+            environment: self.environment.as_ref(),
+            stdin: None,
+            timeout: self.timeout,
+            cancellation: Some(&handle),
+        })?;
+        if output.cancelled {
+            // We're shutting down, so there's nothing to report.
+            return Ok(());
+        }
+        let finished_at = chrono::Utc::now().timestamp();
+        trace!(
+            "[{}] The command '{}' exited with {:?}",
+            self.info.id,
+            self.command,
+            output.exit_code
+        );
+
+        // Report the same build as last time if the command
+        // gave the same output, so that it isn't seen as a new build.
+        let output_hash = hash::stable_hash(&[
+            &format!("{:?}", output.exit_code),
+            &output.stdout,
+            &output.timed_out.to_string(),
+        ]);
+        let mut last = self.last.lock().unwrap();
+        if let Some((last_hash, build)) = &*last {
+            if *last_hash == output_hash {
+                callback(build.clone());
+                return Ok(());
+            }
+        }
+
+        let document = self.get_document(&output);
+        let status = document.get_status();
+        let build = BuildBuilder::new()
+            .build_id(match &document.build_id {
+                Option::None => self.info.id.clone(),
+                Option::Some(id) => id.clone(),
+            })
+            .provider(BuildProvider::Command)
+            .collector(&self.info.id)
+            .project_id(&self.project)
+            .project_name(&self.project)
+            .definition_id(&self.name)
+            .definition_name(&self.name)
+            .build_number(match &document.build_number {
+                Option::None => "latest".to_string(),
+                Option::Some(number) => number.clone(),
+            })
+            .branch(match &document.branch {
+                Option::None => "default".to_string(),
+                Option::Some(branch) => branch.clone(),
+            })
+            .url(document.url.clone().unwrap_or_default())
+            .started_at(document.started_at.unwrap_or(started_at))
+            .finished_at(if status.is_pending() {
+                None
+            } else {
+                Some(document.finished_at.unwrap_or(finished_at))
+            })
+            .status(status)
+            .build()
+            .unwrap();
+
+        *last = Some((output_hash, build.clone()));
+        callback(build);
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::builds::BuildStatus;
+    use crate::engine::state::{BuildRepository, BuildUpdateResult};
+    use test_case::test_case;
+
+    #[test_case("exit 0", BuildStatus::Success ; "Success")]
+    #[test_case("exit 1", BuildStatus::Failed ; "Failed")]
+    fn should_use_exit_code_as_status(script: &str, expected: BuildStatus) {
+        // Given
+        let collector = CommandCollector::new(&CommandConfiguration {
+            id: "command".to_string(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            project: Some("Scripts".to_string()),
+            name: "Backup".to_string(),
+            command: "sh".to_string(),
+            arguments: Some(vec!["-c".to_string(), script.to_string()]),
+            working_directory: None,
+            environment: None,
+            timeout: Some(5),
+            output: None,
+        });
+
+        // When
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        let build = &result[0];
+        assert_eq!(expected, build.status);
+        assert_eq!(BuildProvider::Command, build.provider);
+        assert_eq!("command", build.collector);
+        assert_eq!("Scripts", build.project_name);
+        assert_eq!("Backup", build.definition_name);
+        assert!(build.finished_at.is_some());
+    }
+
+    #[test]
+    fn should_read_json_document_from_stdout() {
+        // Given
+        let collector = CommandCollector::new(&CommandConfiguration {
+            id: "command".to_string(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            project: Some("Scripts".to_string()),
+            name: "Backup".to_string(),
+            command: "sh".to_string(),
+            arguments: Some(vec!["-c".to_string(), r#"echo '{"status":"running","buildNumber":"42","branch":"main","url":"https://example.com","startedAt":1580589796}'"#.to_string()]),
+            working_directory: None,
+            environment: None,
+            timeout: Some(5),
+            output: Some(CommandOutputFormat::Json),
+        });
+
+        // When
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        let build = &result[0];
+        assert_eq!(BuildStatus::Running, build.status);
+        assert_eq!("42", build.build_number);
+        assert_eq!("main", build.branch);
+        assert_eq!("https://example.com", build.url);
+        assert_eq!(1580589796, build.started_at);
+        assert_eq!(None, build.finished_at);
+    }
+
+    #[test]
+    fn should_only_report_one_update_for_identical_runs() {
+        // Given
+        let collector = CommandCollector::new(&CommandConfiguration {
+            id: "command".to_string(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            project: Some("Scripts".to_string()),
+            name: "Backup".to_string(),
+            command: "sh".to_string(),
+            arguments: Some(vec!["-c".to_string(), "sleep 1; exit 1".to_string()]),
+            working_directory: None,
+            environment: None,
+            timeout: Some(5),
+            output: None,
+        });
+        let repository = BuildRepository::new();
+
+        // When
+        let mut builds = Vec::new();
+        let mut results = Vec::new();
+        for _ in 0..2 {
+            collector
+                .collect(
+                    Arc::new(waithandle::EventWaitHandle::new()),
+                    &mut |build: Build| {
+                        results.push(repository.update(&build));
+                        builds.push(build);
+                    },
+                )
+                .unwrap();
+        }
+
+        // Then
+        assert_eq!(builds[0], builds[1]);
+        assert!(results == vec![BuildUpdateResult::Added, BuildUpdateResult::Unchanged]);
+    }
+
+    #[test]
+    fn should_fall_back_to_exit_code_if_json_is_invalid() {
+        // Given
+        let collector = CommandCollector::new(&CommandConfiguration {
+            id: "command".to_string(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            project: Some("Scripts".to_string()),
+            name: "Backup".to_string(),
+            command: "sh".to_string(),
+            arguments: Some(vec![
+                "-c".to_string(),
+                "echo 'not json'; exit 2".to_string(),
+            ]),
+            working_directory: None,
+            environment: None,
+            timeout: Some(5),
+            output: Some(CommandOutputFormat::Json),
+        });
+
+        // When
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        let build = &result[0];
+        assert_eq!(BuildStatus::Failed, build.status);
+    }
+
+    #[test]
+    fn should_stop_command_when_shutting_down() {
+        // Given
+        let collector = CommandCollector::new(&CommandConfiguration {
+            id: "command".to_string(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            project: Some("Scripts".to_string()),
+            name: "Backup".to_string(),
+            command: "sh".to_string(),
+            arguments: Some(vec!["-c".to_string(), "sleep 10".to_string()]),
+            working_directory: None,
+            environment: None,
+            timeout: Some(30),
+            output: None,
+        });
+        let handle = Arc::new(waithandle::EventWaitHandle::new());
+        handle.signal().unwrap();
+
+        // When
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(handle, &mut |build: Build| {
+                // Store the results
+                result.push(build);
+            })
+            .unwrap();
+
+        // Then
+        assert_eq!(0, result.len());
+    }
+}
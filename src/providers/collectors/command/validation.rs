@@ -0,0 +1,86 @@
+use crate::config::{CommandConfiguration, Validate};
+use crate::utils::DuckResult;
+
+impl Validate for CommandConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if self.id.is_empty() {
+            return Err(format_err!("Command collector have no ID."));
+        }
+        if self.name.is_empty() {
+            return Err(format_err!("Command collector '{}' have no name.", self.id));
+        }
+        if self.command.is_empty() {
+            return Err(format_err!(
+                "Command collector '{}' have no command.",
+                self.id
+            ));
+        }
+        if let Some(timeout) = self.timeout {
+            if timeout == 0 {
+                return Err(format_err!(
+                    "Command collector '{}' have a timeout of zero seconds.",
+                    self.id
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers::DuckProviderCollection;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "have no command.")]
+    fn should_return_error_if_command_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "command": {
+                            "id": "backup",
+                            "name": "Backup",
+                            "command": ""
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "have a timeout of zero seconds.")]
+    fn should_return_error_if_timeout_is_zero() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "command": {
+                            "id": "backup",
+                            "name": "Backup",
+                            "command": "./backup.sh",
+                            "timeout": 0
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+}
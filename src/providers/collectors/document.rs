@@ -0,0 +1,64 @@
+use crate::builds::BuildStatus;
+
+/// A build described as JSON by an external source,
+/// such as a local command or a file on disk.
+#[derive(Deserialize, Debug, Default)]
+pub struct BuildDocument {
+    pub status: String,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub definition: Option<String>,
+    #[serde(rename = "buildId", default)]
+    pub build_id: Option<String>,
+    #[serde(rename = "buildNumber", default)]
+    pub build_number: Option<String>,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(rename = "startedAt", default)]
+    pub started_at: Option<i64>,
+    #[serde(rename = "finishedAt", default)]
+    pub finished_at: Option<i64>,
+}
+
+impl BuildDocument {
+    pub fn from_status(status: &str) -> Self {
+        BuildDocument {
+            status: status.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn get_status(&self) -> BuildStatus {
+        match &self.status.to_lowercase()[..] {
+            "success" => BuildStatus::Success,
+            "failed" => BuildStatus::Failed,
+            "running" => BuildStatus::Running,
+            "canceled" => BuildStatus::Canceled,
+            "queued" => BuildStatus::Queued,
+            "skipped" => BuildStatus::Skipped,
+            "blocked" => BuildStatus::Blocked,
+            _ => BuildStatus::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("success", BuildStatus::Success ; "Success")]
+    #[test_case("Failed", BuildStatus::Failed ; "Failed")]
+    #[test_case("RUNNING", BuildStatus::Running ; "Running")]
+    #[test_case("canceled", BuildStatus::Canceled ; "Canceled")]
+    #[test_case("queued", BuildStatus::Queued ; "Queued")]
+    #[test_case("Skipped", BuildStatus::Skipped ; "Skipped")]
+    #[test_case("blocked", BuildStatus::Blocked ; "Blocked")]
+    #[test_case("foo", BuildStatus::Unknown ; "Unknown")]
+    fn should_parse_status(status: &str, expected: BuildStatus) {
+        assert_eq!(expected, BuildDocument::from_status(status).get_status());
+    }
+}
@@ -0,0 +1,142 @@
+use log::trace;
+use url::Url;
+
+use crate::builds::{BuildProvider, BuildPullRequest, BuildStatus, BuildTrigger};
+use crate::config::{DuckConfiguration, DuckCredentials};
+use crate::utils::http::*;
+use crate::utils::DuckResult;
+
+pub struct DuckClient {
+    pub url: Url,
+    credentials: DuckCredentials,
+}
+
+impl DuckClient {
+    pub fn new(config: &DuckConfiguration) -> Self {
+        Self {
+            url: Url::parse(&config.server_url[..]).unwrap(),
+            credentials: match &config.credentials {
+                Some(credentials) => credentials.clone(),
+                None => DuckCredentials::Anonymous,
+            },
+        }
+    }
+
+    pub fn get_builds(&self, client: &impl HttpClient) -> DuckResult<Vec<DuckBuild>> {
+        let url = format!("{url}builds", url = self.url);
+
+        trace!("Sending request to: {}", url);
+        let mut builder = HttpRequestBuilder::get(&url);
+        builder.add_header("Accept", "application/json");
+        self.credentials.authenticate(&mut builder);
+
+        let mut response = client.send(&builder)?;
+
+        trace!("Received response: {}", response.status());
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Received non 200 HTTP status code. ({})",
+                response.status()
+            ));
+        }
+
+        response.deserialize_json()
+    }
+}
+
+impl DuckCredentials {
+    fn authenticate(&self, builder: &mut HttpRequestBuilder) {
+        match self {
+            DuckCredentials::Anonymous => {}
+            DuckCredentials::Basic { username, password } => {
+                builder.basic_auth(username, Some(password));
+            }
+            DuckCredentials::Token(token) => {
+                builder.add_header("Authorization", &format!("Bearer {}", token)[..]);
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DuckBuild {
+    pub provider: String,
+    pub collector: String,
+    pub project: String,
+    pub build: String,
+    pub branch: String,
+    #[serde(rename = "buildId")]
+    pub build_id: String,
+    #[serde(rename = "buildNumber")]
+    pub build_number: String,
+    pub started: i64,
+    pub finished: Option<i64>,
+    pub url: String,
+    pub status: String,
+    #[serde(default)]
+    pub origin: Option<String>,
+    #[serde(default)]
+    pub commit: Option<String>,
+    #[serde(rename = "commitMessage", default)]
+    pub commit_message: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub committer: Option<String>,
+    #[serde(default)]
+    pub trigger: Option<String>,
+    #[serde(default)]
+    pub queued: Option<i64>,
+    #[serde(rename = "pullRequest", default)]
+    pub pull_request: Option<DuckPullRequest>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DuckPullRequest {
+    #[serde(default)]
+    pub number: Option<u64>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+impl DuckBuild {
+    pub fn get_provider(&self) -> BuildProvider {
+        match BuildProvider::from_name(&self.provider[..]) {
+            Some(provider) => provider,
+            None => BuildProvider::Duck,
+        }
+    }
+
+    pub fn get_origin(&self) -> String {
+        // If the remote instance got the build from yet another
+        // Duck instance, keep the collector that originally collected it.
+        match &self.origin {
+            Some(origin) => origin.clone(),
+            None => self.collector.clone(),
+        }
+    }
+
+    pub fn get_trigger(&self) -> Option<BuildTrigger> {
+        match &self.trigger {
+            Some(trigger) => BuildTrigger::from_name(&trigger[..]),
+            None => None,
+        }
+    }
+
+    pub fn get_pull_request(&self) -> Option<BuildPullRequest> {
+        self.pull_request.as_ref().map(|pr| BuildPullRequest {
+            number: pr.number,
+            title: pr.title.clone(),
+            url: pr.url.clone(),
+        })
+    }
+
+    pub fn get_status(&self) -> BuildStatus {
+        match BuildStatus::from_name(&self.status[..]) {
+            Some(status) => status,
+            None => BuildStatus::Unknown,
+        }
+    }
+}
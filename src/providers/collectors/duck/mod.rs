@@ -0,0 +1,388 @@
+use std::sync::Arc;
+
+use waithandle::EventWaitHandle;
+
+use crate::builds::{Build, BuildBuilder, BuildProvider};
+use crate::config::DuckConfiguration;
+use crate::providers::collectors::{BranchFilter, Collector, CollectorInfo};
+use crate::utils::http::HttpClient;
+use crate::utils::DuckResult;
+
+use self::client::DuckClient;
+
+mod client;
+mod validation;
+
+/// Collects builds from another Duck instance.
+///
+/// Duck does not expose an event stream, so the remote
+/// instance is polled like any other build server.
+pub struct DuckCollector<T: HttpClient + Default> {
+    client: DuckClient,
+    http: T,
+    info: CollectorInfo,
+}
+
+impl<T: HttpClient + Default> DuckCollector<T> {
+    pub fn new(config: &DuckConfiguration) -> Self {
+        return DuckCollector {
+            client: DuckClient::new(config),
+            http: Default::default(),
+            info: CollectorInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    Option::None => true,
+                    Option::Some(e) => e,
+                },
+                provider: BuildProvider::Duck,
+                branches: BranchFilter::new(config.branch_filter.as_ref()),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+        };
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+}
+
+impl<T: HttpClient + Default> Collector for DuckCollector<T> {
+    fn info(&self) -> &CollectorInfo {
+        &self.info
+    }
+
+    fn collect(
+        &self,
+        _handle: Arc<EventWaitHandle>,
+        callback: &mut dyn FnMut(Build),
+    ) -> DuckResult<()> {
+        let builds = self.client.get_builds(&self.http)?;
+
+        for build in builds.iter() {
+            callback(
+                BuildBuilder::new()
+                    .build_id(&build.build_id)
+                    .provider(build.get_provider())
+                    .collector(&self.info.id)
+                    .origin(Some(build.get_origin()))
+                    .project_id(&build.project)
+                    .project_name(&build.project)
+                    .definition_id(&build.build)
+                    .definition_name(&build.build)
+                    .build_number(&build.build_number)
+                    .status(build.get_status())
+                    .url(&build.url)
+                    .started_at(build.started)
+                    .finished_at(build.finished)
+                    .branch(&build.branch)
+                    .commit(build.commit.clone())
+                    .commit_message(build.commit_message.clone())
+                    .author(build.author.clone())
+                    .committer(build.committer.clone())
+                    .trigger(build.get_trigger())
+                    .queued_at(build.queued)
+                    .pull_request(build.get_pull_request())
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::{BuildStatus, BuildTrigger};
+    use crate::config::*;
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+
+    #[test]
+    fn should_get_correct_url() {
+        // Given
+        let duck = DuckCollector::<MockHttpClient>::new(&DuckConfiguration {
+            id: "office".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://duck.example.com".to_owned(),
+            credentials: None,
+        });
+
+        let client = duck.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "https://duck.example.com/builds")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/builds.json")),
+        );
+
+        // When
+        duck.collect(Arc::new(EventWaitHandle::new()), &mut |_: Build| {})
+            .unwrap();
+
+        // Then
+        assert_eq!(1, client.get_sent_requests().len());
+        assert_eq!(
+            "https://duck.example.com/builds",
+            &client.get_sent_requests()[0].url
+        );
+    }
+
+    #[test]
+    fn should_not_send_authorization_header_for_anonymous_credentials() {
+        // Given
+        let duck = DuckCollector::<MockHttpClient>::new(&DuckConfiguration {
+            id: "office".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://duck.example.com".to_owned(),
+            credentials: Some(DuckCredentials::Anonymous),
+        });
+
+        let client = duck.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "https://duck.example.com/builds")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/builds.json")),
+        );
+
+        // When
+        duck.collect(Arc::new(EventWaitHandle::new()), &mut |_: Build| {})
+            .unwrap();
+
+        // Then
+        assert!(!client.get_sent_requests()[0]
+            .headers
+            .contains_key("Authorization"));
+    }
+
+    #[test]
+    fn should_send_bearer_token() {
+        // Given
+        let duck = DuckCollector::<MockHttpClient>::new(&DuckConfiguration {
+            id: "office".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://duck.example.com".to_owned(),
+            credentials: Some(DuckCredentials::Token("SECRET".to_owned())),
+        });
+
+        let client = duck.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "https://duck.example.com/builds")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/builds.json")),
+        );
+
+        // When
+        duck.collect(Arc::new(EventWaitHandle::new()), &mut |_: Build| {})
+            .unwrap();
+
+        // Then
+        assert_eq!(
+            "Bearer SECRET",
+            client.get_sent_requests()[0]
+                .headers
+                .get("Authorization")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn should_send_basic_auth_credentials() {
+        // Given
+        let duck = DuckCollector::<MockHttpClient>::new(&DuckConfiguration {
+            id: "office".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://duck.example.com".to_owned(),
+            credentials: Some(DuckCredentials::Basic {
+                username: "patrik".to_owned(),
+                password: "hunter2".to_owned(),
+            }),
+        });
+
+        let client = duck.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "https://duck.example.com/builds")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/builds.json")),
+        );
+
+        // When
+        duck.collect(Arc::new(EventWaitHandle::new()), &mut |_: Build| {})
+            .unwrap();
+
+        // Then
+        assert_eq!(
+            "Basic cGF0cmlrOmh1bnRlcjI=",
+            client.get_sent_requests()[0]
+                .headers
+                .get("Authorization")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn should_return_all_remote_builds() {
+        // Given
+        let duck = DuckCollector::<MockHttpClient>::new(&DuckConfiguration {
+            id: "office".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://duck.example.com".to_owned(),
+            credentials: None,
+        });
+
+        let client = duck.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "https://duck.example.com/builds")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/builds.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        duck.collect(Arc::new(EventWaitHandle::new()), &mut |build: Build| {
+            // Store the results
+            result.push(build);
+        })
+        .unwrap();
+
+        // Then
+        assert_eq!(3, result.len());
+        assert_eq!("office", result[0].collector);
+        assert_eq!("Duck", result[0].project_name);
+        assert_eq!("Build and test", result[0].definition_name);
+        assert_eq!("master", result[0].branch);
+        assert_eq!("1314", result[0].build_number);
+        assert_eq!(BuildStatus::Success, result[0].status);
+        assert_eq!(1573405751, result[0].started_at);
+        assert_eq!(Some(1573405798), result[0].finished_at);
+        assert_eq!(BuildStatus::Running, result[1].status);
+        assert_eq!(None, result[1].finished_at);
+        assert_eq!(BuildStatus::Failed, result[2].status);
+    }
+
+    #[test]
+    fn should_preserve_original_provider_and_collector() {
+        // Given
+        let duck = DuckCollector::<MockHttpClient>::new(&DuckConfiguration {
+            id: "office".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://duck.example.com".to_owned(),
+            credentials: None,
+        });
+
+        let client = duck.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "https://duck.example.com/builds")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/builds.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        duck.collect(Arc::new(EventWaitHandle::new()), &mut |build: Build| {
+            // Store the results
+            result.push(build);
+        })
+        .unwrap();
+
+        // Then
+        assert_eq!(BuildProvider::TeamCity, result[0].provider);
+        assert_eq!(Some("teamcity".to_owned()), result[0].origin);
+        assert_eq!(BuildProvider::GitHub, result[1].provider);
+        assert_eq!(Some("github".to_owned()), result[1].origin);
+    }
+
+    #[test]
+    fn should_keep_origin_of_builds_federated_more_than_once() {
+        // Given
+        let duck = DuckCollector::<MockHttpClient>::new(&DuckConfiguration {
+            id: "office".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://duck.example.com".to_owned(),
+            credentials: None,
+        });
+
+        let client = duck.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "https://duck.example.com/builds")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/builds.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        duck.collect(Arc::new(EventWaitHandle::new()), &mut |build: Build| {
+            // Store the results
+            result.push(build);
+        })
+        .unwrap();
+
+        // Then
+        assert_eq!(BuildProvider::AzureDevOps, result[2].provider);
+        assert_eq!(Some("azure".to_owned()), result[2].origin);
+    }
+
+    #[test]
+    fn should_preserve_commit_information() {
+        // Given
+        let duck = DuckCollector::<MockHttpClient>::new(&DuckConfiguration {
+            id: "office".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            server_url: "https://duck.example.com".to_owned(),
+            credentials: None,
+        });
+
+        let client = duck.get_client();
+
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "https://duck.example.com/builds")
+                .returns_status(StatusCode::OK)
+                .returns_body(include_str!("test_data/builds.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        duck.collect(Arc::new(EventWaitHandle::new()), &mut |build: Build| {
+            // Store the results
+            result.push(build);
+        })
+        .unwrap();
+
+        // Then
+        assert_eq!(None, result[0].commit);
+        assert_eq!(None, result[0].trigger);
+        assert_eq!(
+            Some("677c1482e734f5df9d5a59209598703e879063e7".to_owned()),
+            result[1].commit
+        );
+        assert_eq!(Some("Add foo".to_owned()), result[1].commit_message);
+        assert_eq!(Some("Patrik Svensson".to_owned()), result[1].author);
+        assert_eq!(Some("GitHub".to_owned()), result[1].committer);
+        assert_eq!(Some(BuildTrigger::PullRequest), result[1].trigger);
+        assert_eq!(Some(1573405940), result[1].queued_at);
+    }
+}
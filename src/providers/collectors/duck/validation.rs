@@ -0,0 +1,131 @@
+use url::Url;
+
+use crate::config::{DuckConfiguration, DuckCredentials, Validate};
+use crate::utils::DuckResult;
+
+impl Validate for DuckConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if let Some(credentials) = &self.credentials {
+            credentials.validate()?;
+        }
+
+        if self.id.is_empty() {
+            return Err(format_err!("Duck collector have no ID."));
+        }
+        if let Err(e) = Url::parse(&self.server_url[..]) {
+            return Err(format_err!("Duck server URL is invalid: {}", e));
+        }
+
+        Ok(())
+    }
+}
+
+impl Validate for DuckCredentials {
+    fn validate(&self) -> DuckResult<()> {
+        match self {
+            DuckCredentials::Anonymous => {}
+            DuckCredentials::Basic {
+                username,
+                password: _,
+            } => {
+                if username.is_empty() {
+                    return Err(format_err!("Duck username is empty."));
+                }
+            }
+            DuckCredentials::Token(token) => {
+                if token.is_empty() {
+                    return Err(format_err!("Duck token is empty."));
+                }
+            }
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers::DuckProviderCollection;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "Duck server URL is invalid: relative URL without a base")]
+    fn should_return_error_if_server_url_is_empty_or_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "duck": {
+                            "id": "office",
+                            "serverUrl": ""
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Duck token is empty.")]
+    fn should_return_error_if_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "duck": {
+                            "id": "office",
+                            "serverUrl": "https://duck.example.com",
+                            "credentials": {
+                                "token": ""
+                            }
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Duck username is empty.")]
+    fn should_return_error_if_username_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "duck": {
+                            "id": "office",
+                            "serverUrl": "https://duck.example.com",
+                            "credentials": {
+                                "basic": {
+                                    "username": "",
+                                    "password": "hunter2"
+                                }
+                            }
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+}
@@ -0,0 +1,347 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use log::{trace, warn};
+use waithandle::EventWaitHandle;
+
+use crate::builds::{Build, BuildBuilder, BuildProvider};
+use crate::config::FileConfiguration;
+use crate::providers::collectors::document::BuildDocument;
+use crate::providers::collectors::{BranchFilter, Collector, CollectorInfo};
+use crate::utils::DuckResult;
+
+mod validation;
+
+pub struct FileCollector {
+    paths: Vec<PathBuf>,
+    info: CollectorInfo,
+}
+
+impl FileCollector {
+    pub fn new(config: &FileConfiguration) -> Self {
+        FileCollector {
+            paths: config.paths.iter().map(PathBuf::from).collect(),
+            info: CollectorInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    Option::None => true,
+                    Option::Some(e) => e,
+                },
+                provider: BuildProvider::File,
+                branches: BranchFilter::new(config.branch_filter.as_ref()),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+        }
+    }
+
+    fn get_files(&self) -> Vec<PathBuf> {
+        let mut result = Vec::<PathBuf>::new();
+        for path in self.paths.iter() {
+            if path.is_dir() {
+                let entries = match std::fs::read_dir(path) {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        warn!("Could not read directory '{}'. {}", path.display(), e);
+                        continue;
+                    }
+                };
+                let mut files: Vec<PathBuf> = entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.is_file() && get_format(p).is_some())
+                    .collect();
+                files.sort();
+                result.append(&mut files);
+            } else if path.is_file() {
+                result.push(path.clone());
+            } else {
+                warn!("The path '{}' does not exist.", path.display());
+            }
+        }
+        result
+    }
+
+    fn read_file(&self, path: &Path) -> DuckResult<Vec<BuildDocument>> {
+        let content = std::fs::read_to_string(path)?;
+        match get_format(path) {
+            Some(FileFormat::JsonLines) => {
+                let mut result = Vec::<BuildDocument>::new();
+                for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                    result.push(serde_json::from_str(line)?);
+                }
+                Ok(result)
+            }
+            _ => Ok(match serde_json::from_str::<FileDocument>(&content)? {
+                FileDocument::Many(builds) => builds,
+                FileDocument::Wrapped { builds } => builds,
+                FileDocument::Single(build) => vec![build],
+            }),
+        }
+    }
+}
+
+impl Collector for FileCollector {
+    fn info(&self) -> &CollectorInfo {
+        &self.info
+    }
+
+    fn collect(&self, _: Arc<EventWaitHandle>, callback: &mut dyn FnMut(Build)) -> DuckResult<()> {
+        for path in self.get_files() {
+            trace!("Reading builds from '{}'...", path.display());
+            let documents = match self.read_file(&path) {
+                Ok(documents) => documents,
+                Err(e) => {
+                    warn!("Could not read builds from '{}'. {}", path.display(), e);
+                    continue;
+                }
+            };
+
+            // Builds without an explicit time get the
+            // time when the file was last modified.
+            let modified = match get_modified(&path) {
+                Ok(modified) => modified,
+                Err(e) => {
+                    warn!("Could not read metadata for '{}'. {}", path.display(), e);
+                    continue;
+                }
+            };
+
+            for document in documents.iter() {
+                let (project, definition) = match (&document.project, &document.definition) {
+                    (Some(project), Some(definition)) => (project, definition),
+                    _ => {
+                        warn!(
+                            "A build in '{}' is missing a project or definition.",
+                            path.display()
+                        );
+                        continue;
+                    }
+                };
+
+                let status = document.get_status();
+                let build_number = match &document.build_number {
+                    Option::None => "latest".to_string(),
+                    Option::Some(number) => number.clone(),
+                };
+                callback(
+                    BuildBuilder::new()
+                        .build_id(match &document.build_id {
+                            Option::None => build_number.clone(),
+                            Option::Some(id) => id.clone(),
+                        })
+                        .provider(BuildProvider::File)
+                        .collector(&self.info.id)
+                        .project_id(project)
+                        .project_name(project)
+                        .definition_id(definition)
+                        .definition_name(definition)
+                        .build_number(build_number)
+                        .branch(match &document.branch {
+                            Option::None => "default".to_string(),
+                            Option::Some(branch) => branch.clone(),
+                        })
+                        .url(document.url.clone().unwrap_or_default())
+                        .started_at(document.started_at.unwrap_or(modified))
+                        .finished_at(if status.is_pending() {
+                            None
+                        } else {
+                            Some(document.finished_at.unwrap_or(modified))
+                        })
+                        .status(status)
+                        .build()
+                        .unwrap(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+enum FileFormat {
+    Json,
+    JsonLines,
+}
+
+fn get_format(path: &Path) -> Option<FileFormat> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => Some(FileFormat::Json),
+        Some("jsonl") | Some("ndjson") => Some(FileFormat::JsonLines),
+        _ => None,
+    }
+}
+
+fn get_modified(path: &Path) -> DuckResult<i64> {
+    Ok(std::fs::metadata(path)?
+        .modified()?
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs() as i64)
+}
+
+/// A JSON file can contain a list of builds, an object
+/// with a list of builds or a single build.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FileDocument {
+    Many(Vec<BuildDocument>),
+    Wrapped { builds: Vec<BuildDocument> },
+    Single(BuildDocument),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildStatus;
+
+    #[test]
+    fn should_read_builds_from_json_file() {
+        // Given
+        let collector = FileCollector::new(&FileConfiguration {
+            id: "file".to_string(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            paths: vec![Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("src/providers/collectors/file/test_data/builds.json")
+                .to_str()
+                .unwrap()
+                .to_string()],
+        });
+
+        // When
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(2, result.len());
+        assert_eq!("1234", result[0].build_id);
+        assert_eq!(BuildProvider::File, result[0].provider);
+        assert_eq!("file", result[0].collector);
+        assert_eq!("Firmware", result[0].project_name);
+        assert_eq!("Nightly", result[0].definition_name);
+        assert_eq!("117", result[0].build_number);
+        assert_eq!("develop", result[0].branch);
+        assert_eq!(BuildStatus::Success, result[0].status);
+        assert_eq!("https://builds.example.com/1234", result[0].url);
+        assert_eq!(1580589796, result[0].started_at);
+        assert_eq!(1580590796, result[0].finished_at.unwrap());
+
+        assert_eq!(BuildStatus::Running, result[1].status);
+        assert_eq!("latest", result[1].build_number);
+        assert_eq!("default", result[1].branch);
+        assert_eq!(None, result[1].finished_at);
+    }
+
+    #[test]
+    fn should_read_builds_from_json_lines_file() {
+        // Given
+        let collector = FileCollector::new(&FileConfiguration {
+            id: "file".to_string(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            paths: vec![Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("src/providers/collectors/file/test_data/builds.jsonl")
+                .to_str()
+                .unwrap()
+                .to_string()],
+        });
+
+        // When
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(2, result.len());
+        assert_eq!("Installer", result[0].definition_name);
+        assert_eq!(BuildStatus::Failed, result[0].status);
+        assert_eq!("Documentation", result[1].definition_name);
+        assert_eq!(BuildStatus::Canceled, result[1].status);
+    }
+
+    #[test]
+    fn should_read_all_files_in_directory() {
+        // Given
+        let collector = FileCollector::new(&FileConfiguration {
+            id: "file".to_string(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            paths: vec![Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("src/providers/collectors/file/test_data")
+                .to_str()
+                .unwrap()
+                .to_string()],
+        });
+
+        // When
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(5, result.len());
+    }
+
+    #[test]
+    fn should_skip_paths_that_do_not_exist() {
+        // Given
+        let collector = FileCollector::new(&FileConfiguration {
+            id: "file".to_string(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            paths: vec![
+                Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("src/providers/collectors/file/test_data/missing.json")
+                    .to_str()
+                    .unwrap()
+                    .to_string(),
+                Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("src/providers/collectors/file/test_data/single.json")
+                    .to_str()
+                    .unwrap()
+                    .to_string(),
+            ],
+        });
+
+        // When
+        let mut result = Vec::<Build>::new();
+        collector
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    // Store the results
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!("Backup", result[0].definition_name);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::config::{FileConfiguration, Validate};
+use crate::utils::DuckResult;
+
+impl Validate for FileConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if self.id.is_empty() {
+            return Err(format_err!("File collector have no ID."));
+        }
+        if self.paths.is_empty() {
+            return Err(format_err!(
+                "File collector '{}' have no configured paths.",
+                self.id
+            ));
+        }
+        if self.paths.iter().any(|p| p.is_empty()) {
+            return Err(format_err!(
+                "File collector '{}' contains an empty path.",
+                self.id
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers::DuckProviderCollection;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "have no configured paths.")]
+    fn should_return_error_if_there_are_no_paths() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "file": {
+                            "id": "file",
+                            "paths": [ ]
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "contains an empty path.")]
+    fn should_return_error_if_path_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ 
+                    {
+                        "file": {
+                            "id": "file",
+                            "paths": [ "/data/builds", "" ]
+                        }
+                    }
+                ] 
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_collectors(&config).unwrap();
+    }
+}
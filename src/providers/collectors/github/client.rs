@@ -1,6 +1,6 @@
 use log::trace;
 
-use crate::builds::BuildStatus;
+use crate::builds::{BuildPullRequest, BuildStage, BuildStatus, BuildTrigger};
 use crate::config::{GitHubConfiguration, GitHubCredentials};
 use crate::utils::date;
 use crate::utils::http::*;
@@ -88,6 +88,64 @@ impl GitHubClient {
         // Deserialize and return the value.
         Ok(serde_json::from_str(&body[..])?)
     }
+
+    pub fn get_jobs(&self, client: &impl HttpClient, run: u64) -> DuckResult<GitHubJobsResponse> {
+        let url = format!(
+            "https://api.github.com/repos/{owner}/{repo}/actions/runs/{run}/jobs",
+            owner = self.owner,
+            repo = self.repository,
+            run = run
+        );
+
+        trace!("Sending request to: {}", url);
+        let mut builder = HttpRequestBuilder::get(&url);
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+        self.credentials.authenticate(&mut builder);
+
+        let mut response = client.send(&builder)?;
+
+        trace!("Received response: {}", response.status());
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Received non 200 HTTP status code. ({})",
+                response.status()
+            ));
+        }
+
+        response.deserialize_json()
+    }
+
+    pub fn get_annotations(
+        &self,
+        client: &impl HttpClient,
+        check_run: u64,
+    ) -> DuckResult<Vec<GitHubAnnotation>> {
+        let url = format!(
+            "https://api.github.com/repos/{owner}/{repo}/check-runs/{id}/annotations",
+            owner = self.owner,
+            repo = self.repository,
+            id = check_run
+        );
+
+        trace!("Sending request to: {}", url);
+        let mut builder = HttpRequestBuilder::get(&url);
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+        self.credentials.authenticate(&mut builder);
+
+        let mut response = client.send(&builder)?;
+
+        trace!("Received response: {}", response.status());
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Received non 200 HTTP status code. ({})",
+                response.status()
+            ));
+        }
+
+        response.deserialize_json()
+    }
 }
 
 impl GitHubCredentials {
@@ -118,31 +176,135 @@ pub struct GitHubWorkflowRun {
     pub html_url: String,
     pub created_at: String,
     pub updated_at: String,
+    #[serde(default)]
+    pub run_started_at: Option<String>,
+    #[serde(default)]
+    pub event: Option<String>,
+    #[serde(default)]
+    pub head_sha: Option<String>,
+    #[serde(default)]
+    pub head_commit: Option<GitHubCommit>,
+    #[serde(default)]
+    pub display_title: Option<String>,
+    #[serde(default)]
+    pub pull_requests: Vec<GitHubPullRequest>,
+    #[serde(default)]
+    pub head_repository: Option<GitHubRepository>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitHubPullRequest {
+    pub number: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitHubRepository {
+    pub full_name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitHubCommit {
+    pub message: String,
+    pub author: Option<GitHubCommitUser>,
+    pub committer: Option<GitHubCommitUser>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitHubCommitUser {
+    pub name: String,
 }
 
 impl GitHubWorkflowRun {
     pub fn get_status(&self) -> DuckResult<BuildStatus> {
-        match &self.status[..] {
-            "completed" => match &self.conclusion {
-                None => Err(format_err!("Build is completed without conclusion.")),
-                Some(conclusion) => match &conclusion[..] {
-                    "success" => Ok(BuildStatus::Success),
-                    "cancelled" => Ok(BuildStatus::Canceled),
-                    "failure" => Ok(BuildStatus::Failed),
-                    _ => Ok(BuildStatus::Failed),
-                },
-            },
-            "queued" => Ok(BuildStatus::Running),
-            "in_progress" => Ok(BuildStatus::Running),
-            status => Err(format_err!("Unknown build status '{}'", status)),
+        get_status(&self.status, &self.conclusion)
+    }
+
+    pub fn get_trigger(&self) -> Option<BuildTrigger> {
+        match self.event.as_ref().map(|e| &e[..]) {
+            Some("push") => Some(BuildTrigger::Push),
+            Some("pull_request") | Some("pull_request_target") => Some(BuildTrigger::PullRequest),
+            Some("schedule") => Some(BuildTrigger::Schedule),
+            Some("workflow_dispatch") => Some(BuildTrigger::Manual),
+            _ => None,
         }
     }
 
-    pub fn get_started_timestamp(&self) -> DuckResult<i64> {
+    /// Gets the pull request that the workflow run was triggered by.
+    ///
+    /// GitHub doesn't list pull requests from forks, or pull requests
+    /// that have been closed, so the number isn't always known.
+    pub fn get_pull_request(&self, owner: &str, repository: &str) -> Option<BuildPullRequest> {
+        let number = match self.pull_requests.first() {
+            Some(pull_request) => Some(pull_request.number),
+            None => BuildPullRequest::from_branch(&self.branch).and_then(|pr| pr.number),
+        };
+        if number.is_none() && self.get_trigger() != Some(BuildTrigger::PullRequest) {
+            return None;
+        }
+        Some(BuildPullRequest {
+            number,
+            title: self.display_title.clone(),
+            url: number.map(|number| {
+                format!(
+                    "https://github.com/{}/{}/pull/{}",
+                    owner, repository, number
+                )
+            }),
+        })
+    }
+
+    /// Gets the branch that was built. Branches in forks are prefixed
+    /// with the owner of the fork, the same way that GitHub does it.
+    pub fn get_branch(&self, owner: &str, repository: &str) -> String {
+        match &self.head_repository {
+            Some(head)
+                if !head
+                    .full_name
+                    .eq_ignore_ascii_case(&format!("{}/{}", owner, repository)) =>
+            {
+                let fork_owner = head.full_name.split('/').next().unwrap_or_default();
+                format!("{}:{}", fork_owner, self.branch)
+            }
+            _ => self.branch.clone(),
+        }
+    }
+
+    pub fn get_commit_message(&self) -> Option<String> {
+        self.head_commit.as_ref().map(|c| c.message.clone())
+    }
+
+    pub fn get_author(&self) -> Option<String> {
+        match &self.head_commit {
+            Some(GitHubCommit {
+                author: Some(author),
+                ..
+            }) => Some(author.name.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn get_committer(&self) -> Option<String> {
+        match &self.head_commit {
+            Some(GitHubCommit {
+                committer: Some(committer),
+                ..
+            }) => Some(committer.name.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn get_queued_timestamp(&self) -> DuckResult<i64> {
         let result = date::to_timestamp(&self.created_at, date::GITHUB_FORMAT)?;
         Ok(result)
     }
 
+    pub fn get_started_timestamp(&self) -> DuckResult<i64> {
+        // Older GitHub Enterprise versions don't report when the run started.
+        let started_at = self.run_started_at.as_ref().unwrap_or(&self.created_at);
+        let result = date::to_timestamp(started_at, date::GITHUB_FORMAT)?;
+        Ok(result)
+    }
+
     pub fn get_finished_timestamp(&self) -> DuckResult<Option<i64>> {
         if self.status == "completed" {
             let result = date::to_timestamp(&self.updated_at, date::GITHUB_FORMAT)?;
@@ -152,3 +314,108 @@ impl GitHubWorkflowRun {
         }
     }
 }
+
+#[derive(Deserialize, Debug)]
+pub struct GitHubJobsResponse {
+    pub jobs: Vec<GitHubJob>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitHubJob {
+    pub id: u64,
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub html_url: Option<String>,
+    pub started_at: Option<String>,
+    pub completed_at: Option<String>,
+}
+
+impl GitHubJob {
+    pub fn get_stage(&self) -> DuckResult<BuildStage> {
+        Ok(BuildStage {
+            name: self.name.clone(),
+            status: get_status(&self.status, &self.conclusion)?,
+            started_at: match &self.started_at {
+                Some(value) => Some(date::to_timestamp(value, date::GITHUB_FORMAT)?),
+                None => None,
+            },
+            finished_at: match &self.completed_at {
+                Some(value) => Some(date::to_timestamp(value, date::GITHUB_FORMAT)?),
+                None => None,
+            },
+            url: self.html_url.clone(),
+        })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitHubAnnotation {
+    pub annotation_level: String,
+    pub message: String,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+impl GitHubAnnotation {
+    pub fn is_failure(&self) -> bool {
+        self.annotation_level == "failure"
+    }
+
+    /// Gets the name of the failure, which for test
+    /// reporters usually is the name of the failing test.
+    pub fn get_name(&self) -> String {
+        match &self.title {
+            Some(title) if !title.is_empty() => title.clone(),
+            _ => self.message.lines().next().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+/// Gets the build status for a workflow run or job.
+fn get_status(status: &str, conclusion: &Option<String>) -> DuckResult<BuildStatus> {
+    match status {
+        "completed" => match conclusion {
+            None => Err(format_err!("Build is completed without conclusion.")),
+            Some(conclusion) => match &conclusion[..] {
+                "success" => Ok(BuildStatus::Success),
+                "cancelled" => Ok(BuildStatus::Canceled),
+                "failure" => Ok(BuildStatus::Failed),
+                "skipped" => Ok(BuildStatus::Skipped),
+                "action_required" => Ok(BuildStatus::Blocked),
+                _ => Ok(BuildStatus::Failed),
+            },
+        },
+        "queued" | "requested" | "pending" => Ok(BuildStatus::Queued),
+        "waiting" => Ok(BuildStatus::Blocked),
+        "in_progress" => Ok(BuildStatus::Running),
+        status => Err(format_err!("Unknown build status '{}'", status)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("completed", Some("success"), BuildStatus::Success ; "Success")]
+    #[test_case("completed", Some("cancelled"), BuildStatus::Canceled ; "Cancelled")]
+    #[test_case("completed", Some("failure"), BuildStatus::Failed ; "Failure")]
+    #[test_case("completed", Some("timed_out"), BuildStatus::Failed ; "Timed out")]
+    #[test_case("completed", Some("skipped"), BuildStatus::Skipped ; "Skipped")]
+    #[test_case("completed", Some("action_required"), BuildStatus::Blocked ; "Action required")]
+    #[test_case("queued", None, BuildStatus::Queued ; "Queued")]
+    #[test_case("requested", None, BuildStatus::Queued ; "Requested")]
+    #[test_case("pending", None, BuildStatus::Queued ; "Pending")]
+    #[test_case("waiting", None, BuildStatus::Blocked ; "Waiting")]
+    #[test_case("in_progress", None, BuildStatus::Running ; "In progress")]
+    fn should_get_status(status: &str, conclusion: Option<&str>, expected: BuildStatus) {
+        let conclusion = conclusion.map(|c| c.to_string());
+        assert_eq!(expected, get_status(status, &conclusion).unwrap());
+    }
+
+    #[test]
+    fn should_return_error_for_unknown_status() {
+        assert!(get_status("foo", &None).is_err());
+    }
+}
@@ -1,10 +1,14 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
+use log::warn;
 use waithandle::EventWaitHandle;
 
-use crate::builds::{Build, BuildBuilder, BuildProvider};
+use crate::builds::{
+    Build, BuildBuilder, BuildProvider, BuildStage, BuildStatus, BuildTests, MAX_FAILED_TESTS,
+};
 use crate::config::GitHubConfiguration;
-use crate::providers::collectors::{Collector, CollectorInfo};
+use crate::providers::collectors::{BranchFilter, Collector, CollectorInfo};
 use crate::utils::http::HttpClient;
 use crate::utils::DuckResult;
 
@@ -13,10 +17,15 @@ use self::client::GitHubClient;
 mod client;
 mod validation;
 
+/// The jobs and tests for a workflow run.
+type RunDetails = (Vec<BuildStage>, Option<BuildTests>);
+
 pub struct GitHubCollector<T: HttpClient + Default> {
     client: GitHubClient,
     http: T,
     info: CollectorInfo,
+    /// Jobs and tests for finished workflow runs, which will never change.
+    details: Mutex<HashMap<u64, RunDetails>>,
 }
 
 impl<T: HttpClient + Default> GitHubCollector<T> {
@@ -31,7 +40,10 @@ impl<T: HttpClient + Default> GitHubCollector<T> {
                     Option::Some(e) => e,
                 },
                 provider: BuildProvider::AzureDevOps,
+                branches: BranchFilter::new(config.branch_filter.as_ref()),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
             },
+            details: Mutex::new(HashMap::new()),
         };
     }
 
@@ -39,6 +51,52 @@ impl<T: HttpClient + Default> GitHubCollector<T> {
     pub fn get_client(&self) -> &T {
         &self.http
     }
+
+    fn get_details(&self, build: &Build) -> DuckResult<RunDetails> {
+        let id: u64 = build.build_id.parse()?;
+        if let Some(details) = self.details.lock().unwrap().get(&id) {
+            return Ok(details.clone());
+        }
+
+        let mut stages = Vec::new();
+        let mut failures = Vec::new();
+        for job in self.client.get_jobs(&self.http, id)?.jobs.iter() {
+            let stage = job.get_stage()?;
+            if stage.status == BuildStatus::Failed {
+                // Test reporters add an annotation for every failed test,
+                // so that is the closest thing to test results we have.
+                match self.client.get_annotations(&self.http, job.id) {
+                    Ok(annotations) => failures.extend(
+                        annotations
+                            .iter()
+                            .filter(|a| a.is_failure())
+                            .map(|a| a.get_name()),
+                    ),
+                    Err(e) => warn!("Could not get annotations for job {}. {}", job.id, e),
+                };
+            }
+            stages.push(stage);
+        }
+
+        let tests = if failures.is_empty() {
+            None
+        } else {
+            Some(BuildTests {
+                failed: failures.len() as u32,
+                failures: failures.into_iter().take(MAX_FAILED_TESTS).collect(),
+                ..Default::default()
+            })
+        };
+
+        if !build.status.is_pending() {
+            self.details
+                .lock()
+                .unwrap()
+                .insert(id, (stages.clone(), tests.clone()));
+        }
+
+        Ok((stages, tests))
+    }
 }
 
 impl<T: HttpClient + Default> Collector for GitHubCollector<T> {
@@ -76,7 +134,14 @@ impl<T: HttpClient + Default> Collector for GitHubCollector<T> {
                     .url(&run.html_url)
                     .started_at(run.get_started_timestamp()?)
                     .finished_at(run.get_finished_timestamp()?)
-                    .branch(&run.branch)
+                    .branch(run.get_branch(&self.client.owner, &self.client.repository))
+                    .commit(run.head_sha.clone())
+                    .commit_message(run.get_commit_message())
+                    .author(run.get_author())
+                    .committer(run.get_committer())
+                    .trigger(run.get_trigger())
+                    .queued_at(Some(run.get_queued_timestamp()?))
+                    .pull_request(run.get_pull_request(&self.client.owner, &self.client.repository))
                     .build()
                     .unwrap(),
             );
@@ -85,15 +150,37 @@ impl<T: HttpClient + Default> Collector for GitHubCollector<T> {
         // Sort the builds by date.
         builds.sort_by(|a, b| b.started_at.cmp(&a.started_at));
 
-        // Now only keep the latest ones of the
-        let mut branches = std::collections::HashSet::<&String>::new();
+        // Now only keep the latest ones of the branch,
+        // keeping pull request builds apart from the rest.
+        let mut branches = std::collections::HashSet::<(&String, bool)>::new();
         for build in builds.iter() {
-            if !branches.contains(&build.branch) {
-                branches.insert(&build.branch);
-                callback(build.clone()); // Really want to get rid of this clone...
+            let key = (&build.branch, build.pull_request.is_some());
+            if !branches.contains(&key) {
+                branches.insert(key);
+
+                let mut build = build.clone(); // Really want to get rid of this clone...
+                match self.get_details(&build) {
+                    Ok((stages, tests)) => {
+                        build.stages = Some(stages);
+                        build.tests = tests;
+                    }
+                    Err(e) => warn!(
+                        "Could not get jobs for workflow run {}. {}",
+                        build.build_id, e
+                    ),
+                };
+
+                callback(build);
             }
         }
 
+        // Forget about jobs for workflow runs that are gone.
+        let ids: Vec<u64> = response.workflow_runs.iter().map(|r| r.id).collect();
+        self.details
+            .lock()
+            .unwrap()
+            .retain(|id, _| ids.contains(id));
+
         Ok(())
     }
 }
@@ -101,7 +188,7 @@ impl<T: HttpClient + Default> Collector for GitHubCollector<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::builds::BuildStatus;
+    use crate::builds::{BuildPullRequest, BuildStatus, BuildTrigger};
     use crate::config::*;
     use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
     use reqwest::StatusCode;
@@ -112,6 +199,8 @@ mod tests {
         let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
             id: "github".to_owned(),
             enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
             owner: "spectresystems".to_owned(),
             repository: "duck".to_owned(),
             workflow: "pull_request.yml".to_owned(),
@@ -163,5 +252,315 @@ mod tests {
         );
         assert_eq!(1580601850, result[0].started_at);
         assert_eq!(1580602219, result[0].finished_at.unwrap());
+        assert_eq!(
+            "677c1482e734f5df9d5a59209598703e879063e7",
+            result[0].commit.as_ref().unwrap()
+        );
+        assert!(result[0]
+            .commit_message
+            .as_ref()
+            .unwrap()
+            .starts_with("Remove anonymous auth for GitHub"));
+        assert_eq!("Patrik Svensson", result[0].author.as_ref().unwrap());
+        assert_eq!("Patrik Svensson", result[0].committer.as_ref().unwrap());
+        assert_eq!(Some(BuildTrigger::PullRequest), result[0].trigger);
+        assert_eq!(Some(369), result[0].duration());
+        assert_eq!(
+            Some(BuildPullRequest {
+                number: Some(33),
+                title: None,
+                url: Some("https://github.com/spectresystems/duck/pull/33".to_string()),
+            }),
+            result[0].pull_request
+        );
+        assert_eq!(
+            Some(BuildPullRequest {
+                number: None,
+                title: None,
+                url: None,
+            }),
+            result[1].pull_request
+        );
+    }
+
+    #[test]
+    fn should_detect_pull_requests_that_github_does_not_list() {
+        // Given
+        let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+            id: "github".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            owner: "spectresystems".to_owned(),
+            repository: "duck".to_owned(),
+            workflow: "pull_request.yml".to_owned(),
+            credentials: GitHubCredentials::Basic {
+                username: "foo".to_owned(),
+                password: "lol".to_owned(),
+            },
+        });
+
+        let client = github.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://api.github.com/repos/spectresystems/duck/actions/workflows/pull_request.yml/runs?page=0&per_page=25"
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(r#"{
+                "total_count": 3,
+                "workflow_runs": [
+                    {
+                        "id": 3, "head_branch": "master", "run_number": 3, "event": "pull_request",
+                        "status": "completed", "conclusion": "failure",
+                        "html_url": "https://github.com/spectresystems/duck/actions/runs/3",
+                        "created_at": "2020-02-02T00:03:00Z", "updated_at": "2020-02-02T00:04:00Z",
+                        "display_title": "Fix typo", "pull_requests": [],
+                        "head_repository": { "full_name": "octocat/duck" }
+                    },
+                    {
+                        "id": 2, "head_branch": "feature/foo", "run_number": 2, "event": "pull_request_target",
+                        "status": "completed", "conclusion": "success",
+                        "html_url": "https://github.com/spectresystems/duck/actions/runs/2",
+                        "created_at": "2020-02-02T00:02:00Z", "updated_at": "2020-02-02T00:03:00Z",
+                        "pull_requests": [],
+                        "head_repository": { "full_name": "spectresystems/duck" }
+                    },
+                    {
+                        "id": 1, "head_branch": "master", "run_number": 1, "event": "push",
+                        "status": "completed", "conclusion": "success",
+                        "html_url": "https://github.com/spectresystems/duck/actions/runs/1",
+                        "created_at": "2020-02-02T00:01:00Z", "updated_at": "2020-02-02T00:02:00Z",
+                        "pull_requests": [],
+                        "head_repository": { "full_name": "spectresystems/duck" }
+                    }
+                ]
+            }"#)
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        github
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(3, result.len());
+        // Pull request from a fork
+        assert_eq!("octocat:master", result[0].branch);
+        assert_eq!(
+            Some(BuildPullRequest {
+                number: None,
+                title: Some("Fix typo".to_owned()),
+                url: None,
+            }),
+            result[0].pull_request
+        );
+        // Pull request target
+        assert_eq!("feature/foo", result[1].branch);
+        assert_eq!(Some(BuildTrigger::PullRequest), result[1].trigger);
+        assert!(result[1].pull_request.is_some());
+        // Push to the base branch
+        assert_eq!("master", result[2].branch);
+        assert_eq!(None, result[2].pull_request);
+    }
+
+    #[test]
+    fn should_get_jobs_as_stages() {
+        // Given
+        let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+            id: "github".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            owner: "spectresystems".to_owned(),
+            repository: "duck".to_owned(),
+            workflow: "pull_request.yml".to_owned(),
+            credentials: GitHubCredentials::Basic {
+                username: "foo".to_owned(),
+                password: "lol".to_owned(),
+            },
+        });
+
+        let client = github.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://api.github.com/repos/spectresystems/duck/actions/workflows/pull_request.yml/runs?page=0&per_page=25"
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/builds.json"))
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://api.github.com/repos/spectresystems/duck/actions/runs/33801182/jobs",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/jobs.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        for _ in 0..2 {
+            github
+                .collect(
+                    Arc::new(waithandle::EventWaitHandle::new()),
+                    &mut |build: Build| {
+                        result.push(build);
+                    },
+                )
+                .unwrap();
+        }
+
+        // Then
+        let stages = result[0].stages.as_ref().unwrap();
+        assert_eq!(2, stages.len());
+        assert_eq!("Build frontend", stages[0].name);
+        assert_eq!(BuildStatus::Success, stages[0].status);
+        assert_eq!(Some(1580601855), stages[0].started_at);
+        assert_eq!(Some(1580602022), stages[0].finished_at);
+        assert_eq!(
+            Some("https://github.com/spectresystems/duck/runs/399444496".to_owned()),
+            stages[0].url
+        );
+        assert_eq!(BuildStatus::Failed, stages[1].status);
+        assert_eq!(vec![&stages[1]], result[0].get_failed_stages());
+
+        // The jobs for the finished run should only be fetched once.
+        let requests = client
+            .get_sent_requests()
+            .into_iter()
+            .filter(|r| r.url.ends_with("/runs/33801182/jobs"))
+            .count();
+        assert_eq!(1, requests);
+        assert_eq!(result[0].stages, result[4].stages);
+    }
+
+    #[test]
+    fn should_get_failed_tests_from_annotations() {
+        // Given
+        let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+            id: "github".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            owner: "spectresystems".to_owned(),
+            repository: "duck".to_owned(),
+            workflow: "pull_request.yml".to_owned(),
+            credentials: GitHubCredentials::Basic {
+                username: "foo".to_owned(),
+                password: "lol".to_owned(),
+            },
+        });
+
+        let client = github.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://api.github.com/repos/spectresystems/duck/actions/workflows/pull_request.yml/runs?page=0&per_page=25"
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/builds.json"))
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://api.github.com/repos/spectresystems/duck/actions/runs/33801182/jobs",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/jobs.json")),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://api.github.com/repos/spectresystems/duck/check-runs/399444497/annotations",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(include_str!("test_data/annotations.json")),
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        github
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        let tests = result[0].tests.as_ref().unwrap();
+        assert_eq!(2, tests.failed);
+        assert_eq!(None, tests.total);
+        assert_eq!(
+            vec![
+                "builds::tests::should_get_summary".to_owned(),
+                "Process completed with exit code 101.".to_owned()
+            ],
+            tests.failures
+        );
+    }
+
+    #[test]
+    fn should_get_queued_time_from_when_the_run_was_created() {
+        // Given
+        let github = GitHubCollector::<MockHttpClient>::new(&GitHubConfiguration {
+            id: "github".to_owned(),
+            enabled: Some(true),
+            branch_filter: None,
+            pull_requests: None,
+            owner: "spectresystems".to_owned(),
+            repository: "duck".to_owned(),
+            workflow: "pull_request.yml".to_owned(),
+            credentials: GitHubCredentials::Basic {
+                username: "foo".to_owned(),
+                password: "lol".to_owned(),
+            },
+        });
+
+        let client = github.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://api.github.com/repos/spectresystems/duck/actions/workflows/pull_request.yml/runs?page=0&per_page=25"
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(r#"{
+                "total_count": 1,
+                "workflow_runs": [
+                    {
+                        "id": 1, "head_branch": "master", "run_number": 1, "event": "push",
+                        "status": "completed", "conclusion": "success",
+                        "html_url": "https://github.com/spectresystems/duck/actions/runs/1",
+                        "created_at": "2020-02-02T00:01:00Z", "run_started_at": "2020-02-02T00:01:30Z",
+                        "updated_at": "2020-02-02T00:02:00Z"
+                    }
+                ]
+            }"#)
+        );
+
+        // When
+        let mut result = Vec::<Build>::new();
+        github
+            .collect(
+                Arc::new(waithandle::EventWaitHandle::new()),
+                &mut |build: Build| {
+                    result.push(build);
+                },
+            )
+            .unwrap();
+
+        // Then
+        assert_eq!(1, result.len());
+        assert_eq!(Some(1580601660), result[0].queued_at);
+        assert_eq!(1580601690, result[0].started_at);
     }
 }
@@ -3,18 +3,31 @@ use std::sync::Arc;
 use waithandle::EventWaitHandle;
 
 use crate::builds::{Build, BuildProvider};
-use crate::config::{CollectorConfiguration, Configuration, Validate};
+use crate::config::{CollectorConfiguration, Configuration, PullRequestMode, Validate};
 use crate::utils::http::ReqwestClient;
 use crate::utils::DuckResult;
 
+use self::argocd::ArgoCdCollector;
 use self::azure::AzureDevOpsCollector;
+use self::azure_releases::AzureDevOpsReleasesCollector;
+use self::branches::BranchFilter;
+use self::command::CommandCollector;
+use self::duck::DuckCollector;
+use self::file::FileCollector;
 use self::github::GitHubCollector;
 use self::octopus::OctopusDeployCollector;
 use self::teamcity::TeamCityCollector;
 
 use super::DuckProvider;
 
+mod argocd;
 mod azure;
+mod azure_releases;
+pub mod branches;
+mod command;
+mod document;
+mod duck;
+mod file;
 mod github;
 mod octopus;
 mod teamcity;
@@ -32,6 +45,8 @@ pub struct CollectorInfo {
     pub id: String,
     pub enabled: bool,
     pub provider: BuildProvider,
+    pub branches: BranchFilter,
+    pub pull_requests: PullRequestMode,
 }
 
 pub struct TeamCityProvider {}
@@ -62,6 +77,22 @@ impl<'a> DuckProvider<'a> for AzureDevOpsProvider {
     }
 }
 
+pub struct AzureDevOpsReleasesProvider {}
+impl<'a> DuckProvider<'a> for AzureDevOpsReleasesProvider {
+    fn get_collectors(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Collector>>> {
+        let mut result = Vec::<Box<dyn Collector>>::new();
+        for item in config.collectors.iter() {
+            if let CollectorConfiguration::AzureReleases(c) = item {
+                c.validate()?;
+                result.push(Box::new(
+                    AzureDevOpsReleasesCollector::<ReqwestClient>::new(&c),
+                ));
+            }
+        }
+        return Ok(result);
+    }
+}
+
 pub struct GitHubProvider {}
 impl<'a> DuckProvider<'a> for GitHubProvider {
     fn get_collectors(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Collector>>> {
@@ -89,3 +120,59 @@ impl<'a> DuckProvider<'a> for OctopusDeployProvider {
         return Ok(result);
     }
 }
+
+pub struct ArgoCdProvider {}
+impl<'a> DuckProvider<'a> for ArgoCdProvider {
+    fn get_collectors(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Collector>>> {
+        let mut result = Vec::<Box<dyn Collector>>::new();
+        for item in config.collectors.iter() {
+            if let CollectorConfiguration::ArgoCd(c) = item {
+                c.validate()?;
+                result.push(Box::new(ArgoCdCollector::<ReqwestClient>::new(&c)));
+            }
+        }
+        return Ok(result);
+    }
+}
+
+pub struct CommandProvider {}
+impl<'a> DuckProvider<'a> for CommandProvider {
+    fn get_collectors(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Collector>>> {
+        let mut result = Vec::<Box<dyn Collector>>::new();
+        for item in config.collectors.iter() {
+            if let CollectorConfiguration::Command(c) = item {
+                c.validate()?;
+                result.push(Box::new(CommandCollector::new(&c)));
+            }
+        }
+        return Ok(result);
+    }
+}
+
+pub struct FileProvider {}
+impl<'a> DuckProvider<'a> for FileProvider {
+    fn get_collectors(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Collector>>> {
+        let mut result = Vec::<Box<dyn Collector>>::new();
+        for item in config.collectors.iter() {
+            if let CollectorConfiguration::File(c) = item {
+                c.validate()?;
+                result.push(Box::new(FileCollector::new(&c)));
+            }
+        }
+        return Ok(result);
+    }
+}
+
+pub struct DuckFederationProvider {}
+impl<'a> DuckProvider<'a> for DuckFederationProvider {
+    fn get_collectors(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Collector>>> {
+        let mut result = Vec::<Box<dyn Collector>>::new();
+        for item in config.collectors.iter() {
+            if let CollectorConfiguration::Duck(c) = item {
+                c.validate()?;
+                result.push(Box::new(DuckCollector::<ReqwestClient>::new(&c)));
+            }
+        }
+        return Ok(result);
+    }
+}
@@ -109,6 +109,8 @@ pub struct OctopusDeployment {
     pub start_time: Option<String>,
     #[serde(rename = "CompletedTime")]
     pub finish_time: Option<String>,
+    #[serde(rename = "HasPendingInterruptions", default)]
+    pub has_pending_interruptions: bool,
 }
 
 #[derive(Deserialize, Debug)]
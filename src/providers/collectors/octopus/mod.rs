@@ -34,6 +34,8 @@ impl OctopusDeployCollector {
                     Option::Some(e) => e,
                 },
                 provider: BuildProvider::OctopusDeploy,
+                branches: BranchFilter::new(config.branch_filter.as_ref()),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
             },
         }
     }
@@ -157,7 +159,10 @@ impl OctopusDeployment {
     pub fn get_status(&self) -> BuildStatus {
         match &self.status[..] {
             "Success" => BuildStatus::Success,
-            "Executing" | "Queued" | "Cancelling" | "Failed" | "" => BuildStatus::Running,
+            // Deployments waiting for manual intervention are still executing.
+            "Executing" if self.has_pending_interruptions => BuildStatus::Blocked,
+            "Executing" | "Cancelling" | "" => BuildStatus::Running,
+            "Queued" => BuildStatus::Queued,
             "Canceled" => BuildStatus::Canceled,
             _ => BuildStatus::Failed,
         }
@@ -172,3 +177,37 @@ impl OctopusDeployment {
         .unwrap_or_else(|| self.created_time.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("Success", false, BuildStatus::Success ; "Success")]
+    #[test_case("Executing", false, BuildStatus::Running ; "Executing")]
+    #[test_case("Executing", true, BuildStatus::Blocked ; "Waiting for intervention")]
+    #[test_case("Cancelling", false, BuildStatus::Running ; "Cancelling")]
+    #[test_case("Queued", false, BuildStatus::Queued ; "Queued")]
+    #[test_case("Canceled", false, BuildStatus::Canceled ; "Canceled")]
+    #[test_case("Failed", false, BuildStatus::Failed ; "Failed")]
+    #[test_case("TimedOut", false, BuildStatus::Failed ; "Timed out")]
+    fn should_get_deployment_status(state: &str, interruptions: bool, expected: BuildStatus) {
+        let deployment = OctopusDeployment {
+            id: "Deployments-1".to_string(),
+            project: "Projects-1".to_string(),
+            environment: "Environments-1".to_string(),
+            release_id: "Releases-1".to_string(),
+            release_version: "1.0.0".to_string(),
+            status: state.to_string(),
+            links: OctopusDeploymentLinks {
+                deployment: "/api/deployments/Deployments-1".to_string(),
+            },
+            created_time: "2020-01-29T13:49:54.512+00:00".to_string(),
+            queue_time: None,
+            start_time: None,
+            finish_time: None,
+            has_pending_interruptions: interruptions,
+        };
+        assert_eq!(expected, deployment.get_status());
+    }
+}
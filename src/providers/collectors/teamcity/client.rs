@@ -3,6 +3,7 @@ use reqwest::header::ACCEPT;
 use reqwest::{Client, ClientBuilder, RequestBuilder};
 use url::Url;
 
+use crate::builds::{BuildStage, BuildStatus, BuildTrigger};
 use crate::config::{TeamCityAuth, TeamCityConfiguration};
 use crate::utils::date;
 use crate::utils::DuckResult;
@@ -52,7 +53,10 @@ impl TeamCityClient {
         let mut response = self.send_get_request(format!(
             "{url}{authtype}/app/rest/buildTypes/id:{id}/branches?locator=default:any\
              &fields=count,branch(name,default,active,builds(build(id,number,running,status,\
-             branchName,webUrl,startDate,finishDate),count,$locator(running:any,canceled:any,count:1)))",
+             branchName,webUrl,startDate,finishDate,queuedDate,revisions(revision(version)),\
+             triggered(type),lastChanges(change(username,comment)),snapshot-dependencies(build(\
+             state,status,webUrl,startDate,finishDate,buildType(name))),\
+             testOccurrences(count,passed,failed,ignored)),count,$locator(running:any,canceled:any,count:1)))",
             url = self.url,
             authtype = self.credentials.get_auth_type(),
             id = build_type.id
@@ -63,6 +67,21 @@ impl TeamCityClient {
         Ok(result)
     }
 
+    pub fn get_failed_tests(&self, build: u32, count: usize) -> DuckResult<Vec<String>> {
+        let mut response = self.send_get_request(format!(
+            "{url}{authtype}/app/rest/testOccurrences?locator=build:(id:{id}),status:FAILURE,count:{count}\
+             &fields=testOccurrence(name)",
+            url = self.url,
+            authtype = self.credentials.get_auth_type(),
+            id = build,
+            count = count
+        ))?;
+
+        let result: TeamCityTestOccurrenceCollectionModel = response.json()?;
+
+        Ok(result.tests.into_iter().map(|t| t.name).collect())
+    }
+
     fn send_get_request(&self, url: String) -> DuckResult<reqwest::Response> {
         trace!("Sending request to: {}", url);
         let response = self.client.get(&url).header(ACCEPT, "application/json");
@@ -146,9 +165,179 @@ pub struct TeamCityBuildModel {
     pub started_at: String,
     #[serde(alias = "finishDate")]
     pub finished_at: Option<String>,
+    #[serde(default, alias = "queuedDate")]
+    pub queued_at: Option<String>,
+    #[serde(default)]
+    pub revisions: Option<TeamCityRevisionCollectionModel>,
+    #[serde(default)]
+    pub triggered: Option<TeamCityTriggeredModel>,
+    #[serde(default, alias = "lastChanges")]
+    pub last_changes: Option<TeamCityChangeCollectionModel>,
+    #[serde(default, alias = "snapshot-dependencies")]
+    pub dependencies: Option<TeamCityDependencyCollectionModel>,
+    #[serde(default, alias = "testOccurrences")]
+    pub tests: Option<TeamCityTestSummaryModel>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamCityTestSummaryModel {
+    #[serde(default)]
+    pub count: u32,
+    #[serde(default)]
+    pub passed: u32,
+    #[serde(default)]
+    pub failed: u32,
+    #[serde(default)]
+    pub ignored: u32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamCityTestOccurrenceCollectionModel {
+    #[serde(default, alias = "testOccurrence")]
+    pub tests: Vec<TeamCityTestOccurrenceModel>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamCityTestOccurrenceModel {
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamCityDependencyCollectionModel {
+    #[serde(default, alias = "build")]
+    pub builds: Vec<TeamCityDependencyModel>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamCityDependencyModel {
+    pub state: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default, alias = "webUrl")]
+    pub url: Option<String>,
+    #[serde(default, alias = "startDate")]
+    pub started_at: Option<String>,
+    #[serde(default, alias = "finishDate")]
+    pub finished_at: Option<String>,
+    #[serde(alias = "buildType")]
+    pub build_type: TeamCityDependencyBuildTypeModel,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamCityDependencyBuildTypeModel {
+    pub name: String,
+}
+
+impl TeamCityDependencyModel {
+    pub fn get_stage(&self) -> DuckResult<BuildStage> {
+        Ok(BuildStage {
+            name: self.build_type.name.clone(),
+            status: match &self.state[..] {
+                "queued" => BuildStatus::Queued,
+                "running" => BuildStatus::Running,
+                _ => match self.status.as_ref().map(|s| &s[..]) {
+                    Some("SUCCESS") => BuildStatus::Success,
+                    Some("UNKNOWN") => BuildStatus::Canceled,
+                    _ => BuildStatus::Failed,
+                },
+            },
+            started_at: match &self.started_at {
+                Option::None => None,
+                Option::Some(value) => {
+                    Option::Some(date::to_timestamp(&value[..], date::TEAMCITY_FORMAT)?)
+                }
+            },
+            finished_at: match &self.finished_at {
+                Option::None => None,
+                Option::Some(value) => {
+                    Option::Some(date::to_timestamp(&value[..], date::TEAMCITY_FORMAT)?)
+                }
+            },
+            url: self.url.clone(),
+        })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamCityRevisionCollectionModel {
+    #[serde(default, alias = "revision")]
+    pub revisions: Vec<TeamCityRevisionModel>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamCityRevisionModel {
+    pub version: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamCityTriggeredModel {
+    #[serde(alias = "type")]
+    pub trigger_type: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamCityChangeCollectionModel {
+    #[serde(default, alias = "change")]
+    pub changes: Vec<TeamCityChangeModel>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TeamCityChangeModel {
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
 }
 
 impl TeamCityBuildModel {
+    pub fn get_queued_at(&self) -> DuckResult<Option<i64>> {
+        let queued_at = match &self.queued_at {
+            Option::None => None,
+            Option::Some(value) => {
+                Option::Some(date::to_timestamp(&value[..], date::TEAMCITY_FORMAT)?)
+            }
+        };
+        Ok(queued_at)
+    }
+
+    pub fn get_revision(&self) -> Option<String> {
+        match &self.revisions {
+            Option::None => None,
+            Option::Some(revisions) => revisions.revisions.first().map(|r| r.version.clone()),
+        }
+    }
+
+    pub fn get_trigger(&self) -> Option<BuildTrigger> {
+        match self.triggered.as_ref().map(|t| &t.trigger_type[..]) {
+            Some("vcs") => Some(BuildTrigger::Push),
+            Some("schedule") => Some(BuildTrigger::Schedule),
+            Some("user") => Some(BuildTrigger::Manual),
+            _ => None,
+        }
+    }
+
+    /// Gets the snapshot dependencies of the build as stages.
+    pub fn get_stages(&self) -> DuckResult<Option<Vec<BuildStage>>> {
+        match &self.dependencies {
+            Option::None => Ok(None),
+            Option::Some(dependencies) => {
+                let mut stages = Vec::new();
+                for dependency in dependencies.builds.iter() {
+                    stages.push(dependency.get_stage()?);
+                }
+                Ok(Some(stages))
+            }
+        }
+    }
+
+    /// Gets the last change that was included in the build.
+    pub fn get_last_change(&self) -> Option<&TeamCityChangeModel> {
+        match &self.last_changes {
+            Option::None => None,
+            Option::Some(changes) => changes.changes.first(),
+        }
+    }
+
     pub fn get_finished_at(&self) -> DuckResult<Option<i64>> {
         let finished_at = match &self.finished_at {
             Option::None => None,
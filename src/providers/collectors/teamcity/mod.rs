@@ -3,9 +3,11 @@ use std::sync::Arc;
 use log::{error, trace, warn};
 use waithandle::{EventWaitHandle, WaitHandle};
 
-use crate::builds::{Build, BuildBuilder, BuildProvider, BuildStatus};
+use crate::builds::{
+    Build, BuildBuilder, BuildProvider, BuildPullRequest, BuildStatus, BuildTests, MAX_FAILED_TESTS,
+};
 use crate::config::TeamCityConfiguration;
-use crate::providers::collectors::{Collector, CollectorInfo};
+use crate::providers::collectors::{BranchFilter, Collector, CollectorInfo};
 use crate::utils::{date, DuckResult};
 
 use self::client::*;
@@ -31,9 +33,34 @@ impl TeamCityCollector {
                     Option::Some(e) => e,
                 },
                 provider: BuildProvider::TeamCity,
+                branches: BranchFilter::new(config.branch_filter.as_ref()),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
             },
         };
     }
+
+    fn get_tests(&self, build: &TeamCityBuildModel) -> Option<BuildTests> {
+        let summary = build.tests.as_ref()?;
+        let failures = if summary.failed > 0 {
+            match self.client.get_failed_tests(build.id, MAX_FAILED_TESTS) {
+                Ok(failures) => failures,
+                Err(e) => {
+                    warn!("Could not get failed tests for build {}. {}", build.id, e);
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
+        Some(BuildTests {
+            total: Some(summary.count),
+            passed: Some(summary.passed),
+            failed: summary.failed,
+            ignored: Some(summary.ignored),
+            failures,
+        })
+    }
 }
 
 impl Collector for TeamCityCollector {
@@ -103,6 +130,16 @@ impl Collector for TeamCityCollector {
                                 )?)
                                 .finished_at(build.get_finished_at()?)
                                 .branch(branch_name)
+                                .commit(build.get_revision())
+                                .commit_message(
+                                    build.get_last_change().and_then(|c| c.comment.clone()),
+                                )
+                                .author(build.get_last_change().and_then(|c| c.username.clone()))
+                                .trigger(build.get_trigger())
+                                .pull_request(BuildPullRequest::from_branch(branch_name))
+                                .queued_at(build.get_queued_at()?)
+                                .stages(build.get_stages()?)
+                                .tests(self.get_tests(build))
                                 .build()
                                 .unwrap(),
                         );
@@ -26,11 +26,24 @@ impl<'a> DuckProviderCollection<'a> {
         let mut providers = Vec::<&'a dyn DuckProvider>::new();
         providers.push(&TeamCityProvider {});
         providers.push(&AzureDevOpsProvider {});
+        providers.push(&AzureDevOpsReleasesProvider {});
         providers.push(&GitHubProvider {});
         providers.push(&OctopusDeployProvider {});
+        providers.push(&ArgoCdProvider {});
+        providers.push(&CommandProvider {});
+        providers.push(&FileProvider {});
+        providers.push(&DuckFederationProvider {});
         providers.push(&HueProvider {});
+        providers.push(&LifxProvider {});
+        providers.push(&WledProvider {});
         providers.push(&SlackProvider {});
         providers.push(&MattermostProvider {});
+        providers.push(&DiscordProvider {});
+        providers.push(&EmailProvider {});
+        providers.push(&WebhookProvider {});
+        providers.push(&MqttProvider {});
+        providers.push(&CommandObserverProvider {});
+        providers.push(&IncidentProvider {});
 
         DuckProviderCollection { providers }
     }
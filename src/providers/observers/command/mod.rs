@@ -0,0 +1,370 @@
+use std::collections::{HashMap, HashSet};
+use std::iter::FromIterator;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::Utc;
+use log::{info, trace, warn};
+
+use crate::config::{CommandObserverConfiguration, WebhookEvent};
+use crate::providers::observers::webhook::payload::WebhookPayload;
+use crate::providers::observers::{Observation, Observer, ObserverInfo};
+use crate::utils::process::{self, ProcessRequest};
+use crate::utils::DuckResult;
+
+mod validation;
+
+pub struct CommandObserver {
+    info: ObserverInfo,
+    command: String,
+    arguments: Vec<String>,
+    working_directory: Option<String>,
+    environment: HashMap<String, String>,
+    timeout: Duration,
+    concurrency: usize,
+    events: Option<HashSet<WebhookEvent>>,
+    running: Arc<(Mutex<usize>, Condvar)>,
+}
+
+impl CommandObserver {
+    pub fn new(config: &CommandObserverConfiguration) -> Self {
+        CommandObserver {
+            info: ObserverInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    None => true,
+                    Some(e) => e,
+                },
+                collectors: match &config.collectors {
+                    Option::None => Option::None,
+                    Option::Some(collectors) => {
+                        Some(HashSet::from_iter(collectors.iter().cloned()))
+                    }
+                },
+                view: config.view.clone(),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+            command: config.command.clone(),
+            arguments: config.arguments.clone().unwrap_or_default(),
+            working_directory: config.working_directory.clone(),
+            environment: config.environment.clone().unwrap_or_default(),
+            timeout: Duration::from_secs(u64::from(config.timeout.unwrap_or(30))),
+            concurrency: config.concurrency.unwrap_or(1) as usize,
+            events: match &config.events {
+                Option::None => Option::None,
+                Option::Some(events) => Some(HashSet::from_iter(events.iter().cloned())),
+            },
+            running: Arc::new((Mutex::new(0), Condvar::new())),
+        }
+    }
+
+    /// Runs the command on a background thread, but waits
+    /// for a free slot if too many commands are running already.
+    fn spawn(&self, stdin: String, environment: HashMap<String, String>) {
+        let (lock, condvar) = &*self.running;
+        let mut running = lock.lock().unwrap();
+        while *running >= self.concurrency {
+            running = condvar.wait(running).unwrap();
+        }
+        *running += 1;
+
+        let id = self.info.id.clone();
+        let command = self.command.clone();
+        let arguments = self.arguments.clone();
+        let working_directory = self.working_directory.clone();
+        let timeout = self.timeout;
+        let slots = self.running.clone();
+
+        thread::spawn(move || {
+            let result = process::run(&ProcessRequest {
+                program: &command,
+                arguments: &arguments,
+                working_directory: working_directory.as_ref().map(|d| &d[..]),
+                environment: Some(&environment),
+                stdin: Some(stdin),
+                timeout,
+                cancellation: None,
+            });
+
+            match result {
+                Ok(output) => {
+                    if output.timed_out {
+                        warn!("[{}] The command '{}' timed out.", id, command);
+                    } else if !output.is_success() {
+                        warn!(
+                            "[{}] The command '{}' exited with {:?}.",
+                            id, command, output.exit_code
+                        );
+                    } else {
+                        trace!("[{}] The command '{}' exited successfully.", id, command);
+                    }
+                }
+                Err(e) => warn!("[{}] {}", id, e),
+            }
+
+            let (lock, condvar) = &*slots;
+            *lock.lock().unwrap() -= 1;
+            condvar.notify_all();
+        });
+    }
+
+    /// Waits for all running commands to finish.
+    fn wait(&self) {
+        let (lock, condvar) = &*self.running;
+        let mut running = lock.lock().unwrap();
+        while *running > 0 {
+            running = condvar.wait(running).unwrap();
+        }
+    }
+
+    fn get_environment(&self, payload: &WebhookPayload) -> DuckResult<HashMap<String, String>> {
+        let mut environment = self.environment.clone();
+        environment.insert(
+            "DUCK_EVENT".to_string(),
+            serde_json::to_value(payload.event)?
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+        );
+        if let Some(status) = &payload.status {
+            environment.insert("DUCK_STATUS".to_string(), status.clone());
+        }
+        if let Some(build) = &payload.build {
+            let variables = vec![
+                ("DUCK_BUILD_ID", build.build_id.to_string()),
+                ("DUCK_BUILD_PROVIDER", build.provider.clone()),
+                ("DUCK_BUILD_COLLECTOR", build.collector.to_string()),
+                ("DUCK_BUILD_PROJECT", build.project.to_string()),
+                ("DUCK_BUILD_DEFINITION", build.definition.to_string()),
+                ("DUCK_BUILD_BRANCH", build.branch.to_string()),
+                ("DUCK_BUILD_NUMBER", build.build_number.to_string()),
+                ("DUCK_BUILD_STATUS", build.status.clone()),
+                ("DUCK_BUILD_URL", build.url.to_string()),
+            ];
+            for (name, value) in variables {
+                environment.insert(name.to_string(), value);
+            }
+            let optional = vec![
+                ("DUCK_BUILD_COMMIT", build.commit.map(|c| c.to_string())),
+                ("DUCK_BUILD_AUTHOR", build.author.map(|a| a.to_string())),
+                ("DUCK_BUILD_TRIGGER", build.trigger.clone()),
+            ];
+            for (name, value) in optional {
+                if let Some(value) = value {
+                    environment.insert(name.to_string(), value);
+                }
+            }
+        }
+        Ok(environment)
+    }
+}
+
+impl Observer for CommandObserver {
+    fn info(&self) -> &ObserverInfo {
+        &self.info
+    }
+
+    fn observe(&self, observation: Observation) -> DuckResult<()> {
+        let payload = WebhookPayload::new(&observation, Utc::now().timestamp());
+        if let Some(events) = &self.events {
+            if !events.contains(&payload.event) {
+                return Ok(());
+            }
+        }
+
+        info!("[{}] Running '{}'...", self.info.id, self.command);
+        let environment = self.get_environment(&payload)?;
+        self.spawn(serde_json::to_string(&payload)?, environment);
+
+        // Make sure that all commands have finished before
+        // we let Duck shut down, or they will be killed.
+        if let Observation::ShuttingDown = observation {
+            self.wait();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::builds::{BuildBuilder, BuildStatus};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn get_output_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "duck-command-observer-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn should_pass_build_as_environment_variables() {
+        // Given
+        let path = get_output_path("environment");
+        let observer = CommandObserver::new(&CommandObserverConfiguration {
+            id: "command".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            command: "sh".to_string(),
+            arguments: Some(vec![
+                "-c".to_string(),
+                format!(
+                    "echo \"$DUCK_EVENT $DUCK_BUILD_PROJECT $DUCK_BUILD_STATUS $FOO\" > {}",
+                    path.display()
+                ),
+            ]),
+            working_directory: None,
+            environment: Some(
+                [("FOO".to_string(), "bar".to_string())]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            timeout: Some(5),
+            concurrency: None,
+            events: None,
+        });
+
+        // When
+        observer
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+        observer.wait();
+
+        // Then
+        assert_eq!(
+            "buildStatusChanged project_name Failed bar\n",
+            fs::read_to_string(&path).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_write_payload_to_stdin() {
+        // Given
+        let path = get_output_path("stdin");
+        let observer = CommandObserver::new(&CommandObserverConfiguration {
+            id: "command".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            command: "sh".to_string(),
+            arguments: Some(vec!["-c".to_string(), format!("cat > {}", path.display())]),
+            working_directory: None,
+            environment: Some(
+                [("FOO".to_string(), "bar".to_string())]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            timeout: Some(5),
+            concurrency: None,
+            events: None,
+        });
+
+        // When
+        observer
+            .observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+        observer.wait();
+
+        // Then
+        let payload: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!("duckStatusChanged", payload["event"]);
+        assert_eq!("Success", payload["status"]);
+    }
+
+    #[test]
+    fn should_only_run_for_configured_events() {
+        // Given
+        let path = get_output_path("events");
+        let observer = CommandObserver::new(&CommandObserverConfiguration {
+            id: "command".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            command: "sh".to_string(),
+            arguments: Some(vec![
+                "-c".to_string(),
+                format!("echo $DUCK_EVENT >> {}", path.display()),
+            ]),
+            working_directory: None,
+            environment: Some(
+                [("FOO".to_string(), "bar".to_string())]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            timeout: Some(5),
+            concurrency: None,
+            events: Some(vec![WebhookEvent::ShuttingDown]),
+        });
+
+        // When
+        observer
+            .observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+        observer.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        assert_eq!("shuttingDown\n", fs::read_to_string(&path).unwrap());
+    }
+
+    #[test]
+    fn should_not_run_more_commands_than_allowed_at_the_same_time() {
+        // Given
+        let path = get_output_path("concurrency");
+        let observer = CommandObserver::new(&CommandObserverConfiguration {
+            id: "command".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            command: "sh".to_string(),
+            arguments: Some(vec![
+                "-c".to_string(),
+                format!(
+                    "echo start >> {path}; sleep 0.2; echo end >> {path}",
+                    path = path.display()
+                ),
+            ]),
+            working_directory: None,
+            environment: Some(
+                [("FOO".to_string(), "bar".to_string())]
+                    .iter()
+                    .cloned()
+                    .collect(),
+            ),
+            timeout: Some(5),
+            concurrency: Some(1),
+            events: None,
+        });
+
+        // When
+        for _ in 0..2 {
+            observer
+                .observe(Observation::DuckStatusChanged(BuildStatus::Success))
+                .unwrap();
+        }
+        observer.wait();
+
+        // Then
+        assert_eq!(
+            "start\nend\nstart\nend\n",
+            fs::read_to_string(&path).unwrap()
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::config::{CommandObserverConfiguration, Validate};
+use crate::utils::DuckResult;
+
+impl Validate for CommandObserverConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if self.id.is_empty() {
+            return Err(format_err!("Command observer have no ID."));
+        }
+        if self.command.is_empty() {
+            return Err(format_err!("Command observer have no command."));
+        }
+        if let Some(concurrency) = self.concurrency {
+            if concurrency == 0 {
+                return Err(format_err!(
+                    "Command observer concurrency must be greater than zero."
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers::DuckProviderCollection;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "Command observer have no command.")]
+    fn should_return_error_if_command_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "command": {
+                            "id": "foo",
+                            "command": ""
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Command observer concurrency must be greater than zero.")]
+    fn should_return_error_if_concurrency_is_zero() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "command": {
+                            "id": "foo",
+                            "command": "blink1-tool",
+                            "concurrency": 0
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+}
@@ -0,0 +1,188 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::{trace, warn};
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+use crate::builds::{Build, BuildStatus};
+use crate::config::{DiscordConfiguration, DiscordCredentials};
+use crate::utils::http::{HttpClient, HttpRequestBuilder, HttpResponse};
+use crate::utils::DuckResult;
+
+/// The longest time we're prepared to wait for a rate limit
+/// to reset, since observers are notified on the engine thread.
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(30);
+
+pub struct DiscordClient {
+    credentials: DiscordCredentials,
+    reset_at: Mutex<Option<Instant>>,
+}
+
+impl DiscordCredentials {
+    fn get_url(&self) -> &str {
+        match self {
+            DiscordCredentials::Webhook { url } => url,
+        }
+    }
+}
+
+impl DiscordClient {
+    pub fn new(config: &DiscordConfiguration) -> Self {
+        DiscordClient {
+            credentials: config.credentials.clone(),
+            reset_at: Mutex::new(None),
+        }
+    }
+
+    pub fn send(&self, client: &impl HttpClient, build: &Build) -> DuckResult<()> {
+        self.post(client, get_payload(build))
+    }
+
+    /// Lets everyone know that someone is looking into the build.
+    pub fn send_acknowledgement(&self, client: &impl HttpClient, build: &Build) -> DuckResult<()> {
+        match get_acknowledgement_payload(build) {
+            Some(payload) => self.post(client, payload),
+            None => Ok(()),
+        }
+    }
+
+    fn post(&self, client: &impl HttpClient, payload: serde_json::Value) -> DuckResult<()> {
+        let mut builder = HttpRequestBuilder::post(self.credentials.get_url().to_string());
+        builder.add_header("Content-Type", "application/json");
+        builder.set_body(payload.to_string());
+
+        // Wait for the rate limit bucket to reset if we
+        // exhausted it the last time we sent something.
+        self.wait_for_rate_limit();
+
+        let mut response = client.send(&builder)?;
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            // We were rate limited, so wait as long as Discord
+            // tells us to and then try again once.
+            let wait = get_retry_after(response.headers()).unwrap_or(Duration::from_secs(1));
+            warn!("Discord rate limit reached. Retrying in {:?}...", wait);
+            thread::sleep(wait.min(MAX_RATE_LIMIT_WAIT));
+            response = client.send(&builder)?;
+        }
+
+        self.update_rate_limit(response.headers());
+
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Could not send Discord message ({})",
+                response.status()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn wait_for_rate_limit(&self) {
+        let reset_at = self.reset_at.lock().unwrap().take();
+        if let Some(reset_at) = reset_at {
+            let now = Instant::now();
+            if reset_at > now {
+                let wait = (reset_at - now).min(MAX_RATE_LIMIT_WAIT);
+                trace!("Waiting {:?} for Discord rate limit to reset...", wait);
+                thread::sleep(wait);
+            }
+        }
+    }
+
+    fn update_rate_limit(&self, headers: &HeaderMap) {
+        let remaining = get_header(headers, "X-RateLimit-Remaining");
+        let reset_after = get_header(headers, "X-RateLimit-Reset-After");
+        if let (Some(remaining), Some(reset_after)) = (remaining, reset_after) {
+            if remaining <= 0.0 {
+                let mut reset_at = self.reset_at.lock().unwrap();
+                *reset_at = Some(Instant::now() + Duration::from_secs_f64(reset_after));
+            }
+        }
+    }
+}
+
+fn get_acknowledgement_payload(build: &Build) -> Option<serde_json::Value> {
+    let acknowledgement = build.acknowledged.as_ref()?;
+    let mut content = format!(
+        ":eyes: {} is looking into [{}::{}]({}) ({})",
+        acknowledgement.by, build.project_name, build.definition_name, build.url, build.branch
+    );
+    if let Some(note) = &acknowledgement.note {
+        content.push_str(&format!("\n> {}", note));
+    }
+    Some(json!({
+        "username": "Duck",
+        "content": content
+    }))
+}
+
+fn get_payload(build: &Build) -> serde_json::Value {
+    let mut fields = vec![
+        json!({ "name": "Project", "value": build.project_name, "inline": true }),
+        json!({ "name": "Definition", "value": build.definition_name, "inline": true }),
+        json!({ "name": "Branch", "value": build.branch, "inline": true }),
+        json!({ "name": "Build number", "value": build.build_number, "inline": true }),
+    ];
+    if build.status == BuildStatus::Failed {
+        let failed = build.get_failed_stages();
+        if !failed.is_empty() {
+            let value: Vec<String> = failed
+                .iter()
+                .map(|stage| match &stage.url {
+                    Some(url) => format!("[{}]({})", stage.name, url),
+                    None => stage.name.clone(),
+                })
+                .collect();
+            fields.push(json!({ "name": "Failed stages", "value": value.join("\n") }));
+        }
+        if let Some(tests) = &build.tests {
+            let mut value = vec![tests.summary()];
+            value.extend(tests.failures.iter().map(|name| format!("`{}`", name)));
+            fields.push(json!({ "name": "Tests", "value": value.join("\n") }));
+        }
+    }
+
+    json!({
+        "username": "Duck",
+        "embeds": [{
+            "title": format!(
+                "{}::{} changed to {:?}",
+                build.project_name, build.definition_name, build.status
+            ),
+            "url": build.url,
+            "color": get_color(&build.status),
+            "fields": fields,
+            "footer": {
+                "text": format!("{:?}", build.provider)
+            }
+        }]
+    })
+}
+
+fn get_color(status: &BuildStatus) -> u32 {
+    match status {
+        BuildStatus::Success => 0x002E_CC71,
+        BuildStatus::Failed => 0x00E7_4C3C,
+        BuildStatus::Running => 0x0034_98DB,
+        BuildStatus::Queued => 0x0034_98DB,
+        BuildStatus::Blocked => 0x00F3_9C12,
+        BuildStatus::Canceled | BuildStatus::Skipped => 0x0095_A5A6,
+        BuildStatus::Unknown => 0x007F_8C8D,
+    }
+}
+
+fn get_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    get_header(headers, "Retry-After")
+        .or_else(|| get_header(headers, "X-RateLimit-Reset-After"))
+        .map(Duration::from_secs_f64)
+}
+
+fn get_header(headers: &HeaderMap, name: &str) -> Option<f64> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<f64>().ok())
+        .filter(|value| *value >= 0.0)
+}
@@ -0,0 +1,489 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use log::info;
+
+use crate::config::DiscordConfiguration;
+use crate::providers::observers::{Observation, Observer, ObserverInfo};
+use crate::utils::http::HttpClient;
+use crate::utils::DuckResult;
+
+use self::client::DiscordClient;
+
+mod client;
+mod validation;
+
+pub struct DiscordObserver<T: HttpClient + Default> {
+    client: DiscordClient,
+    http: T,
+    info: ObserverInfo,
+}
+
+impl<T: HttpClient + Default> DiscordObserver<T> {
+    pub fn new(config: &DiscordConfiguration) -> Self {
+        DiscordObserver {
+            client: DiscordClient::new(config),
+            http: Default::default(),
+            info: ObserverInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    None => true,
+                    Some(e) => e,
+                },
+                collectors: match &config.collectors {
+                    Option::None => Option::None,
+                    Option::Some(collectors) => {
+                        Some(HashSet::from_iter(collectors.iter().cloned()))
+                    }
+                },
+                view: config.view.clone(),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+}
+
+impl<T: HttpClient + Default> Observer for DiscordObserver<T> {
+    fn info(&self) -> &ObserverInfo {
+        &self.info
+    }
+
+    fn observe(&self, observation: Observation) -> DuckResult<()> {
+        if observation.is_muted() {
+            return Ok(());
+        }
+
+        match observation {
+            Observation::BuildStatusChanged(build) => {
+                info!(
+                    "Sending Discord message since build status changed ({:?})...",
+                    build.status
+                );
+                self.client.send(&self.http, build)?;
+            }
+            Observation::BuildAcknowledged(build) => {
+                info!("Sending Discord message since build was acknowledged...");
+                self.client.send_acknowledgement(&self.http, build)?;
+            }
+            _ => {}
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::{Acknowledgement, BuildBuilder, BuildStage, BuildStatus, BuildTests};
+    use crate::config::DiscordCredentials;
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+    use test_case::test_case;
+
+    #[test]
+    fn should_post_to_webhook_url() {
+        // Given
+        let discord = DiscordObserver::<MockHttpClient>::new(&DiscordConfiguration {
+            id: "discord".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            credentials: DiscordCredentials::Webhook {
+                url: "https://discord.com/api/webhooks/1234/TOKEN".to_string(),
+            },
+        });
+
+        let client = discord.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://discord.com/api/webhooks/1234/TOKEN",
+            )
+            .returns_status(StatusCode::NO_CONTENT),
+        );
+
+        // When
+        discord
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(HttpMethod::Post, requests[0].method);
+        assert_eq!(
+            "https://discord.com/api/webhooks/1234/TOKEN",
+            &requests[0].url
+        );
+    }
+
+    #[test]
+    fn should_send_embed_with_build_information() {
+        // Given
+        let discord = DiscordObserver::<MockHttpClient>::new(&DiscordConfiguration {
+            id: "discord".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            credentials: DiscordCredentials::Webhook {
+                url: "https://discord.com/api/webhooks/1234/TOKEN".to_string(),
+            },
+        });
+
+        let client = discord.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://discord.com/api/webhooks/1234/TOKEN",
+            )
+            .returns_status(StatusCode::NO_CONTENT),
+        );
+
+        // When
+        discord
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .status(BuildStatus::Failed)
+                    .url("https://example.com/build/1")
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()[..]).unwrap();
+        let embed = &body["embeds"][0];
+        assert_eq!(
+            "project_name::definition_name changed to Failed",
+            embed["title"]
+        );
+        assert_eq!("https://example.com/build/1", embed["url"]);
+        assert_eq!("TeamCity", embed["footer"]["text"]);
+        assert_eq!("Project", embed["fields"][0]["name"]);
+        assert_eq!("project_name", embed["fields"][0]["value"]);
+        assert_eq!("Definition", embed["fields"][1]["name"]);
+        assert_eq!("definition_name", embed["fields"][1]["value"]);
+        assert_eq!("Branch", embed["fields"][2]["name"]);
+        assert_eq!("branch", embed["fields"][2]["value"]);
+        assert_eq!("Build number", embed["fields"][3]["name"]);
+        assert_eq!("build_number", embed["fields"][3]["value"]);
+    }
+
+    #[test_case(BuildStatus::Success, 0x002E_CC71 ; "Success")]
+    #[test_case(BuildStatus::Failed, 0x00E7_4C3C ; "Failed")]
+    #[test_case(BuildStatus::Running, 0x0034_98DB ; "Running")]
+    #[test_case(BuildStatus::Canceled, 0x0095_A5A6 ; "Canceled")]
+    #[test_case(BuildStatus::Unknown, 0x007F_8C8D ; "Unknown")]
+    fn should_set_embed_color_by_status(status: BuildStatus, expected: u32) {
+        // Given
+        let discord = DiscordObserver::<MockHttpClient>::new(&DiscordConfiguration {
+            id: "discord".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            credentials: DiscordCredentials::Webhook {
+                url: "https://discord.com/api/webhooks/1234/TOKEN".to_string(),
+            },
+        });
+
+        let client = discord.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://discord.com/api/webhooks/1234/TOKEN",
+            )
+            .returns_status(StatusCode::NO_CONTENT),
+        );
+
+        // When
+        discord
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(status).unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()[..]).unwrap();
+        assert_eq!(expected, body["embeds"][0]["color"]);
+    }
+
+    #[test]
+    fn should_ignore_other_observations() {
+        // Given
+        let discord = DiscordObserver::<MockHttpClient>::new(&DiscordConfiguration {
+            id: "discord".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            credentials: DiscordCredentials::Webhook {
+                url: "https://discord.com/api/webhooks/1234/TOKEN".to_string(),
+            },
+        });
+
+        // When
+        discord
+            .observe(Observation::BuildUpdated(&BuildBuilder::dummy().unwrap()))
+            .unwrap();
+        discord
+            .observe(Observation::DuckStatusChanged(BuildStatus::Failed))
+            .unwrap();
+
+        // Then
+        assert_eq!(0, discord.get_client().get_sent_requests().len());
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not send Discord message (429 Too Many Requests)")]
+    fn should_retry_once_if_rate_limited() {
+        // Given
+        let discord = DiscordObserver::<MockHttpClient>::new(&DiscordConfiguration {
+            id: "discord".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            credentials: DiscordCredentials::Webhook {
+                url: "https://discord.com/api/webhooks/1234/TOKEN".to_string(),
+            },
+        });
+
+        let client = discord.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://discord.com/api/webhooks/1234/TOKEN",
+            )
+            .returns_status(StatusCode::TOO_MANY_REQUESTS)
+            .returns_header("Retry-After", "0.01"),
+        );
+
+        // When
+        let result = discord.observe(Observation::BuildStatusChanged(
+            &BuildBuilder::dummy().unwrap(),
+        ));
+
+        // Then
+        assert_eq!(2, client.get_sent_requests().len());
+        result.unwrap();
+    }
+
+    #[test]
+    fn should_respect_exhausted_rate_limit_bucket() {
+        // Given
+        let discord = DiscordObserver::<MockHttpClient>::new(&DiscordConfiguration {
+            id: "discord".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            credentials: DiscordCredentials::Webhook {
+                url: "https://discord.com/api/webhooks/1234/TOKEN".to_string(),
+            },
+        });
+
+        let client = discord.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://discord.com/api/webhooks/1234/TOKEN",
+            )
+            .returns_status(StatusCode::NO_CONTENT)
+            .returns_header("X-RateLimit-Remaining", "0")
+            .returns_header("X-RateLimit-Reset-After", "0.2"),
+        );
+        let build = BuildBuilder::dummy().unwrap();
+        discord
+            .observe(Observation::BuildStatusChanged(&build))
+            .unwrap();
+
+        // When
+        let started = std::time::Instant::now();
+        discord
+            .observe(Observation::BuildStatusChanged(&build))
+            .unwrap();
+
+        // Then
+        assert!(started.elapsed() >= std::time::Duration::from_millis(150));
+        assert_eq!(2, client.get_sent_requests().len());
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not send Discord message (502 Bad Gateway)")]
+    fn should_return_error_if_server_return_non_successful_http_status_code() {
+        // Given
+        let discord = DiscordObserver::<MockHttpClient>::new(&DiscordConfiguration {
+            id: "discord".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            credentials: DiscordCredentials::Webhook {
+                url: "https://discord.com/api/webhooks/1234/TOKEN".to_string(),
+            },
+        });
+
+        let client = discord.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://discord.com/api/webhooks/1234/TOKEN",
+            )
+            .returns_status(StatusCode::BAD_GATEWAY),
+        );
+
+        // When, Then
+        discord
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().unwrap(),
+            ))
+            .unwrap();
+    }
+
+    #[test]
+    fn should_include_failed_stages_for_failed_builds() {
+        // Given
+        let discord = DiscordObserver::<MockHttpClient>::new(&DiscordConfiguration {
+            id: "discord".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            credentials: DiscordCredentials::Webhook {
+                url: "https://discord.com/api/webhooks/1234/TOKEN".to_string(),
+            },
+        });
+
+        let client = discord.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://discord.com/api/webhooks/1234/TOKEN",
+            )
+            .returns_status(StatusCode::NO_CONTENT),
+        );
+
+        // When
+        discord
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .status(BuildStatus::Failed)
+                    .stages(Some(vec![
+                        BuildStage::dummy("build", BuildStatus::Success),
+                        BuildStage::dummy("test", BuildStatus::Failed),
+                    ]))
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()[..]).unwrap();
+        let field = &body["embeds"][0]["fields"][4];
+        assert_eq!("Failed stages", field["name"]);
+        assert_eq!("[test](https://dummy/test)", field["value"]);
+    }
+
+    #[test]
+    fn should_include_test_summary_for_failed_builds() {
+        // Given
+        let discord = DiscordObserver::<MockHttpClient>::new(&DiscordConfiguration {
+            id: "discord".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            credentials: DiscordCredentials::Webhook {
+                url: "https://discord.com/api/webhooks/1234/TOKEN".to_string(),
+            },
+        });
+
+        let client = discord.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://discord.com/api/webhooks/1234/TOKEN",
+            )
+            .returns_status(StatusCode::NO_CONTENT),
+        );
+
+        // When
+        discord
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .status(BuildStatus::Failed)
+                    .tests(Some(BuildTests {
+                        total: Some(145),
+                        passed: Some(142),
+                        failed: 3,
+                        ignored: None,
+                        failures: vec!["foo".to_string(), "bar".to_string()],
+                    }))
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()[..]).unwrap();
+        let field = &body["embeds"][0]["fields"][4];
+        assert_eq!("Tests", field["name"]);
+        assert_eq!("142 passed, 3 failed\n`foo`\n`bar`", field["value"]);
+    }
+
+    #[test]
+    fn should_post_message_when_build_is_acknowledged() {
+        // Given
+        let discord = DiscordObserver::<MockHttpClient>::new(&DiscordConfiguration {
+            id: "discord".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            credentials: DiscordCredentials::Webhook {
+                url: "https://discord.com/api/webhooks/1234/TOKEN".to_string(),
+            },
+        });
+
+        let client = discord.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://discord.com/api/webhooks/1234/TOKEN",
+            )
+            .returns_status(StatusCode::NO_CONTENT),
+        );
+        let mut build = BuildBuilder::dummy().status(BuildStatus::Failed).unwrap();
+        build.acknowledged = Some(Acknowledgement::dummy("Patrik"));
+
+        // When
+        discord
+            .observe(Observation::BuildAcknowledged(&build))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()[..]).unwrap();
+        assert_eq!(
+            ":eyes: Patrik is looking into [project_name::definition_name](https://dummy) (branch)\n> On it",
+            body["content"]
+        );
+    }
+}
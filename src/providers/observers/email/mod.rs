@@ -0,0 +1,833 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::iter::FromIterator;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use log::{error, info};
+
+use crate::builds::{Build, BuildStatus};
+use crate::config::EmailConfiguration;
+use crate::providers::observers::{Observation, Observer, ObserverInfo};
+use crate::utils::DuckResult;
+
+use self::transport::{EmailMessage, EmailTransport};
+
+pub use self::transport::SmtpEmailTransport;
+
+mod transport;
+mod validation;
+
+enum EmailEvent {
+    StatusChanged(Box<Build>),
+    ShuttingDown,
+}
+
+/// Sends emails when builds break or get fixed.
+///
+/// Changes are handed to a worker thread that waits for
+/// the configured window to pass before sending, so that
+/// several changes end up in the same email.
+pub struct EmailObserver {
+    sender: Mutex<Sender<EmailEvent>>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+    info: ObserverInfo,
+    /// The last success or failure of every build partition.
+    outcomes: Mutex<HashMap<u64, BuildStatus>>,
+}
+
+struct EmailSettings {
+    from: String,
+    to: Vec<String>,
+    recipients: HashMap<String, Vec<String>>,
+    window: Duration,
+}
+
+impl EmailObserver {
+    pub fn new(config: &EmailConfiguration, transport: impl EmailTransport) -> Self {
+        let settings = EmailSettings {
+            from: config.from.clone(),
+            to: config.to.clone().unwrap_or_default(),
+            recipients: config.recipients.clone().unwrap_or_default(),
+            window: Duration::from_secs(u64::from(config.window.unwrap_or(30))),
+        };
+
+        let (sender, receiver) = channel::<EmailEvent>();
+        let worker = std::thread::spawn(move || run_worker(&transport, &settings, receiver));
+
+        EmailObserver {
+            sender: Mutex::new(sender),
+            worker: Mutex::new(Some(worker)),
+            info: ObserverInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    None => true,
+                    Some(e) => e,
+                },
+                collectors: match &config.collectors {
+                    Option::None => Option::None,
+                    Option::Some(collectors) => {
+                        Some(HashSet::from_iter(collectors.iter().cloned()))
+                    }
+                },
+                view: config.view.clone(),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+            outcomes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records the outcome of the build, and returns whether or not
+    /// the build went from success to failure or the other way around.
+    fn is_flipped(&self, build: &Build) -> bool {
+        if !is_interesting_status(&build.status) {
+            return false;
+        }
+        let mut outcomes = self.outcomes.lock().unwrap();
+        match outcomes.insert(build.partition, build.status.clone()) {
+            Some(previous) => previous != build.status,
+            None => false,
+        }
+    }
+
+    fn send_event(&self, event: EmailEvent) -> DuckResult<()> {
+        let sender = self.sender.lock().unwrap();
+        if sender.send(event).is_err() {
+            return Err(format_err!("The email worker is not running."));
+        }
+        Ok(())
+    }
+}
+
+impl Observer for EmailObserver {
+    fn info(&self) -> &ObserverInfo {
+        &self.info
+    }
+
+    fn observe(&self, observation: Observation) -> DuckResult<()> {
+        match observation {
+            // Only tell anyone if the build broke or got fixed,
+            // not if it was canceled or started running again.
+            // Muted builds are still recorded, but not sent.
+            Observation::BuildStatusChanged(build)
+                if self.is_flipped(build) && build.muted.is_none() =>
+            {
+                self.send_event(EmailEvent::StatusChanged(Box::new(build.clone())))?;
+            }
+            Observation::BuildUpdated(build) if is_interesting_status(&build.status) => {
+                // New builds aren't status changes, but we need
+                // to know how they went to know when they change.
+                // Status changes are also sent as updates right before
+                // the change itself, so only record the first outcome.
+                self.outcomes
+                    .lock()
+                    .unwrap()
+                    .entry(build.partition)
+                    .or_insert_with(|| build.status.clone());
+            }
+            Observation::ShuttingDown => {
+                // Flush any pending changes before shutting down.
+                self.send_event(EmailEvent::ShuttingDown)?;
+                if let Some(worker) = self.worker.lock().unwrap().take() {
+                    if worker.join().is_err() {
+                        return Err(format_err!("The email worker panicked."));
+                    }
+                }
+            }
+            _ => {}
+        };
+
+        Ok(())
+    }
+}
+
+fn is_interesting_status(status: &BuildStatus) -> bool {
+    match status {
+        BuildStatus::Success | BuildStatus::Failed => true,
+        _ => false,
+    }
+}
+
+fn run_worker(
+    transport: &impl EmailTransport,
+    settings: &EmailSettings,
+    receiver: Receiver<EmailEvent>,
+) {
+    let mut stopped = false;
+    while !stopped {
+        // Wait for the first change.
+        let mut pending = match receiver.recv() {
+            Ok(EmailEvent::StatusChanged(build)) => vec![*build],
+            Ok(EmailEvent::ShuttingDown) | Err(_) => break,
+        };
+
+        // Collect everything else that happens within the window.
+        let deadline = Instant::now() + settings.window;
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            match receiver.recv_timeout(deadline - now) {
+                Ok(EmailEvent::StatusChanged(build)) => pending.push(*build),
+                Ok(EmailEvent::ShuttingDown) | Err(RecvTimeoutError::Disconnected) => {
+                    stopped = true;
+                    break;
+                }
+                Err(RecvTimeoutError::Timeout) => break,
+            }
+        }
+
+        for message in create_messages(settings, &pending) {
+            info!(
+                "Sending email to {} since {} build(s) changed status...",
+                message.to.join(", "),
+                pending.len()
+            );
+            if let Err(e) = transport.send(&message) {
+                error!("Could not send email. {}", e);
+            }
+        }
+    }
+}
+
+fn create_messages(settings: &EmailSettings, builds: &[Build]) -> Vec<EmailMessage> {
+    // Only keep the latest change for every build.
+    let mut latest = Vec::<&Build>::new();
+    for build in builds.iter() {
+        latest.retain(|b| b.partition != build.partition);
+        latest.push(build);
+    }
+
+    // Group the changes by their recipients.
+    let mut groups = BTreeMap::<Vec<String>, Vec<&Build>>::new();
+    for build in latest {
+        let mut recipients = match settings.recipients.get(&build.collector) {
+            Some(recipients) => recipients.clone(),
+            None => settings.to.clone(),
+        };
+        if recipients.is_empty() {
+            continue;
+        }
+        recipients.sort();
+        recipients.dedup();
+        groups.entry(recipients).or_default().push(build);
+    }
+
+    groups
+        .into_iter()
+        .map(|(to, builds)| EmailMessage {
+            from: settings.from.clone(),
+            to,
+            subject: get_subject(&builds),
+            text: get_text(&builds),
+            html: get_html(&builds),
+        })
+        .collect()
+}
+
+fn get_change(build: &Build) -> &'static str {
+    match build.status {
+        BuildStatus::Failed => "broken",
+        _ => "fixed",
+    }
+}
+
+fn get_subject(builds: &[&Build]) -> String {
+    if builds.len() == 1 {
+        let build = builds[0];
+        return format!(
+            "Build {}: {}::{} ({})",
+            get_change(build),
+            build.project_name,
+            build.definition_name,
+            build.branch
+        );
+    }
+
+    let broken = builds
+        .iter()
+        .filter(|b| b.status == BuildStatus::Failed)
+        .count();
+    format!(
+        "{} builds changed status ({} broken, {} fixed)",
+        builds.len(),
+        broken,
+        builds.len() - broken
+    )
+}
+
+fn get_text(builds: &[&Build]) -> String {
+    let mut text = String::new();
+    for build in builds.iter() {
+        text.push_str(&format!(
+            "{}::{} ({}) is {}.\nBuild {} from {:?}: {}\n\n",
+            build.project_name,
+            build.definition_name,
+            build.branch,
+            get_change(build),
+            build.build_number,
+            build.provider,
+            build.url
+        ));
+    }
+    text
+}
+
+fn get_html(builds: &[&Build]) -> String {
+    let mut html = String::from("<html><body>");
+    for build in builds.iter() {
+        html.push_str(&format!(
+            "<p><strong style=\"color: {color}\">{project}::{definition} ({branch}) is {change}.</strong><br />\
+             <a href=\"{url}\">Build {number}</a> from {provider:?}</p>",
+            color = match build.status {
+                BuildStatus::Failed => "#e74c3c",
+                _ => "#2ecc71",
+            },
+            project = escape_html(&build.project_name),
+            definition = escape_html(&build.definition_name),
+            branch = escape_html(&build.branch),
+            change = get_change(build),
+            url = escape_html(&build.url),
+            number = escape_html(&build.build_number),
+            provider = build.provider
+        ));
+    }
+    html.push_str("</body></html>");
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildBuilder;
+    use crate::config::{SmtpSecurity, SmtpServerConfiguration};
+    use std::sync::Arc;
+
+    #[derive(Default, Clone)]
+    pub struct MockEmailTransport {
+        sent: Arc<Mutex<Vec<EmailMessage>>>,
+    }
+
+    impl MockEmailTransport {
+        fn get_sent_messages(&self) -> Vec<EmailMessage> {
+            self.sent.lock().unwrap().clone()
+        }
+    }
+
+    impl EmailTransport for MockEmailTransport {
+        fn send(&self, message: &EmailMessage) -> DuckResult<()> {
+            self.sent.lock().unwrap().push(message.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn should_send_email_when_build_breaks() {
+        // Given
+        let transport = MockEmailTransport::default();
+        let email = EmailObserver::new(
+            &EmailConfiguration {
+                id: "email".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                server: SmtpServerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    security: Some(SmtpSecurity::None),
+                    credentials: None,
+                },
+                from: "duck@example.com".to_string(),
+                to: Some(vec!["team@example.com".to_string()]),
+                recipients: None,
+                window: Some(60),
+            },
+            transport.clone(),
+        );
+        email
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy().status(BuildStatus::Success).unwrap(),
+            ))
+            .unwrap();
+
+        // When
+        email
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+        email.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let messages = transport.get_sent_messages();
+        assert_eq!(1, messages.len());
+        assert_eq!("duck@example.com", messages[0].from);
+        assert_eq!(vec!["team@example.com".to_string()], messages[0].to);
+        assert_eq!(
+            "Build broken: project_name::definition_name (branch)",
+            messages[0].subject
+        );
+        assert!(messages[0]
+            .text
+            .contains("project_name::definition_name (branch) is broken."));
+        assert!(messages[0].html.contains("is broken.</strong>"));
+    }
+
+    #[test]
+    fn should_send_email_when_update_is_observed_before_status_change() {
+        // Given
+        let transport = MockEmailTransport::default();
+        let email = EmailObserver::new(
+            &EmailConfiguration {
+                id: "email".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                server: SmtpServerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    security: Some(SmtpSecurity::None),
+                    credentials: None,
+                },
+                from: "duck@example.com".to_string(),
+                to: Some(vec!["team@example.com".to_string()]),
+                recipients: None,
+                window: Some(60),
+            },
+            transport.clone(),
+        );
+        email
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy().status(BuildStatus::Success).unwrap(),
+            ))
+            .unwrap();
+
+        // When
+        let build = BuildBuilder::dummy().status(BuildStatus::Failed).unwrap();
+        email.observe(Observation::BuildUpdated(&build)).unwrap();
+        email
+            .observe(Observation::BuildStatusChanged(&build))
+            .unwrap();
+        email.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let messages = transport.get_sent_messages();
+        assert_eq!(1, messages.len());
+        assert_eq!(
+            "Build broken: project_name::definition_name (branch)",
+            messages[0].subject
+        );
+    }
+
+    #[test]
+    fn should_send_email_when_build_is_fixed() {
+        // Given
+        let transport = MockEmailTransport::default();
+        let email = EmailObserver::new(
+            &EmailConfiguration {
+                id: "email".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                server: SmtpServerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    security: Some(SmtpSecurity::None),
+                    credentials: None,
+                },
+                from: "duck@example.com".to_string(),
+                to: Some(vec!["team@example.com".to_string()]),
+                recipients: None,
+                window: Some(60),
+            },
+            transport.clone(),
+        );
+        email
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+
+        // When
+        email
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Success).unwrap(),
+            ))
+            .unwrap();
+        email.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let messages = transport.get_sent_messages();
+        assert_eq!(1, messages.len());
+        assert_eq!(
+            "Build fixed: project_name::definition_name (branch)",
+            messages[0].subject
+        );
+    }
+
+    #[test]
+    fn should_not_send_email_for_uninteresting_observations() {
+        // Given
+        let transport = MockEmailTransport::default();
+        let email = EmailObserver::new(
+            &EmailConfiguration {
+                id: "email".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                server: SmtpServerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    security: Some(SmtpSecurity::None),
+                    credentials: None,
+                },
+                from: "duck@example.com".to_string(),
+                to: Some(vec!["team@example.com".to_string()]),
+                recipients: None,
+                window: Some(60),
+            },
+            transport.clone(),
+        );
+
+        // When
+        email
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Canceled).unwrap(),
+            ))
+            .unwrap();
+        email
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+        email
+            .observe(Observation::DuckStatusChanged(BuildStatus::Failed))
+            .unwrap();
+        email.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        assert_eq!(0, transport.get_sent_messages().len());
+    }
+
+    #[test]
+    fn should_not_send_email_if_build_was_canceled_in_between() {
+        // Given
+        let transport = MockEmailTransport::default();
+        let email = EmailObserver::new(
+            &EmailConfiguration {
+                id: "email".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                server: SmtpServerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    security: Some(SmtpSecurity::None),
+                    credentials: None,
+                },
+                from: "duck@example.com".to_string(),
+                to: Some(vec!["team@example.com".to_string()]),
+                recipients: None,
+                window: Some(60),
+            },
+            transport.clone(),
+        );
+        email
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy()
+                    .project_id("project1")
+                    .status(BuildStatus::Success)
+                    .unwrap(),
+            ))
+            .unwrap();
+        email
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy()
+                    .project_id("project2")
+                    .status(BuildStatus::Failed)
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // When
+        for (project, status) in [
+            ("project1", BuildStatus::Canceled),
+            ("project1", BuildStatus::Success),
+            ("project2", BuildStatus::Canceled),
+            ("project2", BuildStatus::Failed),
+        ] {
+            email
+                .observe(Observation::BuildStatusChanged(
+                    &BuildBuilder::dummy()
+                        .project_id(project)
+                        .status(status)
+                        .unwrap(),
+                ))
+                .unwrap();
+        }
+        email.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        assert_eq!(0, transport.get_sent_messages().len());
+    }
+
+    #[test]
+    fn should_batch_changes_within_window_into_one_email() {
+        // Given
+        let transport = MockEmailTransport::default();
+        let email = EmailObserver::new(
+            &EmailConfiguration {
+                id: "email".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                server: SmtpServerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    security: Some(SmtpSecurity::None),
+                    credentials: None,
+                },
+                from: "duck@example.com".to_string(),
+                to: Some(vec!["team@example.com".to_string()]),
+                recipients: None,
+                window: Some(60),
+            },
+            transport.clone(),
+        );
+        email
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy()
+                    .project_id("project1")
+                    .status(BuildStatus::Success)
+                    .unwrap(),
+            ))
+            .unwrap();
+        email
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy()
+                    .project_id("project2")
+                    .status(BuildStatus::Failed)
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // When
+        email
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .project_id("project1")
+                    .project_name("Project 1")
+                    .status(BuildStatus::Failed)
+                    .unwrap(),
+            ))
+            .unwrap();
+        email
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .project_id("project2")
+                    .project_name("Project 2")
+                    .status(BuildStatus::Success)
+                    .unwrap(),
+            ))
+            .unwrap();
+        email.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let messages = transport.get_sent_messages();
+        assert_eq!(1, messages.len());
+        assert_eq!(
+            "2 builds changed status (1 broken, 1 fixed)",
+            messages[0].subject
+        );
+        assert!(messages[0].text.contains("Project 1::definition_name"));
+        assert!(messages[0].text.contains("Project 2::definition_name"));
+    }
+
+    #[test]
+    fn should_only_report_latest_change_for_a_build() {
+        // Given
+        let transport = MockEmailTransport::default();
+        let email = EmailObserver::new(
+            &EmailConfiguration {
+                id: "email".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                server: SmtpServerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    security: Some(SmtpSecurity::None),
+                    credentials: None,
+                },
+                from: "duck@example.com".to_string(),
+                to: Some(vec!["team@example.com".to_string()]),
+                recipients: None,
+                window: Some(60),
+            },
+            transport.clone(),
+        );
+        email
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy().status(BuildStatus::Success).unwrap(),
+            ))
+            .unwrap();
+
+        // When
+        email
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+        email
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Success).unwrap(),
+            ))
+            .unwrap();
+        email.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let messages = transport.get_sent_messages();
+        assert_eq!(1, messages.len());
+        assert_eq!(
+            "Build fixed: project_name::definition_name (branch)",
+            messages[0].subject
+        );
+    }
+
+    #[test]
+    fn should_send_to_collector_specific_recipients() {
+        // Given
+        let mut recipients = HashMap::new();
+        recipients.insert(
+            "teamcity".to_string(),
+            vec!["backend@example.com".to_string()],
+        );
+        let transport = MockEmailTransport::default();
+        let email = EmailObserver::new(
+            &EmailConfiguration {
+                id: "email".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                server: SmtpServerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    security: Some(SmtpSecurity::None),
+                    credentials: None,
+                },
+                from: "duck@example.com".to_string(),
+                to: Some(vec!["team@example.com".to_string()]),
+                recipients: Some(recipients),
+                window: Some(60),
+            },
+            transport.clone(),
+        );
+        email
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy()
+                    .collector("teamcity")
+                    .status(BuildStatus::Success)
+                    .unwrap(),
+            ))
+            .unwrap();
+        email
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy()
+                    .collector("github")
+                    .status(BuildStatus::Success)
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // When
+        email
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .collector("teamcity")
+                    .status(BuildStatus::Failed)
+                    .unwrap(),
+            ))
+            .unwrap();
+        email
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .collector("github")
+                    .status(BuildStatus::Failed)
+                    .unwrap(),
+            ))
+            .unwrap();
+        email.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let messages = transport.get_sent_messages();
+        assert_eq!(2, messages.len());
+        assert_eq!(vec!["backend@example.com".to_string()], messages[0].to);
+        assert_eq!(vec!["team@example.com".to_string()], messages[1].to);
+    }
+
+    #[test]
+    fn should_escape_html() {
+        // Given
+        let transport = MockEmailTransport::default();
+        let email = EmailObserver::new(
+            &EmailConfiguration {
+                id: "email".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                server: SmtpServerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    security: Some(SmtpSecurity::None),
+                    credentials: None,
+                },
+                from: "duck@example.com".to_string(),
+                to: Some(vec!["team@example.com".to_string()]),
+                recipients: None,
+                window: Some(60),
+            },
+            transport.clone(),
+        );
+        email
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy()
+                    .branch("<script>")
+                    .status(BuildStatus::Success)
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // When
+        email
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .branch("<script>")
+                    .status(BuildStatus::Failed)
+                    .unwrap(),
+            ))
+            .unwrap();
+        email.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let messages = transport.get_sent_messages();
+        assert!(messages[0].html.contains("(&lt;script&gt;)"));
+    }
+}
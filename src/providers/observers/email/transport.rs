@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use lettre::smtp::authentication::Credentials;
+use lettre::{ClientSecurity, ClientTlsParameters, SmtpClient, Transport};
+use lettre_email::EmailBuilder;
+use log::trace;
+use native_tls::TlsConnector;
+
+use crate::config::{SmtpSecurity, SmtpServerConfiguration};
+use crate::utils::DuckResult;
+
+#[derive(Clone, Debug)]
+pub struct EmailMessage {
+    pub from: String,
+    pub to: Vec<String>,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+pub trait EmailTransport: Send + 'static {
+    fn send(&self, message: &EmailMessage) -> DuckResult<()>;
+}
+
+pub struct SmtpEmailTransport {
+    server: SmtpServerConfiguration,
+}
+
+impl SmtpEmailTransport {
+    pub fn new(server: &SmtpServerConfiguration) -> Self {
+        Self {
+            server: server.clone(),
+        }
+    }
+
+    fn get_security(&self) -> SmtpSecurity {
+        self.server.security.unwrap_or(SmtpSecurity::StartTls)
+    }
+
+    fn get_port(&self) -> u16 {
+        match self.server.port {
+            Some(port) => port,
+            None => match self.get_security() {
+                SmtpSecurity::None => lettre::smtp::SMTP_PORT,
+                SmtpSecurity::StartTls => lettre::smtp::SUBMISSION_PORT,
+                SmtpSecurity::Tls => lettre::smtp::SUBMISSIONS_PORT,
+            },
+        }
+    }
+
+    fn get_client_security(&self) -> DuckResult<ClientSecurity> {
+        let security = self.get_security();
+        if security == SmtpSecurity::None {
+            return Ok(ClientSecurity::None);
+        }
+
+        let parameters =
+            ClientTlsParameters::new(self.server.host.clone(), TlsConnector::builder().build()?);
+
+        Ok(match security {
+            SmtpSecurity::Tls => ClientSecurity::Wrapper(parameters),
+            _ => ClientSecurity::Required(parameters),
+        })
+    }
+}
+
+impl EmailTransport for SmtpEmailTransport {
+    fn send(&self, message: &EmailMessage) -> DuckResult<()> {
+        let mut builder = EmailBuilder::new()
+            .from(message.from.clone())
+            .subject(message.subject.clone())
+            .alternative(message.html.clone(), message.text.clone());
+        for recipient in message.to.iter() {
+            builder = builder.to(recipient.clone());
+        }
+        let email = builder.build()?;
+
+        let mut client = SmtpClient::new(
+            (&self.server.host[..], self.get_port()),
+            self.get_client_security()?,
+        )?
+        .timeout(Some(Duration::from_secs(30)));
+        if let Some(credentials) = &self.server.credentials {
+            client = client.credentials(Credentials::new(
+                credentials.username.clone(),
+                credentials.password.clone(),
+            ));
+        }
+
+        trace!(
+            "Sending email to {}:{}...",
+            self.server.host,
+            self.get_port()
+        );
+        let mut transport = client.transport();
+        let result = transport.send(email.into());
+        transport.close();
+        result?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// A minimal SMTP server that accepts a single message
+    /// and returns the commands and data it received.
+    fn start_smtp_server() -> (u16, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut received = Vec::<String>::new();
+            let mut in_data = false;
+
+            writer.write_all(b"220 localhost ESMTP\r\n").unwrap();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end().to_string();
+                received.push(line.clone());
+
+                if in_data {
+                    if line == "." {
+                        in_data = false;
+                        writer.write_all(b"250 OK\r\n").unwrap();
+                    }
+                    continue;
+                }
+
+                let command = line.to_uppercase();
+                if command.starts_with("EHLO") {
+                    writer.write_all(b"250 localhost\r\n").unwrap();
+                } else if command.starts_with("DATA") {
+                    in_data = true;
+                    writer.write_all(b"354 Go ahead\r\n").unwrap();
+                } else if command.starts_with("QUIT") {
+                    writer.write_all(b"221 Bye\r\n").unwrap();
+                    break;
+                } else {
+                    writer.write_all(b"250 OK\r\n").unwrap();
+                }
+            }
+
+            received
+        });
+
+        (port, handle)
+    }
+
+    #[test]
+    fn should_send_email_to_smtp_server() {
+        // Given
+        let (port, server) = start_smtp_server();
+        let transport = SmtpEmailTransport::new(&SmtpServerConfiguration {
+            host: "127.0.0.1".to_string(),
+            port: Some(port),
+            security: Some(SmtpSecurity::None),
+            credentials: None,
+        });
+
+        // When
+        transport
+            .send(&EmailMessage {
+                from: "duck@example.com".to_string(),
+                to: vec![
+                    "alice@example.com".to_string(),
+                    "bob@example.com".to_string(),
+                ],
+                subject: "Build broken".to_string(),
+                text: "The build is broken".to_string(),
+                html: "<p>The build is broken</p>".to_string(),
+            })
+            .unwrap();
+
+        // Then
+        let received = server.join().unwrap();
+        assert!(received.contains(&"MAIL FROM:<duck@example.com>".to_string()));
+        assert!(received.contains(&"RCPT TO:<alice@example.com>".to_string()));
+        assert!(received.contains(&"RCPT TO:<bob@example.com>".to_string()));
+        assert!(received.contains(&"Subject: Build broken".to_string()));
+        assert!(received.contains(&"The build is broken".to_string()));
+        assert!(received.contains(&"<p>The build is broken</p>".to_string()));
+    }
+}
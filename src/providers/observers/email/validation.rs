@@ -0,0 +1,144 @@
+use crate::config::{EmailConfiguration, Validate};
+use crate::utils::DuckResult;
+
+impl Validate for EmailConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if self.id.is_empty() {
+            return Err(format_err!("Email observer have no ID."));
+        }
+        if self.server.host.is_empty() {
+            return Err(format_err!(
+                "Email observer '{}' have no SMTP server host.",
+                self.id
+            ));
+        }
+        if !is_address(&self.from) {
+            return Err(format_err!(
+                "Email observer '{}' have an invalid sender address.",
+                self.id
+            ));
+        }
+
+        let mut recipients = self.to.clone().unwrap_or_default();
+        if let Some(collectors) = &self.recipients {
+            for addresses in collectors.values() {
+                recipients.extend(addresses.iter().cloned());
+            }
+        }
+        if recipients.is_empty() {
+            return Err(format_err!(
+                "Email observer '{}' have no recipients.",
+                self.id
+            ));
+        }
+        for recipient in recipients.iter() {
+            if !is_address(recipient) {
+                return Err(format_err!(
+                    "Email observer '{}' have an invalid recipient address '{}'.",
+                    self.id,
+                    recipient
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_address(address: &str) -> bool {
+    let parts: Vec<&str> = address.split('@').collect();
+    parts.len() == 2 && !parts[0].is_empty() && !parts[1].is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers::DuckProviderCollection;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "Email observer 'email' have no recipients.")]
+    fn should_return_error_if_there_are_no_recipients() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "email": {
+                            "id": "email",
+                            "server": {
+                                "host": "smtp.example.com"
+                            },
+                            "from": "duck@example.com"
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Email observer 'email' have an invalid recipient address 'team'.")]
+    fn should_return_error_if_recipient_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "email": {
+                            "id": "email",
+                            "server": {
+                                "host": "smtp.example.com"
+                            },
+                            "from": "duck@example.com",
+                            "to": [ "team" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Email observer 'email' have no SMTP server host.")]
+    fn should_return_error_if_host_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "email": {
+                            "id": "email",
+                            "server": {
+                                "host": ""
+                            },
+                            "from": "duck@example.com",
+                            "to": [ "team@example.com" ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+}
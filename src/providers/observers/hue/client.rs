@@ -1,7 +1,14 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use log::warn;
+use reqwest::StatusCode;
 use url::Url;
 
 use crate::builds::BuildStatus;
-use crate::config::HueConfiguration;
+use crate::config::{
+    HueAlert, HueConfiguration, HueEffect, HueStateConfiguration, HueStatesConfiguration,
+};
 use crate::utils::http::{HttpClient, HttpRequestBuilder, HttpResponse};
 use crate::utils::{colors::Rgb, DuckResult};
 
@@ -10,6 +17,29 @@ pub struct HueClient {
     url: Url,
     username: String,
     lights: Vec<String>,
+    groups: Vec<String>,
+    states: HueStatesConfiguration,
+    /// Whether or not any state uses an effect, in which
+    /// case the effect must be reset by all other states.
+    effects: bool,
+    saved: Mutex<SavedLights>,
+}
+
+/// The state the lights were in before we changed them.
+enum SavedLights {
+    NotSaved,
+    Saved(Vec<(String, String)>),
+    Failed,
+}
+
+enum HueState {
+    Scene(String),
+    Light {
+        xy: (f32, f32),
+        brightness: u8,
+        alert: HueAlert,
+        effect: HueEffect,
+    },
 }
 
 impl HueClient {
@@ -22,6 +52,41 @@ impl HueClient {
             url: Url::parse(&config.hub_url[..]).unwrap(),
             username: config.username.clone(),
             lights: config.lights.clone(),
+            groups: config.groups.clone().unwrap_or_default(),
+            states: config.states.clone().unwrap_or_default(),
+            effects: match &config.states {
+                Some(states) => [
+                    &states.success,
+                    &states.failed,
+                    &states.running,
+                    &states.canceled,
+                    &states.unknown,
+                    &states.queued,
+                    &states.blocked,
+                ]
+                .iter()
+                .any(|state| matches!(state, Some(state) if state.effect.is_some())),
+                None => false,
+            },
+            saved: Mutex::new(SavedLights::NotSaved),
+        }
+    }
+
+    pub fn restore(&self, client: &impl HttpClient) -> DuckResult<()> {
+        let mut saved = self.saved.lock().unwrap();
+        match &*saved {
+            // We never touched the lights.
+            SavedLights::NotSaved => Ok(()),
+            SavedLights::Saved(lights) => {
+                for (light, body) in lights.iter() {
+                    self.put_light(client, light, body.clone())?;
+                }
+                *saved = SavedLights::NotSaved;
+                Ok(())
+            }
+            // We don't know what state the lights were in,
+            // so the best thing we can do is to turn them off.
+            SavedLights::Failed => self.turn_off(client),
         }
     }
 
@@ -31,58 +96,291 @@ impl HueClient {
     }
 
     pub fn set_state(&self, client: &impl HttpClient, status: BuildStatus) -> DuckResult<()> {
-        if let Some((x, y)) = Self::get_cie_coordinates(&status) {
-            self.set_light_state(
-                client,
-                format!(
-                    "{{\"alert\":\"{alert}\",\"xy\":[{x},{y}],\"on\":{on},\"bri\":{brightness}}}",
-                    alert = match status {
-                        BuildStatus::Failed => "select",
-                        _ => "none",
-                    },
-                    x = x,
-                    y = y,
-                    brightness = self.brightness,
-                    on = true
-                ),
-            )?;
+        if let Some(state) = self.get_state(&status) {
+            self.save_lights(client);
+            match state {
+                HueState::Scene(scene) => self.activate_scene(client, &scene)?,
+                HueState::Light {
+                    xy: (x, y),
+                    brightness,
+                    alert,
+                    effect,
+                } => self.set_light_state(
+                    client,
+                    format!(
+                        "{{\"alert\":\"{alert}\",{effect}\"xy\":[{x},{y}],\"on\":{on},\"bri\":{brightness}}}",
+                        alert = match alert {
+                            HueAlert::None => "none",
+                            HueAlert::Select => "select",
+                            HueAlert::LongSelect => "lselect",
+                        },
+                        effect = match effect {
+                            _ if !self.effects => "",
+                            HueEffect::None => "\"effect\":\"none\",",
+                            HueEffect::ColorLoop => "\"effect\":\"colorloop\",",
+                        },
+                        x = x,
+                        y = y,
+                        brightness = brightness,
+                        on = true
+                    ),
+                )?,
+            };
         }
         Ok(())
     }
 
-    fn get_cie_coordinates(status: &BuildStatus) -> Option<(f32, f32)> {
-        return match status {
-            BuildStatus::Success => Some(Rgb::new(0, 255, 0).to_cie_coordinates()),
-            BuildStatus::Failed => Some(Rgb::new(255, 0, 0).to_cie_coordinates()),
-            BuildStatus::Running => Some(Rgb::new(127, 200, 255).to_cie_coordinates()),
-            _ => None,
+    fn get_state(&self, status: &BuildStatus) -> Option<HueState> {
+        let (configured, default_alert) = match status {
+            BuildStatus::Success => (&self.states.success, HueAlert::None),
+            BuildStatus::Failed => (&self.states.failed, HueAlert::Select),
+            BuildStatus::Running => (&self.states.running, HueAlert::None),
+            BuildStatus::Queued if self.states.queued.is_none() => {
+                (&self.states.running, HueAlert::None)
+            }
+            BuildStatus::Queued => (&self.states.queued, HueAlert::None),
+            BuildStatus::Blocked => (&self.states.blocked, HueAlert::None),
+            BuildStatus::Canceled => (&self.states.canceled, HueAlert::None),
+            BuildStatus::Unknown | BuildStatus::Skipped => (&self.states.unknown, HueAlert::None),
+        };
+
+        let state = match configured {
+            Some(state) => state.clone(),
+            None => HueStateConfiguration::default(),
         };
+        if let Some(scene) = state.scene {
+            return Some(HueState::Scene(scene));
+        }
+
+        let color = match &state.color {
+            Some(color) => Rgb::parse(color),
+            None => Rgb::for_status(status),
+        }?;
+
+        Some(HueState::Light {
+            xy: color.to_cie_coordinates(),
+            brightness: state.brightness.unwrap_or(self.brightness),
+            alert: state.alert.unwrap_or(default_alert),
+            effect: state.effect.unwrap_or(HueEffect::None),
+        })
     }
 
-    fn set_light_state(&self, client: &impl HttpClient, body: String) -> DuckResult<()> {
-        for light in &self.lights {
+    /// Saves the current state of all lights the first time
+    /// they are changed, so it can be restored later.
+    fn save_lights(&self, client: &impl HttpClient) {
+        let mut saved = self.saved.lock().unwrap();
+        if let SavedLights::NotSaved = *saved {
+            *saved = match self.get_light_states(client) {
+                Ok(lights) => SavedLights::Saved(lights),
+                Err(e) => {
+                    warn!("Could not save the current state of the lights. {}", e);
+                    SavedLights::Failed
+                }
+            };
+        }
+    }
+
+    fn get_light_states(&self, client: &impl HttpClient) -> DuckResult<Vec<(String, String)>> {
+        let mut lights = self.lights.clone();
+        for group in self.groups.iter() {
+            let url = format!(
+                "{url}api/{username}/groups/{id}",
+                url = self.url,
+                username = self.username,
+                id = group
+            );
+            let group: HueGroup = self.get(client, &url)?;
+            for light in group.lights {
+                if !lights.contains(&light) {
+                    lights.push(light);
+                }
+            }
+        }
+
+        let mut result = Vec::new();
+        for light in lights {
             let url = format!(
-                "{url}api/{username}/lights/{id}/state",
+                "{url}api/{username}/lights/{id}",
                 url = self.url,
                 username = self.username,
                 id = light
             );
+            let light_info: HueLight = self.get(client, &url)?;
+            result.push((light, light_info.state.to_body()));
+        }
+        Ok(result)
+    }
 
-            let mut builder = HttpRequestBuilder::put(url);
-            builder.add_header("Content-Type", "application/json");
-            builder.add_header("Accept", "application/json");
-            builder.set_body(body.clone());
-
-            let response = client.send(&builder)?;
-            if !response.status().is_success() {
-                return Err(format_err!(
-                    "Could not update state for light '{id}' ({status})",
-                    id = light,
-                    status = response.status()
-                ));
-            }
+    fn activate_scene(&self, client: &impl HttpClient, scene: &str) -> DuckResult<()> {
+        // Scenes can be referenced by either name or ID.
+        let url = format!(
+            "{url}api/{username}/scenes",
+            url = self.url,
+            username = self.username
+        );
+        let scenes: HashMap<String, HueScene> = self.get(client, &url)?;
+        let id = match scenes
+            .iter()
+            .find(|(id, s)| &id[..] == scene || s.name == scene)
+        {
+            Some((id, _)) => id,
+            None => return Err(format_err!("Could not find Hue scene '{}'", scene)),
+        };
+
+        // Group 0 contains all lights, so use that if no groups are configured.
+        let body = format!("{{\"scene\":\"{}\"}}", id);
+        if self.groups.is_empty() {
+            self.put_group(client, "0", body.clone())?;
+        }
+        for group in self.groups.iter() {
+            self.put_group(client, group, body.clone())?;
         }
 
         Ok(())
     }
+
+    fn set_light_state(&self, client: &impl HttpClient, body: String) -> DuckResult<()> {
+        for light in &self.lights {
+            self.put_light(client, light, body.clone())?;
+        }
+        for group in &self.groups {
+            self.put_group(client, group, body.clone())?;
+        }
+
+        Ok(())
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(
+        &self,
+        client: &impl HttpClient,
+        url: &str,
+    ) -> DuckResult<T> {
+        let mut builder = HttpRequestBuilder::get(url);
+        builder.add_header("Accept", "application/json");
+
+        let mut response = client.send(&builder)?;
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Could not get '{url}' ({status})",
+                url = url,
+                status = response.status()
+            ));
+        }
+
+        response.deserialize_json()
+    }
+
+    fn put_light(&self, client: &impl HttpClient, light: &str, body: String) -> DuckResult<()> {
+        let url = format!(
+            "{url}api/{username}/lights/{id}/state",
+            url = self.url,
+            username = self.username,
+            id = light
+        );
+        let status = self.put(client, url, body)?;
+        if !status.is_success() {
+            return Err(format_err!(
+                "Could not update state for light '{id}' ({status})",
+                id = light,
+                status = status
+            ));
+        }
+        Ok(())
+    }
+
+    fn put_group(&self, client: &impl HttpClient, group: &str, body: String) -> DuckResult<()> {
+        let url = format!(
+            "{url}api/{username}/groups/{id}/action",
+            url = self.url,
+            username = self.username,
+            id = group
+        );
+        let status = self.put(client, url, body)?;
+        if !status.is_success() {
+            return Err(format_err!(
+                "Could not update state for group '{id}' ({status})",
+                id = group,
+                status = status
+            ));
+        }
+        Ok(())
+    }
+
+    fn put(&self, client: &impl HttpClient, url: String, body: String) -> DuckResult<StatusCode> {
+        let mut builder = HttpRequestBuilder::put(url);
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+        builder.set_body(body);
+
+        let response = client.send(&builder)?;
+        Ok(response.status())
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct HueGroup {
+    lights: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct HueScene {
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct HueLight {
+    state: HueLightState,
+}
+
+#[derive(Deserialize, Debug)]
+struct HueLightState {
+    on: bool,
+    #[serde(default)]
+    bri: Option<u8>,
+    #[serde(default)]
+    xy: Option<(f32, f32)>,
+    #[serde(default)]
+    ct: Option<u16>,
+    #[serde(default)]
+    hue: Option<u16>,
+    #[serde(default)]
+    sat: Option<u8>,
+    #[serde(default)]
+    effect: Option<String>,
+    #[serde(default)]
+    colormode: Option<String>,
+}
+
+impl HueLightState {
+    fn to_body(&self) -> String {
+        let mut state = json!({
+            "on": self.on,
+            "alert": "none",
+            "effect": self.effect.clone().unwrap_or_else(|| "none".to_string()),
+        });
+        if let Some(bri) = self.bri {
+            state["bri"] = json!(bri);
+        }
+        // Only restore the color using the mode the light was in.
+        match self.colormode.as_ref().map(|m| &m[..]) {
+            Some("xy") => {
+                if let Some((x, y)) = self.xy {
+                    state["xy"] = json!([x, y]);
+                }
+            }
+            Some("ct") => {
+                if let Some(ct) = self.ct {
+                    state["ct"] = json!(ct);
+                }
+            }
+            Some("hs") => {
+                if let (Some(hue), Some(sat)) = (self.hue, self.sat) {
+                    state["hue"] = json!(hue);
+                    state["sat"] = json!(sat);
+                }
+            }
+            _ => {}
+        }
+        state.to_string()
+    }
 }
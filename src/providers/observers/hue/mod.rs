@@ -36,6 +36,8 @@ impl<T: HttpClient + Default> HueObserver<T> {
                         Some(HashSet::from_iter(collectors.iter().cloned()))
                     }
                 },
+                view: config.view.clone(),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
             },
         }
     }
@@ -61,8 +63,8 @@ impl<T: HttpClient + Default> Observer for HueObserver<T> {
                 self.client.set_state(&self.http, status)?;
             }
             Observation::ShuttingDown => {
-                info!("[{}] Turning off all lights...", self.info.id);
-                self.client.turn_off(&self.http)?;
+                info!("[{}] Restoring light state...", self.info.id);
+                self.client.restore(&self.http)?;
             }
             _ => {}
         }
@@ -74,6 +76,7 @@ impl<T: HttpClient + Default> Observer for HueObserver<T> {
 mod tests {
     use super::*;
     use crate::builds::BuildStatus;
+    use crate::config::{HueAlert, HueEffect, HueStateConfiguration, HueStatesConfiguration};
     use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
     use reqwest::StatusCode;
     use test_case::test_case;
@@ -86,9 +89,13 @@ mod tests {
             enabled: Some(true),
             brightness: Some(255),
             collectors: None,
+            view: None,
+            pull_requests: None,
             hub_url: "https://example.com".to_string(),
             username: "patrik".to_string(),
             lights: vec!["foo".to_string()],
+            groups: None,
+            states: None,
         });
 
         let client = hue.get_client();
@@ -105,7 +112,12 @@ mod tests {
             .unwrap();
 
         // Then
-        let requests = client.get_sent_requests();
+        // The light state is read first so it can be restored later.
+        let requests: Vec<_> = client
+            .get_sent_requests()
+            .into_iter()
+            .filter(|r| r.method == HttpMethod::Put)
+            .collect();
         assert_eq!(1, requests.len());
         assert_eq!(HttpMethod::Put, requests[0].method);
         assert_eq!(
@@ -124,9 +136,13 @@ mod tests {
             enabled: Some(true),
             brightness: Some(255),
             collectors: None,
+            view: None,
+            pull_requests: None,
             hub_url: "https://example.com".to_string(),
             username: "patrik".to_string(),
             lights: vec!["foo".to_string()],
+            groups: None,
+            states: None,
         });
 
         let client = hue.get_client();
@@ -142,12 +158,341 @@ mod tests {
         hue.observe(Observation::DuckStatusChanged(status)).unwrap();
 
         // Then
-        let requests = client.get_sent_requests();
+        // The light state is read first so it can be restored later.
+        let requests: Vec<_> = client
+            .get_sent_requests()
+            .into_iter()
+            .filter(|r| r.method == HttpMethod::Put)
+            .collect();
         assert_eq!(1, requests.len());
         assert!(&requests[0].body.is_some());
         assert_eq!(expected, &requests[0].body.clone().unwrap());
     }
 
+    #[test_case(BuildStatus::Canceled ; "Canceled")]
+    #[test_case(BuildStatus::Unknown ; "Unknown")]
+    fn should_not_change_lights_for_unconfigured_status(status: BuildStatus) {
+        // Given
+        let hue = HueObserver::<MockHttpClient>::new(&HueConfiguration {
+            id: "hue".to_string(),
+            enabled: Some(true),
+            brightness: Some(255),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            hub_url: "https://example.com".to_string(),
+            username: "patrik".to_string(),
+            lights: vec!["foo".to_string()],
+            groups: None,
+            states: None,
+        });
+
+        let client = hue.get_client();
+
+        // When
+        hue.observe(Observation::DuckStatusChanged(status)).unwrap();
+
+        // Then
+        assert_eq!(0, client.get_sent_requests().len());
+    }
+
+    #[test]
+    fn should_use_configured_state() {
+        // Given
+        let hue = HueObserver::<MockHttpClient>::new(&HueConfiguration {
+            id: "hue".to_string(),
+            enabled: Some(true),
+            brightness: Some(255),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            hub_url: "https://example.com".to_string(),
+            username: "patrik".to_string(),
+            lights: vec!["foo".to_string()],
+            groups: None,
+            states: Some(HueStatesConfiguration {
+                running: Some(HueStateConfiguration {
+                    color: Some("#FF8000".to_string()),
+                    brightness: Some(128),
+                    alert: Some(HueAlert::LongSelect),
+                    effect: Some(HueEffect::ColorLoop),
+                    scene: None,
+                }),
+                canceled: Some(HueStateConfiguration {
+                    color: Some("#FFFFFF".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+        });
+
+        let client = hue.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Put,
+                "https://example.com/api/patrik/lights/foo/state",
+            )
+            .returns_status(StatusCode::OK),
+        );
+
+        // When
+        hue.observe(Observation::DuckStatusChanged(BuildStatus::Running))
+            .unwrap();
+        hue.observe(Observation::DuckStatusChanged(BuildStatus::Canceled))
+            .unwrap();
+
+        // Then
+        let requests: Vec<_> = client
+            .get_sent_requests()
+            .into_iter()
+            .filter(|r| r.method == HttpMethod::Put)
+            .collect();
+        assert_eq!(2, requests.len());
+        assert_eq!(
+            "{\"alert\":\"lselect\",\"effect\":\"colorloop\",\"xy\":[0.48973638,0.4603247],\"on\":true,\"bri\":128}",
+            &requests[0].body.clone().unwrap()
+        );
+        assert_eq!(
+            "{\"alert\":\"none\",\"effect\":\"none\",\"xy\":[0.33618072,0.36036962],\"on\":true,\"bri\":255}",
+            &requests[1].body.clone().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_update_groups() {
+        // Given
+        let hue = HueObserver::<MockHttpClient>::new(&HueConfiguration {
+            id: "hue".to_string(),
+            enabled: Some(true),
+            brightness: Some(255),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            hub_url: "https://example.com".to_string(),
+            username: "patrik".to_string(),
+            lights: vec![],
+            groups: Some(vec!["1".to_string()]),
+            states: None,
+        });
+
+        let client = hue.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://example.com/api/patrik/groups/1",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body("{ \"name\": \"Office\", \"lights\": [ \"2\" ] }"),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Put,
+                "https://example.com/api/patrik/groups/1/action",
+            )
+            .returns_status(StatusCode::OK),
+        );
+
+        // When
+        hue.observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+
+        // Then
+        let requests: Vec<_> = client
+            .get_sent_requests()
+            .into_iter()
+            .filter(|r| r.method == HttpMethod::Put)
+            .collect();
+        assert_eq!(1, requests.len());
+        assert_eq!(
+            "https://example.com/api/patrik/groups/1/action",
+            &requests[0].url
+        );
+    }
+
+    #[test_case(None, "0" ; "All lights")]
+    #[test_case(Some(vec!["1".to_string()]), "1" ; "Configured group")]
+    fn should_activate_scene_by_name(groups: Option<Vec<String>>, group: &str) {
+        // Given
+        let hue = HueObserver::<MockHttpClient>::new(&HueConfiguration {
+            id: "hue".to_string(),
+            enabled: Some(true),
+            brightness: Some(255),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            hub_url: "https://example.com".to_string(),
+            username: "patrik".to_string(),
+            lights: vec![],
+            groups,
+            states: Some(HueStatesConfiguration {
+                failed: Some(HueStateConfiguration {
+                    scene: Some("Red alert".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+        });
+
+        let client = hue.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Get,
+                "https://example.com/api/patrik/groups/1",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body("{ \"lights\": [ ] }"),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "https://example.com/api/patrik/scenes")
+                .returns_status(StatusCode::OK)
+                .returns_body(
+                    "{ \"abc\": { \"name\": \"Relax\" }, \"def\": { \"name\": \"Red alert\" } }",
+                ),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Put,
+                format!("https://example.com/api/patrik/groups/{}/action", group),
+            )
+            .returns_status(StatusCode::OK),
+        );
+
+        // When
+        hue.observe(Observation::DuckStatusChanged(BuildStatus::Failed))
+            .unwrap();
+
+        // Then
+        let requests: Vec<_> = client
+            .get_sent_requests()
+            .into_iter()
+            .filter(|r| r.method == HttpMethod::Put)
+            .collect();
+        assert_eq!(1, requests.len());
+        assert_eq!(
+            format!("https://example.com/api/patrik/groups/{}/action", group),
+            requests[0].url
+        );
+        assert_eq!("{\"scene\":\"def\"}", &requests[0].body.clone().unwrap());
+    }
+
+    #[test]
+    fn should_restore_previous_state_when_shutting_down() {
+        // Given
+        let hue = HueObserver::<MockHttpClient>::new(&HueConfiguration {
+            id: "hue".to_string(),
+            enabled: Some(true),
+            brightness: Some(255),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            hub_url: "https://example.com".to_string(),
+            username: "patrik".to_string(),
+            lights: vec!["foo".to_string()],
+            groups: None,
+            states: None,
+        });
+
+        let client = hue.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Get, "https://example.com/api/patrik/lights/foo")
+                .returns_status(StatusCode::OK)
+                .returns_body(
+                    "{ \"state\": { \"on\": true, \"bri\": 100, \"ct\": 366, \"xy\": [0.1, 0.2], \"colormode\": \"ct\" } }",
+                ),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Put,
+                "https://example.com/api/patrik/lights/foo/state",
+            )
+            .returns_status(StatusCode::OK),
+        );
+
+        // When
+        hue.observe(Observation::DuckStatusChanged(BuildStatus::Failed))
+            .unwrap();
+        hue.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let requests: Vec<_> = client
+            .get_sent_requests()
+            .into_iter()
+            .filter(|r| r.method == HttpMethod::Put)
+            .collect();
+        assert_eq!(2, requests.len());
+        assert_eq!(
+            "{\"alert\":\"none\",\"bri\":100,\"ct\":366,\"effect\":\"none\",\"on\":true}",
+            &requests[1].body.clone().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_turn_off_lights_when_shutting_down_if_state_could_not_be_saved() {
+        // Given
+        let hue = HueObserver::<MockHttpClient>::new(&HueConfiguration {
+            id: "hue".to_string(),
+            enabled: Some(true),
+            brightness: Some(255),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            hub_url: "https://example.com".to_string(),
+            username: "patrik".to_string(),
+            lights: vec!["foo".to_string()],
+            groups: None,
+            states: None,
+        });
+
+        let client = hue.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Put,
+                "https://example.com/api/patrik/lights/foo/state",
+            )
+            .returns_status(StatusCode::OK),
+        );
+
+        // When
+        hue.observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+        hue.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let requests: Vec<_> = client
+            .get_sent_requests()
+            .into_iter()
+            .filter(|r| r.method == HttpMethod::Put)
+            .collect();
+        assert_eq!(2, requests.len());
+        assert_eq!("{\"on\": false }", &requests[1].body.clone().unwrap());
+    }
+
+    #[test]
+    fn should_not_touch_lights_when_shutting_down_if_never_changed() {
+        // Given
+        let hue = HueObserver::<MockHttpClient>::new(&HueConfiguration {
+            id: "hue".to_string(),
+            enabled: Some(true),
+            brightness: Some(255),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            hub_url: "https://example.com".to_string(),
+            username: "patrik".to_string(),
+            lights: vec!["foo".to_string()],
+            groups: None,
+            states: None,
+        });
+
+        let client = hue.get_client();
+
+        // When
+        hue.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        assert_eq!(0, client.get_sent_requests().len());
+    }
+
     #[test]
     #[should_panic(expected = "Could not update state for light \\'foo\\' (502 Bad Gateway)")]
     fn should_return_error_if_server_return_non_successful_http_status_code() {
@@ -157,9 +502,13 @@ mod tests {
             enabled: Some(true),
             brightness: Some(255),
             collectors: None,
+            view: None,
+            pull_requests: None,
             hub_url: "https://example.com".to_string(),
             username: "patrik".to_string(),
             lights: vec!["foo".to_string()],
+            groups: None,
+            states: None,
         });
 
         let client = hue.get_client();
@@ -1,7 +1,7 @@
 use url::Url;
 
-use crate::config::{HueConfiguration, Validate};
-use crate::utils::DuckResult;
+use crate::config::{HueConfiguration, HueStateConfiguration, Validate};
+use crate::utils::{colors::Rgb, DuckResult};
 
 impl Validate for HueConfiguration {
     fn validate(&self) -> DuckResult<()> {
@@ -14,10 +14,44 @@ impl Validate for HueConfiguration {
         if self.username.is_empty() {
             return Err(format_err!("Hue username is empty."));
         }
+        let has_groups = match &self.groups {
+            Some(groups) => !groups.is_empty(),
+            None => false,
+        };
+        if self.lights.is_empty() && !has_groups {
+            return Err(format_err!("Hue observer has no lights or groups."));
+        }
+        if let Some(states) = &self.states {
+            validate_state("success", &states.success)?;
+            validate_state("failed", &states.failed)?;
+            validate_state("running", &states.running)?;
+            validate_state("canceled", &states.canceled)?;
+            validate_state("unknown", &states.unknown)?;
+        }
         Ok(())
     }
 }
 
+fn validate_state(name: &str, state: &Option<HueStateConfiguration>) -> DuckResult<()> {
+    if let Some(state) = state {
+        if let Some(color) = &state.color {
+            if Rgb::parse(color).is_none() {
+                return Err(format_err!(
+                    "Hue {} color '{}' is not a valid hex color.",
+                    name,
+                    color
+                ));
+            }
+        }
+        if let Some(scene) = &state.scene {
+            if scene.is_empty() {
+                return Err(format_err!("Hue {} scene is empty.", name));
+            }
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::config::Configuration;
@@ -101,6 +135,88 @@ mod tests {
         )
         .unwrap();
 
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+    #[test]
+    #[should_panic(expected = "Hue observer has no lights or groups.")]
+    fn should_return_error_if_hue_has_no_lights_or_groups() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "hue": {
+                            "id": "bar",
+                            "hubUrl": "https://localhost:6000",
+                            "username": "vpBIFkq-2iWFvSLf62u1HvcmLbqbDf76N-CTom8b",
+                            "groups": [ ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Hue failed color 'red' is not a valid hex color.")]
+    fn should_return_error_if_hue_state_color_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "hue": {
+                            "id": "bar",
+                            "hubUrl": "https://localhost:6000",
+                            "username": "vpBIFkq-2iWFvSLf62u1HvcmLbqbDf76N-CTom8b",
+                            "lights": [ "3" ],
+                            "states": { "failed": { "color": "red" } }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Hue running scene is empty.")]
+    fn should_return_error_if_hue_state_scene_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "hue": {
+                            "id": "bar",
+                            "hubUrl": "https://localhost:6000",
+                            "username": "vpBIFkq-2iWFvSLf62u1HvcmLbqbDf76N-CTom8b",
+                            "groups": [ "1" ],
+                            "states": { "running": { "scene": "" } }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
         let collection = DuckProviderCollection::new();
         collection.get_observers(&config).unwrap();
     }
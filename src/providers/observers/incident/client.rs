@@ -0,0 +1,178 @@
+use serde_json::Value;
+
+use crate::builds::Build;
+use crate::config::{IncidentConfiguration, IncidentService};
+use crate::utils::hash;
+use crate::utils::http::{HttpClient, HttpRequestBuilder, HttpResponse};
+use crate::utils::DuckResult;
+
+pub struct IncidentClient {
+    service: IncidentService,
+}
+
+impl IncidentClient {
+    pub fn new(config: &IncidentConfiguration) -> Self {
+        IncidentClient {
+            service: config.service.clone(),
+        }
+    }
+
+    pub fn open(&self, client: &impl HttpClient, build: &Build) -> DuckResult<()> {
+        match &self.service {
+            IncidentService::PagerDuty {
+                routing_key,
+                server_url,
+            } => self.send(
+                client,
+                "PagerDuty",
+                get_pagerduty_url(server_url),
+                None,
+                json!({
+                    "routing_key": routing_key,
+                    "event_action": "trigger",
+                    "dedup_key": get_key(build),
+                    "payload": {
+                        "summary": get_summary(build),
+                        "source": build.collector,
+                        "severity": "critical",
+                        "component": build.project_name,
+                        "group": build.definition_name,
+                        "custom_details": get_details(build)
+                    },
+                    "links": [
+                        { "href": build.url, "text": "Build" }
+                    ]
+                }),
+            ),
+            IncidentService::Opsgenie {
+                api_key,
+                server_url,
+            } => self.send(
+                client,
+                "Opsgenie",
+                format!("{}/v2/alerts", get_opsgenie_url(server_url)),
+                Some(api_key),
+                json!({
+                    "message": get_summary(build),
+                    "alias": get_key(build),
+                    "description": build.url,
+                    "source": "Duck",
+                    "entity": build.project_name,
+                    "priority": "P1",
+                    "details": get_details(build)
+                }),
+            ),
+        }
+    }
+
+    pub fn resolve(&self, client: &impl HttpClient, build: &Build) -> DuckResult<()> {
+        match &self.service {
+            IncidentService::PagerDuty {
+                routing_key,
+                server_url,
+            } => self.send(
+                client,
+                "PagerDuty",
+                get_pagerduty_url(server_url),
+                None,
+                json!({
+                    "routing_key": routing_key,
+                    "event_action": "resolve",
+                    "dedup_key": get_key(build)
+                }),
+            ),
+            IncidentService::Opsgenie {
+                api_key,
+                server_url,
+            } => self.send(
+                client,
+                "Opsgenie",
+                format!(
+                    "{}/v2/alerts/{}/close?identifierType=alias",
+                    get_opsgenie_url(server_url),
+                    get_key(build)
+                ),
+                Some(api_key),
+                json!({
+                    "source": "Duck",
+                    "note": format!("{} is {:?} again", build.definition_name, build.status)
+                }),
+            ),
+        }
+    }
+
+    fn send(
+        &self,
+        client: &impl HttpClient,
+        service: &str,
+        url: String,
+        api_key: Option<&String>,
+        payload: Value,
+    ) -> DuckResult<()> {
+        let mut builder = HttpRequestBuilder::post(url);
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+        if let Some(api_key) = api_key {
+            builder.add_header("Authorization", &*format!("GenieKey {}", api_key));
+        }
+        builder.set_body(payload.to_string());
+
+        let response = client.send(&builder)?;
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Could not send incident to {} ({})",
+                service,
+                response.status()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Incidents are deduplicated by collector, project and definition
+/// rather than by partition, since deployments (such as the ones from
+/// Octopus Deploy) use the release as branch, and a new release
+/// must be able to resolve the incident opened by a previous one.
+pub fn get_key(build: &Build) -> String {
+    format!(
+        "duck-{}",
+        hash::stable_hash(&[&build.collector, &build.project_id, &build.definition_id])
+    )
+}
+
+fn get_pagerduty_url(server_url: &Option<String>) -> String {
+    format!(
+        "{}/v2/enqueue",
+        server_url
+            .as_ref()
+            .map(|u| u.trim_end_matches('/'))
+            .unwrap_or("https://events.pagerduty.com")
+    )
+}
+
+fn get_opsgenie_url(server_url: &Option<String>) -> &str {
+    server_url
+        .as_ref()
+        .map(|u| u.trim_end_matches('/'))
+        .unwrap_or("https://api.opsgenie.com")
+}
+
+fn get_summary(build: &Build) -> String {
+    format!(
+        "{}::{} ({}) {:?}",
+        build.project_name, build.definition_name, build.branch, build.status
+    )
+}
+
+fn get_details(build: &Build) -> Value {
+    json!({
+        "provider": format!("{:?}", build.provider),
+        "collector": build.collector,
+        "project": build.project_name,
+        "definition": build.definition_name,
+        "branch": build.branch,
+        "buildNumber": build.build_number,
+        "url": build.url
+    })
+}
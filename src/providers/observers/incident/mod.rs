@@ -0,0 +1,410 @@
+use std::collections::{HashMap, HashSet};
+use std::iter::FromIterator;
+use std::sync::Mutex;
+
+use log::info;
+
+use crate::builds::BuildStatus;
+use crate::config::IncidentConfiguration;
+use crate::providers::observers::{Observation, Observer, ObserverInfo};
+use crate::utils::http::HttpClient;
+use crate::utils::DuckResult;
+
+use self::client::IncidentClient;
+
+mod client;
+mod validation;
+
+pub struct IncidentObserver<T: HttpClient + Default> {
+    client: IncidentClient,
+    http: T,
+    definitions: Option<HashSet<String>>,
+    /// The last status that was sent for each incident key.
+    incidents: Mutex<HashMap<String, BuildStatus>>,
+    info: ObserverInfo,
+}
+
+impl<T: HttpClient + Default> IncidentObserver<T> {
+    pub fn new(config: &IncidentConfiguration) -> Self {
+        IncidentObserver {
+            client: IncidentClient::new(config),
+            http: Default::default(),
+            definitions: match &config.definitions {
+                Option::None => Option::None,
+                Option::Some(definitions) => Some(HashSet::from_iter(definitions.iter().cloned())),
+            },
+            incidents: Mutex::new(HashMap::new()),
+            info: ObserverInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    None => true,
+                    Some(e) => e,
+                },
+                collectors: match &config.collectors {
+                    Option::None => Option::None,
+                    Option::Some(collectors) => {
+                        Some(HashSet::from_iter(collectors.iter().cloned()))
+                    }
+                },
+                view: config.view.clone(),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+}
+
+impl<T: HttpClient + Default> Observer for IncidentObserver<T> {
+    fn info(&self) -> &ObserverInfo {
+        &self.info
+    }
+
+    fn observe(&self, observation: Observation) -> DuckResult<()> {
+        // A new release is a new partition, so its first deployment is
+        // never reported as a status change. Look at all updates instead.
+        if let Observation::BuildUpdated(build) = observation {
+            if let Some(definitions) = &self.definitions {
+                if !definitions.contains(&build.definition_name) {
+                    return Ok(());
+                }
+            }
+
+            let key = client::get_key(build);
+            let mut incidents = self.incidents.lock().unwrap();
+            if incidents.get(&key) == Some(&build.status) {
+                return Ok(());
+            }
+
+            match build.status {
+                // Nobody should be paged about muted builds,
+                // but their incidents are still resolved.
+                BuildStatus::Failed if build.muted.is_none() => {
+                    info!(
+                        "[{}] Opening incident for {}::{}...",
+                        self.info.id, build.project_name, build.definition_name
+                    );
+                    self.client.open(&self.http, build)?;
+                }
+                BuildStatus::Success => {
+                    info!(
+                        "[{}] Resolving incident for {}::{}...",
+                        self.info.id, build.project_name, build.definition_name
+                    );
+                    self.client.resolve(&self.http, build)?;
+                }
+                _ => return Ok(()),
+            }
+
+            incidents.insert(key, build.status.clone());
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::{BuildBuilder, BuildProvider, BuildStatus};
+    use crate::config::IncidentService;
+    use crate::engine::state::{BuildRepository, BuildUpdateResult};
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+
+    #[test]
+    fn should_trigger_pagerduty_incident_when_build_fails() {
+        // Given
+        let observer = IncidentObserver::<MockHttpClient>::new(&IncidentConfiguration {
+            id: "incident".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            definitions: None,
+            service: IncidentService::PagerDuty {
+                routing_key: "routing".to_string(),
+                server_url: Some("http://localhost:8080/".to_string()),
+            },
+        });
+
+        let client = observer.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "http://localhost:8080/v2/enqueue")
+                .returns_status(StatusCode::ACCEPTED),
+        );
+        let build = BuildBuilder::dummy().status(BuildStatus::Failed).unwrap();
+
+        // When
+        observer.observe(Observation::BuildUpdated(&build)).unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!("http://localhost:8080/v2/enqueue", &requests[0].url);
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()).unwrap();
+        assert_eq!("routing", body["routing_key"]);
+        assert_eq!("trigger", body["event_action"]);
+        assert_eq!(client::get_key(&build), body["dedup_key"]);
+        assert_eq!(
+            "project_name::definition_name (branch) Failed",
+            body["payload"]["summary"]
+        );
+        assert_eq!("https://dummy", body["links"][0]["href"]);
+    }
+
+    #[test]
+    fn should_resolve_pagerduty_incident_when_build_succeeds() {
+        // Given
+        let observer = IncidentObserver::<MockHttpClient>::new(&IncidentConfiguration {
+            id: "incident".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            definitions: None,
+            service: IncidentService::PagerDuty {
+                routing_key: "routing".to_string(),
+                server_url: Some("http://localhost:8080/".to_string()),
+            },
+        });
+
+        let client = observer.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "http://localhost:8080/v2/enqueue")
+                .returns_status(StatusCode::ACCEPTED),
+        );
+        let build = BuildBuilder::dummy().status(BuildStatus::Success).unwrap();
+
+        // When
+        observer.observe(Observation::BuildUpdated(&build)).unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()).unwrap();
+        assert_eq!("resolve", body["event_action"]);
+        assert_eq!(client::get_key(&build), body["dedup_key"]);
+    }
+
+    #[test]
+    fn should_ignore_builds_for_other_definitions() {
+        // Given
+        let observer = IncidentObserver::<MockHttpClient>::new(&IncidentConfiguration {
+            id: "incident".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            definitions: Some(vec!["Production".to_string()]),
+            service: IncidentService::PagerDuty {
+                routing_key: "routing".to_string(),
+                server_url: Some("http://localhost:8080/".to_string()),
+            },
+        });
+
+        let client = observer.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "http://localhost:8080/v2/enqueue")
+                .returns_status(StatusCode::ACCEPTED),
+        );
+
+        // When
+        observer
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        assert_eq!(0, client.get_sent_requests().len());
+    }
+
+    #[test]
+    fn should_create_and_close_opsgenie_alert() {
+        // Given
+        let observer = IncidentObserver::<MockHttpClient>::new(&IncidentConfiguration {
+            id: "incident".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            definitions: None,
+            service: IncidentService::Opsgenie {
+                api_key: "secret".to_string(),
+                server_url: None,
+            },
+        });
+        let build = BuildBuilder::dummy().status(BuildStatus::Failed).unwrap();
+        let close_url = format!(
+            "https://api.opsgenie.com/v2/alerts/{}/close?identifierType=alias",
+            client::get_key(&build)
+        );
+        let client = observer.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://api.opsgenie.com/v2/alerts")
+                .returns_status(StatusCode::ACCEPTED),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, &close_url[..])
+                .returns_status(StatusCode::ACCEPTED),
+        );
+
+        // When
+        observer.observe(Observation::BuildUpdated(&build)).unwrap();
+        observer
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy().status(BuildStatus::Success).unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(2, requests.len());
+        assert_eq!(
+            "GenieKey secret",
+            requests[0].headers.get("Authorization").unwrap()
+        );
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()).unwrap();
+        assert_eq!(client::get_key(&build), body["alias"]);
+        assert_eq!(
+            "project_name::definition_name (branch) Failed",
+            body["message"]
+        );
+        assert_eq!(close_url, requests[1].url);
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not send incident to PagerDuty (400 Bad Request)")]
+    fn should_return_error_if_server_return_non_successful_http_status_code() {
+        // Given
+        let observer = IncidentObserver::<MockHttpClient>::new(&IncidentConfiguration {
+            id: "incident".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            definitions: None,
+            service: IncidentService::PagerDuty {
+                routing_key: "routing".to_string(),
+                server_url: Some("http://localhost:8080/".to_string()),
+            },
+        });
+
+        let client = observer.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "http://localhost:8080/v2/enqueue")
+                .returns_status(StatusCode::BAD_REQUEST),
+        );
+
+        // When, Then
+        observer
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+    }
+
+    #[test]
+    fn should_resolve_incident_opened_by_previous_release() {
+        // Given
+        let observer = IncidentObserver::<MockHttpClient>::new(&IncidentConfiguration {
+            id: "incident".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            definitions: None,
+            service: IncidentService::PagerDuty {
+                routing_key: "routing".to_string(),
+                server_url: Some("http://localhost:8080/".to_string()),
+            },
+        });
+
+        let client = observer.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "http://localhost:8080/v2/enqueue")
+                .returns_status(StatusCode::ACCEPTED),
+        );
+        let repository = BuildRepository::new();
+        let deployments = vec![
+            ("Deployments-1", "Releases-1", BuildStatus::Success),
+            ("Deployments-2", "Releases-2", BuildStatus::Failed),
+            ("Deployments-3", "Releases-3", BuildStatus::Success),
+        ];
+
+        // When
+        for (deployment, release, status) in deployments {
+            let build = BuildBuilder::dummy()
+                .build_id(deployment)
+                .provider(BuildProvider::OctopusDeploy)
+                .collector("octopus")
+                .project_id("Projects-1")
+                .project_name("Duck")
+                .definition_id("Environments-1")
+                .definition_name("Production")
+                .branch(release)
+                .status(status)
+                .unwrap();
+            if repository.update(&build) != BuildUpdateResult::Unchanged {
+                observer.observe(Observation::BuildUpdated(&build)).unwrap();
+            }
+        }
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(3, requests.len());
+        let bodies: Vec<serde_json::Value> = requests
+            .iter()
+            .map(|r| serde_json::from_str(&r.body.clone().unwrap()).unwrap())
+            .collect();
+        assert_eq!("resolve", bodies[0]["event_action"]);
+        assert_eq!("trigger", bodies[1]["event_action"]);
+        assert_eq!("resolve", bodies[2]["event_action"]);
+        assert_eq!(bodies[1]["dedup_key"], bodies[2]["dedup_key"]);
+    }
+
+    #[test]
+    fn should_not_send_same_status_twice() {
+        // Given
+        let observer = IncidentObserver::<MockHttpClient>::new(&IncidentConfiguration {
+            id: "incident".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            definitions: None,
+            service: IncidentService::PagerDuty {
+                routing_key: "routing".to_string(),
+                server_url: Some("http://localhost:8080/".to_string()),
+            },
+        });
+
+        let client = observer.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "http://localhost:8080/v2/enqueue")
+                .returns_status(StatusCode::ACCEPTED),
+        );
+
+        // When
+        for build_id in &["1", "2"] {
+            observer
+                .observe(Observation::BuildUpdated(
+                    &BuildBuilder::dummy()
+                        .build_id(*build_id)
+                        .status(BuildStatus::Failed)
+                        .unwrap(),
+                ))
+                .unwrap();
+        }
+
+        // Then
+        assert_eq!(1, client.get_sent_requests().len());
+    }
+}
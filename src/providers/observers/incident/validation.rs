@@ -0,0 +1,126 @@
+use url::Url;
+
+use crate::config::{IncidentConfiguration, IncidentService, Validate};
+use crate::utils::DuckResult;
+
+impl Validate for IncidentConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if self.id.is_empty() {
+            return Err(format_err!("Incident observer have no ID."));
+        }
+        let server_url = match &self.service {
+            IncidentService::PagerDuty {
+                routing_key,
+                server_url,
+            } => {
+                if routing_key.is_empty() {
+                    return Err(format_err!("PagerDuty routing key is empty."));
+                }
+                server_url
+            }
+            IncidentService::Opsgenie {
+                api_key,
+                server_url,
+            } => {
+                if api_key.is_empty() {
+                    return Err(format_err!("Opsgenie API key is empty."));
+                }
+                server_url
+            }
+        };
+        if let Some(server_url) = server_url {
+            if let Err(e) = Url::parse(server_url) {
+                return Err(format_err!("Incident server URL is invalid: {}", e));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers::DuckProviderCollection;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "PagerDuty routing key is empty.")]
+    fn should_return_error_if_pagerduty_routing_key_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "incident": {
+                            "id": "foo",
+                            "service": {
+                                "pagerduty": { "routingKey": "" }
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Opsgenie API key is empty.")]
+    fn should_return_error_if_opsgenie_api_key_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "incident": {
+                            "id": "foo",
+                            "service": {
+                                "opsgenie": { "apiKey": "" }
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Incident server URL is invalid: relative URL without a base")]
+    fn should_return_error_if_incident_server_url_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "incident": {
+                            "id": "foo",
+                            "service": {
+                                "opsgenie": { "apiKey": "secret", "serverUrl": "foo" }
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+}
@@ -0,0 +1,161 @@
+use std::sync::Mutex;
+
+use crate::builds::BuildStatus;
+use crate::config::{
+    LifxConfiguration, LifxEffect, LifxStateConfiguration, LifxStatesConfiguration,
+};
+use crate::utils::http::{HttpClient, HttpRequestBuilder, HttpResponse};
+use crate::utils::{colors::Rgb, DuckResult};
+
+pub struct LifxClient {
+    brightness: u8,
+    server_url: String,
+    token: String,
+    selector: String,
+    states: LifxStatesConfiguration,
+    effect_running: Mutex<bool>,
+}
+
+impl LifxClient {
+    pub fn new(config: &LifxConfiguration) -> Self {
+        LifxClient {
+            brightness: config.brightness.unwrap_or(255),
+            server_url: config
+                .server_url
+                .clone()
+                .unwrap_or_else(|| "https://api.lifx.com".to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            token: config.token.clone(),
+            selector: config.selector.clone().unwrap_or_else(|| "all".to_string()),
+            states: config.states.clone().unwrap_or_default(),
+            effect_running: Mutex::new(false),
+        }
+    }
+
+    pub fn turn_off(&self, client: &impl HttpClient) -> DuckResult<()> {
+        self.stop_effect(client)?;
+        self.send(
+            client,
+            HttpRequestBuilder::put(self.get_url("state")),
+            json!({ "power": "off" }),
+        )
+    }
+
+    pub fn set_state(&self, client: &impl HttpClient, status: BuildStatus) -> DuckResult<()> {
+        let state = self.get_state(&status);
+
+        let color = match &state.color {
+            Some(color) => Rgb::parse(color),
+            None => Rgb::for_status(&status),
+        };
+        let color = match color {
+            Some(color) => color.to_hex(),
+            None => return Ok(()),
+        };
+        let brightness = get_brightness(state.brightness.unwrap_or(self.brightness));
+
+        // A running effect is not affected by changing the state,
+        // so we need to stop it before doing anything else.
+        self.stop_effect(client)?;
+
+        match state.effect.unwrap_or(LifxEffect::None) {
+            LifxEffect::None => self.send(
+                client,
+                HttpRequestBuilder::put(self.get_url("state")),
+                json!({
+                    "power": "on",
+                    "color": color,
+                    "brightness": brightness,
+                }),
+            ),
+            effect => {
+                self.send(
+                    client,
+                    HttpRequestBuilder::post(self.get_url(match effect {
+                        LifxEffect::Pulse => "effects/pulse",
+                        _ => "effects/breathe",
+                    })),
+                    // Keep the effect going for about an hour,
+                    // or until the build status changes.
+                    json!({
+                        "color": format!("{} brightness:{}", color, brightness),
+                        "period": 2,
+                        "cycles": 1800,
+                        "persist": true,
+                        "power_on": true,
+                    }),
+                )?;
+                *self.effect_running.lock().unwrap() = true;
+                Ok(())
+            }
+        }
+    }
+
+    fn get_state(&self, status: &BuildStatus) -> LifxStateConfiguration {
+        let configured = match status {
+            BuildStatus::Success => &self.states.success,
+            BuildStatus::Failed => &self.states.failed,
+            BuildStatus::Running => &self.states.running,
+            BuildStatus::Queued if self.states.queued.is_none() => &self.states.running,
+            BuildStatus::Queued => &self.states.queued,
+            BuildStatus::Blocked => &self.states.blocked,
+            BuildStatus::Canceled => &self.states.canceled,
+            BuildStatus::Unknown | BuildStatus::Skipped => &self.states.unknown,
+        };
+        match configured {
+            Some(state) => state.clone(),
+            None => LifxStateConfiguration::default(),
+        }
+    }
+
+    fn stop_effect(&self, client: &impl HttpClient) -> DuckResult<()> {
+        let mut effect_running = self.effect_running.lock().unwrap();
+        if *effect_running {
+            self.send(
+                client,
+                HttpRequestBuilder::post(self.get_url("effects/off")),
+                json!({}),
+            )?;
+            *effect_running = false;
+        }
+        Ok(())
+    }
+
+    fn get_url(&self, endpoint: &str) -> String {
+        format!(
+            "{server}/v1/lights/{selector}/{endpoint}",
+            server = self.server_url,
+            selector = self.selector,
+            endpoint = endpoint
+        )
+    }
+
+    fn send(
+        &self,
+        client: &impl HttpClient,
+        mut builder: HttpRequestBuilder,
+        body: serde_json::Value,
+    ) -> DuckResult<()> {
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+        builder.add_header("Authorization", &*format!("Bearer {}", self.token));
+        builder.set_body(body.to_string());
+
+        let response = client.send(&builder)?;
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Could not update LIFX lights '{selector}' ({status})",
+                selector = self.selector,
+                status = response.status()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+// LIFX expects the brightness to be a value between 0.0 and 1.0.
+fn get_brightness(brightness: u8) -> f64 {
+    (f64::from(brightness) / 255.0 * 100.0).round() / 100.0
+}
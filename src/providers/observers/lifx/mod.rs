@@ -0,0 +1,331 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use log::info;
+
+use crate::config::LifxConfiguration;
+use crate::providers::observers::{Observation, Observer, ObserverInfo};
+use crate::utils::http::HttpClient;
+use crate::utils::DuckResult;
+
+use self::client::LifxClient;
+
+mod client;
+mod validation;
+
+pub struct LifxObserver<T: HttpClient + Default> {
+    client: LifxClient,
+    http: T,
+    info: ObserverInfo,
+}
+
+impl<T: HttpClient + Default> LifxObserver<T> {
+    pub fn new(config: &LifxConfiguration) -> Self {
+        LifxObserver {
+            client: LifxClient::new(config),
+            http: Default::default(),
+            info: ObserverInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    None => true,
+                    Some(e) => e,
+                },
+                collectors: match &config.collectors {
+                    Option::None => Option::None,
+                    Option::Some(collectors) => {
+                        Some(HashSet::from_iter(collectors.iter().cloned()))
+                    }
+                },
+                view: config.view.clone(),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+}
+
+impl<T: HttpClient + Default> Observer for LifxObserver<T> {
+    fn info(&self) -> &ObserverInfo {
+        &self.info
+    }
+
+    fn observe(&self, observation: Observation) -> DuckResult<()> {
+        match observation {
+            Observation::DuckStatusChanged(status) => {
+                info!(
+                    "[{}] Setting light state to '{:?}'...",
+                    self.info.id, status
+                );
+                self.client.set_state(&self.http, status)?;
+            }
+            Observation::ShuttingDown => {
+                info!("[{}] Turning off all lights...", self.info.id);
+                self.client.turn_off(&self.http)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildStatus;
+    use crate::config::{LifxEffect, LifxStateConfiguration, LifxStatesConfiguration};
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+    use test_case::test_case;
+
+    #[test]
+    fn should_send_request_to_correct_url() {
+        // Given
+        let lifx = LifxObserver::<MockHttpClient>::new(&LifxConfiguration {
+            id: "lifx".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            server_url: Some("https://example.com/".to_string()),
+            token: "secret".to_string(),
+            selector: Some("group:Office".to_string()),
+            brightness: None,
+            states: None,
+        });
+
+        let client = lifx.get_client();
+        for endpoint in &["state", "effects/breathe", "effects/pulse", "effects/off"] {
+            client.add_response(
+                MockHttpResponseBuilder::new(
+                    HttpMethod::Put,
+                    format!("https://example.com/v1/lights/group:Office/{}", endpoint),
+                )
+                .returns_status(StatusCode::OK),
+            );
+        }
+
+        // When
+        lifx.observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(HttpMethod::Put, requests[0].method);
+        assert_eq!(
+            "https://example.com/v1/lights/group:Office/state",
+            &requests[0].url
+        );
+        assert_eq!(
+            "Bearer secret",
+            requests[0].headers.get("Authorization").unwrap()
+        );
+    }
+
+    #[test_case(BuildStatus::Success, "{\"brightness\":1.0,\"color\":\"#00ff00\",\"power\":\"on\"}" ; "Success")]
+    #[test_case(BuildStatus::Failed, "{\"brightness\":1.0,\"color\":\"#ff0000\",\"power\":\"on\"}" ; "Failed")]
+    #[test_case(BuildStatus::Running, "{\"brightness\":1.0,\"color\":\"#7fc8ff\",\"power\":\"on\"}" ; "Running")]
+    fn should_send_correct_payload(status: BuildStatus, expected: &str) {
+        // Given
+        let lifx = LifxObserver::<MockHttpClient>::new(&LifxConfiguration {
+            id: "lifx".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            server_url: Some("https://example.com/".to_string()),
+            token: "secret".to_string(),
+            selector: Some("group:Office".to_string()),
+            brightness: None,
+            states: None,
+        });
+
+        let client = lifx.get_client();
+        for endpoint in &["state", "effects/breathe", "effects/pulse", "effects/off"] {
+            client.add_response(
+                MockHttpResponseBuilder::new(
+                    HttpMethod::Put,
+                    format!("https://example.com/v1/lights/group:Office/{}", endpoint),
+                )
+                .returns_status(StatusCode::OK),
+            );
+        }
+
+        // When
+        lifx.observe(Observation::DuckStatusChanged(status))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(expected, &requests[0].body.clone().unwrap());
+    }
+
+    #[test_case(BuildStatus::Canceled ; "Canceled")]
+    #[test_case(BuildStatus::Unknown ; "Unknown")]
+    fn should_not_change_lights_for_unconfigured_status(status: BuildStatus) {
+        // Given
+        let lifx = LifxObserver::<MockHttpClient>::new(&LifxConfiguration {
+            id: "lifx".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            server_url: Some("https://example.com/".to_string()),
+            token: "secret".to_string(),
+            selector: Some("group:Office".to_string()),
+            brightness: None,
+            states: None,
+        });
+
+        let client = lifx.get_client();
+        for endpoint in &["state", "effects/breathe", "effects/pulse", "effects/off"] {
+            client.add_response(
+                MockHttpResponseBuilder::new(
+                    HttpMethod::Put,
+                    format!("https://example.com/v1/lights/group:Office/{}", endpoint),
+                )
+                .returns_status(StatusCode::OK),
+            );
+        }
+
+        // When
+        lifx.observe(Observation::DuckStatusChanged(status))
+            .unwrap();
+
+        // Then
+        assert_eq!(0, client.get_sent_requests().len());
+    }
+
+    #[test]
+    fn should_start_and_stop_configured_effect() {
+        // Given
+        let lifx = LifxObserver::<MockHttpClient>::new(&LifxConfiguration {
+            id: "lifx".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            server_url: Some("https://example.com/".to_string()),
+            token: "secret".to_string(),
+            selector: Some("group:Office".to_string()),
+            brightness: None,
+            states: Some(LifxStatesConfiguration {
+                running: Some(LifxStateConfiguration {
+                    color: Some("#FF8000".to_string()),
+                    brightness: Some(128),
+                    effect: Some(LifxEffect::Breathe),
+                }),
+                ..Default::default()
+            }),
+        });
+
+        let client = lifx.get_client();
+        for endpoint in &["state", "effects/breathe", "effects/pulse", "effects/off"] {
+            client.add_response(
+                MockHttpResponseBuilder::new(
+                    HttpMethod::Put,
+                    format!("https://example.com/v1/lights/group:Office/{}", endpoint),
+                )
+                .returns_status(StatusCode::OK),
+            );
+        }
+
+        // When
+        lifx.observe(Observation::DuckStatusChanged(BuildStatus::Running))
+            .unwrap();
+        lifx.observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(3, requests.len());
+        assert_eq!(HttpMethod::Post, requests[0].method);
+        assert_eq!(
+            "https://example.com/v1/lights/group:Office/effects/breathe",
+            &requests[0].url
+        );
+        assert_eq!(
+            "{\"color\":\"#ff8000 brightness:0.5\",\"cycles\":1800,\"period\":2,\"persist\":true,\"power_on\":true}",
+            &requests[0].body.clone().unwrap()
+        );
+        assert_eq!(
+            "https://example.com/v1/lights/group:Office/effects/off",
+            &requests[1].url
+        );
+        assert_eq!(
+            "https://example.com/v1/lights/group:Office/state",
+            &requests[2].url
+        );
+    }
+
+    #[test]
+    fn should_turn_off_lights_when_shutting_down() {
+        // Given
+        let lifx = LifxObserver::<MockHttpClient>::new(&LifxConfiguration {
+            id: "lifx".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            server_url: Some("https://example.com/".to_string()),
+            token: "secret".to_string(),
+            selector: Some("group:Office".to_string()),
+            brightness: None,
+            states: None,
+        });
+
+        let client = lifx.get_client();
+        for endpoint in &["state", "effects/breathe", "effects/pulse", "effects/off"] {
+            client.add_response(
+                MockHttpResponseBuilder::new(
+                    HttpMethod::Put,
+                    format!("https://example.com/v1/lights/group:Office/{}", endpoint),
+                )
+                .returns_status(StatusCode::OK),
+            );
+        }
+
+        // When
+        lifx.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!("{\"power\":\"off\"}", &requests[0].body.clone().unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not update LIFX lights 'all' (401 Unauthorized)")]
+    fn should_return_error_if_server_return_non_successful_http_status_code() {
+        // Given
+        let lifx = LifxObserver::<MockHttpClient>::new(&LifxConfiguration {
+            id: "lifx".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            server_url: None,
+            token: "secret".to_string(),
+            selector: None,
+            brightness: None,
+            states: None,
+        });
+        let client = lifx.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Put,
+                "https://api.lifx.com/v1/lights/all/state",
+            )
+            .returns_status(StatusCode::UNAUTHORIZED),
+        );
+
+        // When, Then
+        lifx.observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+    }
+}
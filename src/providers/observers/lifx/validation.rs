@@ -0,0 +1,152 @@
+use url::Url;
+
+use crate::config::{LifxConfiguration, LifxStateConfiguration, Validate};
+use crate::utils::{colors::Rgb, DuckResult};
+
+impl Validate for LifxConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if self.id.is_empty() {
+            return Err(format_err!("LIFX ID is empty."));
+        }
+        if let Some(server_url) = &self.server_url {
+            if let Err(e) = Url::parse(server_url) {
+                return Err(format_err!("LIFX server URL is invalid: {}", e));
+            }
+        }
+        if self.token.is_empty() {
+            return Err(format_err!("LIFX token is empty."));
+        }
+        if let Some(states) = &self.states {
+            validate_state("success", &states.success)?;
+            validate_state("failed", &states.failed)?;
+            validate_state("running", &states.running)?;
+            validate_state("canceled", &states.canceled)?;
+            validate_state("unknown", &states.unknown)?;
+        }
+        Ok(())
+    }
+}
+
+fn validate_state(name: &str, state: &Option<LifxStateConfiguration>) -> DuckResult<()> {
+    if let Some(state) = state {
+        if let Some(color) = &state.color {
+            if Rgb::parse(color).is_none() {
+                return Err(format_err!(
+                    "LIFX {} color '{}' is not a valid hex color.",
+                    name,
+                    color
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers::DuckProviderCollection;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "The id '' is invalid.")]
+    fn should_return_error_if_lifx_id_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "lifx": {
+                            "id": "",
+                            "token": "secret"
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "LIFX server URL is invalid: relative URL without a base")]
+    fn should_return_error_if_lifx_server_url_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "lifx": {
+                            "id": "lifx",
+                            "serverUrl": "foo",
+                            "token": "secret"
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "LIFX token is empty.")]
+    fn should_return_error_if_lifx_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "lifx": {
+                            "id": "lifx",
+                            "token": ""
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "LIFX failed color 'red' is not a valid hex color.")]
+    fn should_return_error_if_lifx_state_color_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "lifx": {
+                            "id": "lifx",
+                            "token": "secret",
+                            "states": { "failed": { "color": "red" } }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+}
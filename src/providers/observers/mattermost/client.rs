@@ -1,18 +1,23 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde_json::Value;
+
+use crate::builds::{Build, BuildStatus};
 use crate::config::{MattermostConfiguration, MattermostCredentials};
-use crate::utils::http::{HttpClient, HttpRequestBuilder, HttpResponse};
+use crate::utils::http::{HttpClient, HttpMethod, HttpRequestBuilder, HttpResponse};
 use crate::utils::DuckResult;
 
 pub struct MattermostClient {
     channel: Option<String>,
     credentials: MattermostCredentials,
+    /// Posts about failed builds, keyed by build partition.
+    posts: Mutex<HashMap<u64, String>>,
 }
 
-impl MattermostCredentials {
-    fn get_url(&self) -> &str {
-        match self {
-            MattermostCredentials::Webhook { url } => url,
-        }
-    }
+#[derive(Deserialize, Debug)]
+struct MattermostPost {
+    id: String,
 }
 
 impl MattermostClient {
@@ -20,14 +25,73 @@ impl MattermostClient {
         MattermostClient {
             channel: config.channel.clone(),
             credentials: config.credentials.clone(),
+            posts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn send(&self, client: &impl HttpClient, build: &Build) -> DuckResult<()> {
+        match &self.credentials {
+            MattermostCredentials::Webhook { url } => self.send_to_webhook(client, url, build),
+            MattermostCredentials::Token { url, token } => {
+                self.send_to_api(client, url.trim_end_matches('/'), token, build)
+            }
+        }
+    }
+
+    /// Lets everyone know that someone is looking into the build.
+    pub fn send_acknowledgement(&self, client: &impl HttpClient, build: &Build) -> DuckResult<()> {
+        let text = match get_acknowledgement_text(build) {
+            Some(text) => text,
+            None => return Ok(()),
+        };
+
+        match &self.credentials {
+            MattermostCredentials::Webhook { url } => {
+                let mut payload = json!({ "text": text });
+                if let Some(channel) = &self.channel {
+                    payload["channel_id"] = json!(channel);
+                }
+                self.post_to_webhook(client, url, payload)
+            }
+            MattermostCredentials::Token { url, token } => {
+                // Reply in the thread about the failed build if there is one.
+                let mut post = json!({
+                    "channel_id": self.channel.clone().unwrap_or_default(),
+                    "message": text
+                });
+                if let Some(id) = self.posts.lock().unwrap().get(&build.partition) {
+                    post["root_id"] = json!(id);
+                }
+                self.call(
+                    client,
+                    token,
+                    HttpRequestBuilder::post(format!("{}/api/v4/posts", url.trim_end_matches('/'))),
+                    post,
+                )?;
+                Ok(())
+            }
         }
     }
 
-    pub fn send(&self, client: &impl HttpClient, message: &str) -> DuckResult<()> {
-        let mut builder = HttpRequestBuilder::post(self.credentials.get_url().to_string());
+    fn send_to_webhook(
+        &self,
+        client: &impl HttpClient,
+        url: &str,
+        build: &Build,
+    ) -> DuckResult<()> {
+        self.post_to_webhook(client, url, self.get_payload(build))
+    }
+
+    fn post_to_webhook(
+        &self,
+        client: &impl HttpClient,
+        url: &str,
+        payload: Value,
+    ) -> DuckResult<()> {
+        let mut builder = HttpRequestBuilder::post(url.to_string());
         builder.add_header("Content-Type", "application/json");
         builder.add_header("Accept", "application/json");
-        builder.set_body(self.get_payload(message).to_string());
+        builder.set_body(payload.to_string());
 
         let response = client.send(&builder)?;
         if !response.status().is_success() {
@@ -40,13 +104,186 @@ impl MattermostClient {
         Ok(())
     }
 
-    fn get_payload(&self, message: &str) -> serde_json::Value {
+    fn send_to_api(
+        &self,
+        client: &impl HttpClient,
+        url: &str,
+        token: &str,
+        build: &Build,
+    ) -> DuckResult<()> {
+        let channel = self.channel.clone().unwrap_or_default();
+        let mut posts = self.posts.lock().unwrap();
+        let existing = posts.get(&build.partition).cloned();
+
+        match (&build.status, existing) {
+            (BuildStatus::Failed, None) => {
+                // Create a new post about the failed build,
+                // and remember it so we can update it later.
+                let post = self.call(
+                    client,
+                    token,
+                    HttpRequestBuilder::post(format!("{}/api/v4/posts", url)),
+                    get_post(&channel, build),
+                )?;
+                posts.insert(build.partition, post.id);
+            }
+            (_, Some(id)) => {
+                // The build has been re-run, so update the original
+                // post and let everyone in the thread know about it.
+                let mut post = get_post(&channel, build);
+                post["id"] = json!(id);
+                self.call(
+                    client,
+                    token,
+                    HttpRequestBuilder::new(
+                        HttpMethod::Put,
+                        format!("{}/api/v4/posts/{}", url, id),
+                    ),
+                    post,
+                )?;
+
+                let mut reply = get_post(&channel, build);
+                reply["root_id"] = json!(id);
+                self.call(
+                    client,
+                    token,
+                    HttpRequestBuilder::post(format!("{}/api/v4/posts", url)),
+                    reply,
+                )?;
+
+                if build.status == BuildStatus::Success {
+                    posts.remove(&build.partition);
+                }
+            }
+            (_, None) => {
+                self.call(
+                    client,
+                    token,
+                    HttpRequestBuilder::post(format!("{}/api/v4/posts", url)),
+                    get_post(&channel, build),
+                )?;
+            }
+        };
+
+        Ok(())
+    }
+
+    fn call(
+        &self,
+        client: &impl HttpClient,
+        token: &str,
+        mut builder: HttpRequestBuilder,
+        payload: Value,
+    ) -> DuckResult<MattermostPost> {
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+        builder.add_header("Authorization", &*format!("Bearer {}", token));
+        builder.set_body(payload.to_string());
+
+        let mut response = client.send(&builder)?;
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Could not send Mattermost message. ({})",
+                response.status()
+            ));
+        }
+
+        response.deserialize_json()
+    }
+
+    fn get_payload(&self, build: &Build) -> Value {
         match self.channel {
-            Option::None => json!({ "text": message }),
+            Option::None => json!({ "attachments": [get_attachment(build)] }),
             Option::Some(_) => json!({
                 "channel_id": self.channel,
-                "text": message
+                "attachments": [get_attachment(build)]
             }),
         }
     }
 }
+
+fn get_acknowledgement_text(build: &Build) -> Option<String> {
+    let acknowledgement = build.acknowledged.as_ref()?;
+    let mut text = format!(
+        ":eyes: {} is looking into {}::{} ({})",
+        acknowledgement.by, build.project_name, build.definition_name, build.branch
+    );
+    if let Some(note) = &acknowledgement.note {
+        text.push_str(&format!("\n> {}", note));
+    }
+    Some(text)
+}
+
+fn get_post(channel: &str, build: &Build) -> Value {
+    json!({
+        "channel_id": channel,
+        "message": "",
+        "props": {
+            "attachments": [get_attachment(build)]
+        }
+    })
+}
+
+fn get_attachment(build: &Build) -> Value {
+    let text = format!(
+        "{:?} build status for {}::{} ({}) changed to *{:?}*",
+        build.provider, build.project_name, build.definition_name, build.branch, build.status
+    );
+    let mut fields = vec![
+        json!({ "short": true, "title": "Status", "value": format!("{:?}", build.status) }),
+        json!({ "short": true, "title": "Branch", "value": build.branch }),
+        json!({ "short": true, "title": "Build", "value": build.build_number }),
+        json!({ "short": true, "title": "Provider", "value": format!("{:?}", build.provider) }),
+    ];
+    if let Some(commit) = &build.commit {
+        fields.push(json!({ "short": true, "title": "Commit", "value": commit }));
+    }
+    if let Some(author) = &build.author {
+        fields.push(json!({ "short": true, "title": "Author", "value": author }));
+    }
+    if build.status == BuildStatus::Failed {
+        let failed = build.get_failed_stages();
+        if !failed.is_empty() {
+            let value: Vec<String> = failed
+                .iter()
+                .map(|stage| match &stage.url {
+                    Some(url) => format!("[{}]({})", stage.name, url),
+                    None => stage.name.clone(),
+                })
+                .collect();
+            fields.push(
+                json!({ "short": false, "title": "Failed stages", "value": value.join(", ") }),
+            );
+        }
+        if let Some(tests) = &build.tests {
+            let mut value = tests.summary();
+            if !tests.failures.is_empty() {
+                let failures: Vec<String> = tests
+                    .failures
+                    .iter()
+                    .map(|name| format!("`{}`", name))
+                    .collect();
+                value.push_str(&format!(" ({})", failures.join(", ")));
+            }
+            fields.push(json!({ "short": false, "title": "Tests", "value": value }));
+        }
+    }
+    json!({
+        "fallback": text,
+        "color": get_color(&build.status),
+        "title": format!("{}::{}", build.project_name, build.definition_name),
+        "title_link": build.url,
+        "text": text,
+        "fields": fields
+    })
+}
+
+fn get_color(status: &BuildStatus) -> &'static str {
+    match status {
+        BuildStatus::Success => "#36A64F",
+        BuildStatus::Failed => "#D00000",
+        BuildStatus::Running | BuildStatus::Queued => "#2196F3",
+        BuildStatus::Blocked => "#FFA000",
+        _ => "#9E9E9E",
+    }
+}
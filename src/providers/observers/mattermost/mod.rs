@@ -37,6 +37,8 @@ impl<T: HttpClient + Default> MattermostObserver<T> {
                         Some(HashSet::from_iter(collectors.iter().cloned()))
                     }
                 },
+                view: config.view.clone(),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
             },
         }
     }
@@ -53,24 +55,23 @@ impl<T: HttpClient + Default> Observer for MattermostObserver<T> {
     }
 
     fn observe(&self, observation: Observation) -> DuckResult<()> {
-        if let Observation::BuildStatusChanged(build) = observation {
-            if build.status != BuildStatus::Unknown {
+        if observation.is_muted() {
+            return Ok(());
+        }
+
+        match observation {
+            Observation::BuildStatusChanged(build) if build.status != BuildStatus::Unknown => {
                 info!(
                     "Sending Mattermost message since build status changed ({:?})...",
                     build.status
                 );
-                self.client.send(
-                    &self.http,
-                    &format!(
-                        "{:?} build status for {}::{} ({}) changed to *{:?}*",
-                        build.provider,
-                        build.project_name,
-                        build.definition_name,
-                        build.branch,
-                        build.status
-                    )[..],
-                )?;
+                self.client.send(&self.http, build)?;
             }
+            Observation::BuildAcknowledged(build) => {
+                info!("Sending Mattermost message since build was acknowledged...");
+                self.client.send_acknowledgement(&self.http, build)?;
+            }
+            _ => {}
         };
 
         Ok(())
@@ -80,9 +81,11 @@ impl<T: HttpClient + Default> Observer for MattermostObserver<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::builds::{BuildBuilder, BuildStatus};
+    use crate::builds::{Acknowledgement, BuildBuilder, BuildStage, BuildStatus, BuildTests};
     use crate::config::MattermostCredentials;
-    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use crate::utils::http::{
+        HttpMethod, HttpRequestBuilder, MockHttpClient, MockHttpResponseBuilder,
+    };
     use reqwest::StatusCode;
     use test_case::test_case;
 
@@ -93,6 +96,8 @@ mod tests {
             id: "hue".to_string(),
             enabled: Some(true),
             collectors: None,
+            view: None,
+            pull_requests: None,
             channel: None,
             credentials: MattermostCredentials::Webhook {
                 url: "https://example.com/webhook".to_string(),
@@ -119,14 +124,18 @@ mod tests {
         assert_eq!("https://example.com/webhook", &requests[0].url);
     }
 
-    #[test_case(BuildStatus::Success, "{\"text\":\"TeamCity build status for project_name::definition_name (branch) changed to *Success*\"}" ; "Success")]
-    #[test_case(BuildStatus::Failed, "{\"text\":\"TeamCity build status for project_name::definition_name (branch) changed to *Failed*\"}" ; "Failed")]
-    fn should_send_correct_payload(status: BuildStatus, expected: &str) {
+    #[test_case(BuildStatus::Success, "#36A64F" ; "Success")]
+    #[test_case(BuildStatus::Failed, "#D00000" ; "Failed")]
+    #[test_case(BuildStatus::Running, "#2196F3" ; "Running")]
+    #[test_case(BuildStatus::Canceled, "#9E9E9E" ; "Canceled")]
+    fn should_send_attachment_with_status_color(status: BuildStatus, expected: &str) {
         // Given
         let mattermost = MattermostObserver::<MockHttpClient>::new(&MattermostConfiguration {
             id: "hue".to_string(),
             enabled: Some(true),
             collectors: None,
+            view: None,
+            pull_requests: None,
             channel: None,
             credentials: MattermostCredentials::Webhook {
                 url: "https://example.com/webhook".to_string(),
@@ -142,15 +151,157 @@ mod tests {
         // When
         mattermost
             .observe(Observation::BuildStatusChanged(
-                &BuildBuilder::dummy().status(status).unwrap(),
+                &BuildBuilder::dummy().status(status.clone()).unwrap(),
             ))
             .unwrap();
 
         // Then
         let requests = client.get_sent_requests();
         assert_eq!(1, requests.len());
-        assert!(&requests[0].body.is_some());
-        assert_eq!(expected, &requests[0].body.clone().unwrap());
+        let attachment = &get_body(&requests[0])["attachments"][0];
+        assert_eq!(expected, attachment["color"]);
+        assert_eq!("project_name::definition_name", attachment["title"]);
+        assert_eq!("https://dummy", attachment["title_link"]);
+        assert_eq!(
+            format!(
+                "TeamCity build status for project_name::definition_name (branch) changed to *{:?}*",
+                status
+            ),
+            attachment["text"]
+        );
+        assert_eq!("Branch", attachment["fields"][1]["title"]);
+        assert_eq!("branch", attachment["fields"][1]["value"]);
+    }
+
+    #[test]
+    fn should_include_commit_and_author_fields_if_known() {
+        // Given
+        let mattermost = MattermostObserver::<MockHttpClient>::new(&MattermostConfiguration {
+            id: "mattermost".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: None,
+            credentials: MattermostCredentials::Webhook {
+                url: "https://example.com/webhook".to_string(),
+            },
+        });
+
+        let client = mattermost.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Put, "https://example.com/webhook")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        mattermost
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .commit(Some("abc123".to_string()))
+                    .author(Some("Patrik".to_string()))
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let attachment = &get_body(&requests[0])["attachments"][0];
+        assert_eq!("Commit", attachment["fields"][4]["title"]);
+        assert_eq!("abc123", attachment["fields"][4]["value"]);
+        assert_eq!("Author", attachment["fields"][5]["title"]);
+        assert_eq!("Patrik", attachment["fields"][5]["value"]);
+    }
+
+    #[test]
+    fn should_include_failed_stages_for_failed_builds() {
+        // Given
+        let mattermost = MattermostObserver::<MockHttpClient>::new(&MattermostConfiguration {
+            id: "mattermost".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: None,
+            credentials: MattermostCredentials::Webhook {
+                url: "https://example.com/webhook".to_string(),
+            },
+        });
+
+        let client = mattermost.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Put, "https://example.com/webhook")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        mattermost
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .status(BuildStatus::Failed)
+                    .stages(Some(vec![
+                        BuildStage::dummy("build", BuildStatus::Failed),
+                        BuildStage::dummy("test", BuildStatus::Failed),
+                    ]))
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let attachment = &get_body(&requests[0])["attachments"][0];
+        assert_eq!("Failed stages", attachment["fields"][4]["title"]);
+        assert_eq!(
+            "[build](https://dummy/build), [test](https://dummy/test)",
+            attachment["fields"][4]["value"]
+        );
+    }
+
+    #[test]
+    fn should_include_test_summary_for_failed_builds() {
+        // Given
+        let mattermost = MattermostObserver::<MockHttpClient>::new(&MattermostConfiguration {
+            id: "mattermost".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: None,
+            credentials: MattermostCredentials::Webhook {
+                url: "https://example.com/webhook".to_string(),
+            },
+        });
+
+        let client = mattermost.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Put, "https://example.com/webhook")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        mattermost
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .status(BuildStatus::Failed)
+                    .tests(Some(BuildTests {
+                        total: Some(145),
+                        passed: Some(142),
+                        failed: 3,
+                        ignored: None,
+                        failures: vec!["foo".to_string(), "bar".to_string()],
+                    }))
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let attachment = &get_body(&requests[0])["attachments"][0];
+        assert_eq!("Tests", attachment["fields"][4]["title"]);
+        assert_eq!(
+            "142 passed, 3 failed (`foo`, `bar`)",
+            attachment["fields"][4]["value"]
+        );
     }
 
     #[test]
@@ -160,6 +311,8 @@ mod tests {
             id: "hue".to_string(),
             enabled: Some(true),
             collectors: None,
+            view: None,
+            pull_requests: None,
             channel: Some("foo".to_string()),
             credentials: MattermostCredentials::Webhook {
                 url: "https://example.com/webhook".to_string(),
@@ -182,10 +335,192 @@ mod tests {
         // Then
         let requests = client.get_sent_requests();
         assert_eq!(1, requests.len());
-        assert!(&requests[0].body.is_some());
+        assert_eq!("foo", get_body(&requests[0])["channel_id"]);
+    }
+
+    fn get_body(request: &HttpRequestBuilder) -> serde_json::Value {
+        serde_json::from_str(&request.body.clone().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn should_create_post_using_rest_api() {
+        // Given
+        let mattermost = MattermostObserver::<MockHttpClient>::new(&MattermostConfiguration {
+            id: "mattermost".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: Some("channel_id".to_string()),
+            credentials: MattermostCredentials::Token {
+                url: "https://mattermost.example.com/".to_string(),
+                token: "secret".to_string(),
+            },
+        });
+
+        let client = mattermost.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://mattermost.example.com/api/v4/posts",
+            )
+            .returns_status(StatusCode::CREATED)
+            .returns_body("{ \"id\": \"post_id\" }"),
+        );
+
+        // When
+        mattermost
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(HttpMethod::Post, requests[0].method);
+        assert_eq!(
+            "https://mattermost.example.com/api/v4/posts",
+            &requests[0].url
+        );
+        assert_eq!(
+            "Bearer secret",
+            requests[0].headers.get("Authorization").unwrap()
+        );
+        let body = get_body(&requests[0]);
+        assert_eq!("channel_id", body["channel_id"]);
+        assert_eq!("#D00000", body["props"]["attachments"][0]["color"]);
+    }
+
+    #[test]
+    fn should_update_post_and_reply_in_thread_when_build_is_fixed() {
+        // Given
+        let mattermost = MattermostObserver::<MockHttpClient>::new(&MattermostConfiguration {
+            id: "mattermost".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: Some("channel_id".to_string()),
+            credentials: MattermostCredentials::Token {
+                url: "https://mattermost.example.com/".to_string(),
+                token: "secret".to_string(),
+            },
+        });
+
+        let client = mattermost.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://mattermost.example.com/api/v4/posts",
+            )
+            .returns_status(StatusCode::CREATED)
+            .returns_body("{ \"id\": \"post_id\" }"),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Put,
+                "https://mattermost.example.com/api/v4/posts/post_id",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body("{ \"id\": \"post_id\" }"),
+        );
+        mattermost
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+
+        // When
+        mattermost
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Success).unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(3, requests.len());
+        assert_eq!(HttpMethod::Put, requests[1].method);
+        assert_eq!(
+            "https://mattermost.example.com/api/v4/posts/post_id",
+            &requests[1].url
+        );
+        let update = get_body(&requests[1]);
+        assert_eq!("post_id", update["id"]);
+        assert_eq!("#36A64F", update["props"]["attachments"][0]["color"]);
+        let reply = get_body(&requests[2]);
+        assert_eq!("post_id", reply["root_id"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not send Mattermost message. (403 Forbidden)")]
+    fn should_return_error_if_rest_api_call_was_not_successful() {
+        // Given
+        let mattermost = MattermostObserver::<MockHttpClient>::new(&MattermostConfiguration {
+            id: "mattermost".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: Some("channel_id".to_string()),
+            credentials: MattermostCredentials::Token {
+                url: "https://mattermost.example.com/".to_string(),
+                token: "secret".to_string(),
+            },
+        });
+
+        let client = mattermost.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://mattermost.example.com/api/v4/posts",
+            )
+            .returns_status(StatusCode::FORBIDDEN),
+        );
+
+        // When, Then
+        mattermost
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+    }
+
+    #[test]
+    fn should_post_message_when_build_is_acknowledged() {
+        // Given
+        let mattermost = MattermostObserver::<MockHttpClient>::new(&MattermostConfiguration {
+            id: "mattermost".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: None,
+            credentials: MattermostCredentials::Webhook {
+                url: "https://example.com/webhook".to_string(),
+            },
+        });
+
+        let client = mattermost.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/webhook")
+                .returns_status(StatusCode::OK),
+        );
+        let mut build = BuildBuilder::dummy().status(BuildStatus::Failed).unwrap();
+        build.acknowledged = Some(Acknowledgement::dummy("Patrik"));
+
+        // When
+        mattermost
+            .observe(Observation::BuildAcknowledged(&build))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
         assert_eq!(
-            "{\"channel_id\":\"foo\",\"text\":\"TeamCity build status for project_name::definition_name (branch) changed to *Success*\"}",
-            &requests[0].body.clone().unwrap()
+            ":eyes: Patrik is looking into project_name::definition_name (branch)\n> On it",
+            get_body(&requests[0])["text"]
         );
     }
 }
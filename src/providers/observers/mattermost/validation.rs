@@ -14,6 +14,13 @@ impl Validate for MattermostConfiguration {
                 return Err(format_err!("Mattermost channel is empty."));
             }
         }
+        if let MattermostCredentials::Token { .. } = self.credentials {
+            if self.channel.is_none() {
+                return Err(format_err!(
+                    "Mattermost channel must be set when using a token."
+                ));
+            }
+        }
         Ok(())
     }
 }
@@ -26,6 +33,14 @@ impl Validate for MattermostCredentials {
                     return Err(format_err!("Mattermost webhook URL is invalid: {}", e));
                 }
             }
+            MattermostCredentials::Token { url, token } => {
+                if let Err(e) = Url::parse(url) {
+                    return Err(format_err!("Mattermost server URL is invalid: {}", e));
+                }
+                if token.is_empty() {
+                    return Err(format_err!("Mattermost token is empty."));
+                }
+            }
         };
         Ok(())
     }
@@ -124,4 +139,96 @@ mod tests {
         let collection = DuckProviderCollection::new();
         collection.get_observers(&config).unwrap();
     }
+
+    #[test]
+    #[should_panic(expected = "Mattermost server URL is invalid: relative URL without a base")]
+    fn should_return_error_if_mattermost_server_url_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "mattermost": {
+                            "id": "foo",
+                            "channel": "4xp9fdt2xpgwm8t3h8hcumbvnr",
+                            "credentials": {
+                                "token": {
+                                    "url": "",
+                                    "token": "secret"
+                                }
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Mattermost token is empty.")]
+    fn should_return_error_if_mattermost_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "mattermost": {
+                            "id": "foo",
+                            "channel": "4xp9fdt2xpgwm8t3h8hcumbvnr",
+                            "credentials": {
+                                "token": {
+                                    "url": "https://mattermost.example.com",
+                                    "token": ""
+                                }
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Mattermost channel must be set when using a token.")]
+    fn should_return_error_if_mattermost_channel_is_missing_when_using_token() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "mattermost": {
+                            "id": "foo",
+                            "credentials": {
+                                "token": {
+                                    "url": "https://mattermost.example.com",
+                                    "token": "secret"
+                                }
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
 }
@@ -1,19 +1,35 @@
 use std::collections::HashSet;
 
 use crate::builds::{Build, BuildStatus};
-use crate::config::{Configuration, ObserverConfiguration, Validate};
+use crate::config::{Configuration, ObserverConfiguration, PullRequestMode, Validate};
 use crate::utils::http::ReqwestClient;
 use crate::utils::DuckResult;
 
+use self::command::CommandObserver;
+use self::discord::DiscordObserver;
+use self::email::{EmailObserver, SmtpEmailTransport};
 use self::hue::HueObserver;
+use self::incident::IncidentObserver;
+use self::lifx::LifxObserver;
 use self::mattermost::MattermostObserver;
+use self::mqtt::{MqttObserver, RumqttPublisher};
 use self::slack::SlackObserver;
+use self::webhook::WebhookObserver;
+use self::wled::WledObserver;
 
 use super::DuckProvider;
 
+mod command;
+mod discord;
+mod email;
 mod hue;
+mod incident;
+mod lifx;
 mod mattermost;
+mod mqtt;
 mod slack;
+mod webhook;
+mod wled;
 
 pub trait Observer: Send {
     fn info(&self) -> &ObserverInfo;
@@ -24,12 +40,17 @@ pub struct ObserverInfo {
     pub id: String,
     pub enabled: bool,
     pub collectors: Option<HashSet<String>>,
+    /// The view the observer is interested in, if any.
+    pub view: Option<String>,
+    pub pull_requests: PullRequestMode,
 }
 
 pub enum Observation<'a> {
     DuckStatusChanged(BuildStatus),
     BuildUpdated(&'a Build),
     BuildStatusChanged(&'a Build),
+    /// Someone is looking into the build.
+    BuildAcknowledged(&'a Build),
     ShuttingDown,
 }
 
@@ -39,6 +60,27 @@ pub enum ObservationOrigin<'a> {
 }
 
 impl<'a> Observation<'a> {
+    /// Gets the build for an observation, if any.
+    pub fn get_build(&self) -> Option<&Build> {
+        match self {
+            Observation::BuildUpdated(build)
+            | Observation::BuildStatusChanged(build)
+            | Observation::BuildAcknowledged(build) => Some(build),
+            Observation::DuckStatusChanged(_) | Observation::ShuttingDown => None,
+        }
+    }
+
+    /// Gets whether or not the observation is about a build that
+    /// someone has muted, which observers shouldn't notify people about.
+    pub fn is_muted(&self) -> bool {
+        match self {
+            Observation::BuildUpdated(build) | Observation::BuildStatusChanged(build) => {
+                build.muted.is_some()
+            }
+            _ => false,
+        }
+    }
+
     /// Gets the collector for an observation.
     pub fn get_origin(&self) -> ObservationOrigin {
         match self {
@@ -47,6 +89,7 @@ impl<'a> Observation<'a> {
             Observation::BuildStatusChanged(build) => {
                 ObservationOrigin::Collector(&build.collector)
             }
+            Observation::BuildAcknowledged(build) => ObservationOrigin::Collector(&build.collector),
             Observation::ShuttingDown => ObservationOrigin::System,
         }
     }
@@ -68,6 +111,38 @@ impl<'a> DuckProvider<'a> for HueProvider {
     }
 }
 
+pub struct LifxProvider {}
+impl<'a> DuckProvider<'a> for LifxProvider {
+    fn get_observers(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Observer>>> {
+        let mut result = Vec::<Box<dyn Observer>>::new();
+        if let Some(observers) = &config.observers {
+            for item in observers.iter() {
+                if let ObserverConfiguration::Lifx(c) = item {
+                    c.validate()?;
+                    result.push(Box::new(LifxObserver::<ReqwestClient>::new(&c)));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+pub struct WledProvider {}
+impl<'a> DuckProvider<'a> for WledProvider {
+    fn get_observers(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Observer>>> {
+        let mut result = Vec::<Box<dyn Observer>>::new();
+        if let Some(observers) = &config.observers {
+            for item in observers.iter() {
+                if let ObserverConfiguration::Wled(c) = item {
+                    c.validate()?;
+                    result.push(Box::new(WledObserver::<ReqwestClient>::new(&c)));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
 pub struct MattermostProvider {}
 impl<'a> DuckProvider<'a> for MattermostProvider {
     fn get_observers(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Observer>>> {
@@ -99,3 +174,102 @@ impl<'a> DuckProvider<'a> for SlackProvider {
         Ok(result)
     }
 }
+
+pub struct DiscordProvider {}
+impl<'a> DuckProvider<'a> for DiscordProvider {
+    fn get_observers(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Observer>>> {
+        let mut result = Vec::<Box<dyn Observer>>::new();
+        if let Some(observers) = &config.observers {
+            for item in observers.iter() {
+                if let ObserverConfiguration::Discord(c) = item {
+                    c.validate()?;
+                    result.push(Box::new(DiscordObserver::<ReqwestClient>::new(&c)));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+pub struct EmailProvider {}
+impl<'a> DuckProvider<'a> for EmailProvider {
+    fn get_observers(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Observer>>> {
+        let mut result = Vec::<Box<dyn Observer>>::new();
+        if let Some(observers) = &config.observers {
+            for item in observers.iter() {
+                if let ObserverConfiguration::Email(c) = item {
+                    c.validate()?;
+                    result.push(Box::new(EmailObserver::new(
+                        &c,
+                        SmtpEmailTransport::new(&c.server),
+                    )));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+pub struct WebhookProvider {}
+impl<'a> DuckProvider<'a> for WebhookProvider {
+    fn get_observers(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Observer>>> {
+        let mut result = Vec::<Box<dyn Observer>>::new();
+        if let Some(observers) = &config.observers {
+            for item in observers.iter() {
+                if let ObserverConfiguration::Webhook(c) = item {
+                    c.validate()?;
+                    result.push(Box::new(WebhookObserver::<ReqwestClient>::new(&c)));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+pub struct MqttProvider {}
+impl<'a> DuckProvider<'a> for MqttProvider {
+    fn get_observers(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Observer>>> {
+        let mut result = Vec::<Box<dyn Observer>>::new();
+        if let Some(observers) = &config.observers {
+            for item in observers.iter() {
+                if let ObserverConfiguration::Mqtt(c) = item {
+                    c.validate()?;
+                    result.push(Box::new(MqttObserver::new(&c, RumqttPublisher::new(&c)?)));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+pub struct CommandObserverProvider {}
+impl<'a> DuckProvider<'a> for CommandObserverProvider {
+    fn get_observers(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Observer>>> {
+        let mut result = Vec::<Box<dyn Observer>>::new();
+        if let Some(observers) = &config.observers {
+            for item in observers.iter() {
+                if let ObserverConfiguration::Command(c) = item {
+                    c.validate()?;
+                    result.push(Box::new(CommandObserver::new(&c)));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+pub struct IncidentProvider {}
+impl<'a> DuckProvider<'a> for IncidentProvider {
+    fn get_observers(&self, config: &Configuration) -> DuckResult<Vec<Box<dyn Observer>>> {
+        let mut result = Vec::<Box<dyn Observer>>::new();
+        if let Some(observers) = &config.observers {
+            for item in observers.iter() {
+                if let ObserverConfiguration::Incident(c) = item {
+                    c.validate()?;
+                    result.push(Box::new(IncidentObserver::<ReqwestClient>::new(&c)));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use log::info;
+
+use crate::builds::Build;
+use crate::config::MqttConfiguration;
+use crate::providers::observers::{Observation, Observer, ObserverInfo};
+use crate::utils::DuckResult;
+
+use self::publisher::{MqttMessage, MqttPublisher};
+
+pub use self::publisher::RumqttPublisher;
+
+mod publisher;
+mod validation;
+
+static DEFAULT_STATUS_TOPIC: &str = "duck/status";
+static DEFAULT_BUILDS_TOPIC: &str = "duck/builds/{collector}/{project}/{definition}/{branch}";
+
+pub struct MqttObserver<T: MqttPublisher> {
+    publisher: T,
+    status_topic: String,
+    builds_topic: String,
+    info: ObserverInfo,
+}
+
+impl<T: MqttPublisher> MqttObserver<T> {
+    pub fn new(config: &MqttConfiguration, publisher: T) -> Self {
+        let topics = config.topics.clone().unwrap_or_default();
+        MqttObserver {
+            publisher,
+            status_topic: topics
+                .status
+                .unwrap_or_else(|| DEFAULT_STATUS_TOPIC.to_string()),
+            builds_topic: topics
+                .builds
+                .unwrap_or_else(|| DEFAULT_BUILDS_TOPIC.to_string()),
+            info: ObserverInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    None => true,
+                    Some(e) => e,
+                },
+                collectors: match &config.collectors {
+                    Option::None => Option::None,
+                    Option::Some(collectors) => {
+                        Some(HashSet::from_iter(collectors.iter().cloned()))
+                    }
+                },
+                view: config.view.clone(),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get_publisher(&self) -> &T {
+        &self.publisher
+    }
+
+    fn get_build_topic(&self, build: &Build) -> String {
+        self.builds_topic
+            .replace("{collector}", &escape_topic_level(&build.collector))
+            .replace("{project}", &escape_topic_level(&build.project_name))
+            .replace("{definition}", &escape_topic_level(&build.definition_name))
+            .replace("{branch}", &escape_topic_level(&build.branch))
+    }
+}
+
+impl<T: MqttPublisher> Observer for MqttObserver<T> {
+    fn info(&self) -> &ObserverInfo {
+        &self.info
+    }
+
+    fn observe(&self, observation: Observation) -> DuckResult<()> {
+        match observation {
+            Observation::DuckStatusChanged(status) => {
+                info!("Publishing Duck status ({:?}) to MQTT...", status);
+                self.publisher.publish(&[MqttMessage {
+                    topic: self.status_topic.clone(),
+                    payload: format!("{:?}", status),
+                }])?;
+            }
+            Observation::BuildStatusChanged(build) => {
+                info!(
+                    "Publishing build status ({:?}) to MQTT since build status changed...",
+                    build.status
+                );
+                self.publisher.publish(&[MqttMessage {
+                    topic: self.get_build_topic(build),
+                    payload: json!({
+                        "status": format!("{:?}", build.status),
+                        "provider": format!("{:?}", build.provider),
+                        "collector": build.collector,
+                        "project": build.project_name,
+                        "definition": build.definition_name,
+                        "branch": build.branch,
+                        "buildNumber": build.build_number,
+                        "url": build.url,
+                    })
+                    .to_string(),
+                }])?;
+            }
+            _ => {}
+        };
+
+        Ok(())
+    }
+}
+
+/// Replaces characters that are not allowed in a topic level
+/// (the wildcards and the level separator) with underscores.
+fn escape_topic_level(value: &str) -> String {
+    value.replace(&['/', '+', '#'][..], "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::{BuildBuilder, BuildStatus};
+    use crate::config::{MqttBrokerConfiguration, MqttTopicsConfiguration};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    pub struct MockMqttPublisher {
+        published: Mutex<Vec<MqttMessage>>,
+    }
+
+    impl MockMqttPublisher {
+        fn get_published(&self) -> Vec<MqttMessage> {
+            self.published.lock().unwrap().clone()
+        }
+    }
+
+    impl MqttPublisher for MockMqttPublisher {
+        fn publish(&self, messages: &[MqttMessage]) -> DuckResult<()> {
+            self.published
+                .lock()
+                .unwrap()
+                .extend(messages.iter().cloned());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn should_publish_duck_status_to_default_topic() {
+        // Given
+        let mqtt = MqttObserver::new(
+            &MqttConfiguration {
+                id: "mqtt".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                broker: MqttBrokerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    tls: None,
+                    client_id: None,
+                    credentials: None,
+                },
+                topics: None,
+                qos: None,
+                retain: None,
+            },
+            MockMqttPublisher::default(),
+        );
+
+        // When
+        mqtt.observe(Observation::DuckStatusChanged(BuildStatus::Failed))
+            .unwrap();
+
+        // Then
+        let published = mqtt.get_publisher().get_published();
+        assert_eq!(1, published.len());
+        assert_eq!("duck/status", published[0].topic);
+        assert_eq!("Failed", published[0].payload);
+    }
+
+    #[test]
+    fn should_publish_build_status_to_default_topic() {
+        // Given
+        let mqtt = MqttObserver::new(
+            &MqttConfiguration {
+                id: "mqtt".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                broker: MqttBrokerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    tls: None,
+                    client_id: None,
+                    credentials: None,
+                },
+                topics: None,
+                qos: None,
+                retain: None,
+            },
+            MockMqttPublisher::default(),
+        );
+
+        // When
+        mqtt.observe(Observation::BuildStatusChanged(
+            &BuildBuilder::dummy()
+                .branch("feature/foo")
+                .status(BuildStatus::Success)
+                .unwrap(),
+        ))
+        .unwrap();
+
+        // Then
+        let published = mqtt.get_publisher().get_published();
+        assert_eq!(1, published.len());
+        assert_eq!(
+            "duck/builds/collector/project_name/definition_name/feature_foo",
+            published[0].topic
+        );
+        let payload: serde_json::Value = serde_json::from_str(&published[0].payload).unwrap();
+        assert_eq!("Success", payload["status"]);
+        assert_eq!("TeamCity", payload["provider"]);
+        assert_eq!("feature/foo", payload["branch"]);
+    }
+
+    #[test]
+    fn should_publish_to_configured_topics() {
+        // Given
+        let mqtt = MqttObserver::new(
+            &MqttConfiguration {
+                id: "mqtt".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                broker: MqttBrokerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    tls: None,
+                    client_id: None,
+                    credentials: None,
+                },
+                topics: Some(MqttTopicsConfiguration {
+                    status: Some("office/lamp/status".to_string()),
+                    builds: Some("office/{project}/{branch}".to_string()),
+                }),
+                qos: None,
+                retain: None,
+            },
+            MockMqttPublisher::default(),
+        );
+
+        // When
+        mqtt.observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+        mqtt.observe(Observation::BuildStatusChanged(
+            &BuildBuilder::dummy().unwrap(),
+        ))
+        .unwrap();
+
+        // Then
+        let published = mqtt.get_publisher().get_published();
+        assert_eq!("office/lamp/status", published[0].topic);
+        assert_eq!("office/project_name/branch", published[1].topic);
+    }
+
+    #[test]
+    fn should_not_publish_other_observations() {
+        // Given
+        let mqtt = MqttObserver::new(
+            &MqttConfiguration {
+                id: "mqtt".to_string(),
+                enabled: Some(true),
+                collectors: None,
+                view: None,
+                pull_requests: None,
+                broker: MqttBrokerConfiguration {
+                    host: "localhost".to_string(),
+                    port: None,
+                    tls: None,
+                    client_id: None,
+                    credentials: None,
+                },
+                topics: None,
+                qos: None,
+                retain: None,
+            },
+            MockMqttPublisher::default(),
+        );
+
+        // When
+        mqtt.observe(Observation::BuildUpdated(&BuildBuilder::dummy().unwrap()))
+            .unwrap();
+        mqtt.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        assert_eq!(0, mqtt.get_publisher().get_published().len());
+    }
+}
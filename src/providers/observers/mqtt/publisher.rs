@@ -0,0 +1,331 @@
+use std::time::{Duration, Instant};
+
+use log::trace;
+use rumqttc::{Client, Event, MqttOptions, Outgoing, Packet, QoS, RecvTimeoutError, Transport};
+
+use crate::config::MqttConfiguration;
+use crate::utils::DuckResult;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MqttMessage {
+    pub topic: String,
+    pub payload: String,
+}
+
+pub trait MqttPublisher: Send {
+    fn publish(&self, messages: &[MqttMessage]) -> DuckResult<()>;
+}
+
+/// Publishes messages using a short lived connection to the broker.
+/// Observations are rare enough that keeping a connection open isn't worth it.
+pub struct RumqttPublisher {
+    options: MqttOptions,
+    qos: QoS,
+    retain: bool,
+    timeout: Duration,
+}
+
+impl RumqttPublisher {
+    pub fn new(config: &MqttConfiguration) -> DuckResult<Self> {
+        let broker = &config.broker;
+        let tls = broker.tls.unwrap_or(false);
+        let port = match broker.port {
+            Some(port) => port,
+            None if tls => 8883,
+            None => 1883,
+        };
+        let client_id = match &broker.client_id {
+            Some(client_id) => client_id.clone(),
+            None => format!("duck-{}", config.id),
+        };
+
+        let mut options = MqttOptions::new(client_id, broker.host.clone(), port);
+        options.set_keep_alive(Duration::from_secs(30));
+        if tls {
+            options.set_transport(Transport::tls_with_default_config());
+        }
+        if let Some(credentials) = &broker.credentials {
+            options.set_credentials(credentials.username.clone(), credentials.password.clone());
+        }
+
+        Ok(Self {
+            options,
+            qos: rumqttc::qos(config.qos.unwrap_or(1))?,
+            retain: config.retain.unwrap_or(true),
+            timeout: Duration::from_secs(30),
+        })
+    }
+}
+
+impl MqttPublisher for RumqttPublisher {
+    fn publish(&self, messages: &[MqttMessage]) -> DuckResult<()> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+
+        let (mut client, mut connection) = Client::new(self.options.clone(), messages.len());
+        for message in messages.iter() {
+            trace!("Publishing MQTT message to {}...", message.topic);
+            client.publish(
+                &message.topic[..],
+                self.qos,
+                self.retain,
+                message.payload.as_bytes(),
+            )?;
+        }
+
+        // Drive the connection until all messages have been
+        // acknowledged by the broker, and then disconnect.
+        // Give up if the broker doesn't get there in time.
+        let deadline = Instant::now() + self.timeout;
+        let mut pending = messages.len();
+        let mut disconnecting = false;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let event = match connection.recv_timeout(remaining) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(format_err!(
+                        "Timed out while publishing MQTT messages to {}.",
+                        self.options.broker_address().0
+                    ))
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            match event? {
+                Event::Outgoing(Outgoing::Publish(_)) if self.qos == QoS::AtMostOnce => {
+                    pending -= 1
+                }
+                Event::Incoming(Packet::PubAck(_)) if self.qos == QoS::AtLeastOnce => pending -= 1,
+                Event::Incoming(Packet::PubComp(_)) if self.qos == QoS::ExactlyOnce => pending -= 1,
+                Event::Outgoing(Outgoing::Disconnect) => break,
+                _ => {}
+            }
+            if pending == 0 && !disconnecting {
+                client.try_disconnect()?;
+                disconnecting = true;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{MqttBrokerConfiguration, MqttConfiguration, MqttCredentials};
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    #[derive(Debug)]
+    struct ReceivedPublish {
+        topic: String,
+        payload: String,
+        qos: u8,
+        retain: bool,
+    }
+
+    type Broker = thread::JoinHandle<(Vec<u8>, Vec<ReceivedPublish>)>;
+
+    fn read_packet(stream: &mut TcpStream) -> Option<(u8, Vec<u8>)> {
+        let mut header = [0u8; 1];
+        if stream.read_exact(&mut header).is_err() {
+            return None;
+        }
+        // Decode the variable length "remaining length" field.
+        let mut length = 0usize;
+        let mut multiplier = 1usize;
+        loop {
+            let mut byte = [0u8; 1];
+            stream.read_exact(&mut byte).ok()?;
+            length += (byte[0] & 0x7F) as usize * multiplier;
+            multiplier *= 128;
+            if byte[0] & 0x80 == 0 {
+                break;
+            }
+        }
+        let mut body = vec![0u8; length];
+        stream.read_exact(&mut body).ok()?;
+        Some((header[0], body))
+    }
+
+    /// A minimal MQTT 3.1.1 broker that accepts a single connection
+    /// and returns the CONNECT packet and all messages it received.
+    fn start_broker() -> (u16, Broker) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut connect = Vec::new();
+            let mut received = Vec::new();
+            while let Some((header, body)) = read_packet(&mut stream) {
+                match header >> 4 {
+                    1 => {
+                        connect = body;
+                        stream.write_all(&[0x20, 0x02, 0x00, 0x00]).unwrap();
+                    }
+                    3 => {
+                        let qos = (header >> 1) & 0x03;
+                        let topic_length = ((body[0] as usize) << 8) | body[1] as usize;
+                        let topic = String::from_utf8(body[2..2 + topic_length].to_vec()).unwrap();
+                        let mut offset = 2 + topic_length;
+                        if qos > 0 {
+                            let id = [body[offset], body[offset + 1]];
+                            offset += 2;
+                            stream.write_all(&[0x40, 0x02, id[0], id[1]]).unwrap();
+                        }
+                        received.push(ReceivedPublish {
+                            topic,
+                            payload: String::from_utf8(body[offset..].to_vec()).unwrap(),
+                            qos,
+                            retain: header & 0x01 == 1,
+                        });
+                    }
+                    12 => stream.write_all(&[0xD0, 0x00]).unwrap(),
+                    14 => break,
+                    _ => {}
+                }
+            }
+            (connect, received)
+        });
+        (port, handle)
+    }
+
+    #[test]
+    fn should_publish_messages_to_broker() {
+        // Given
+        let (port, broker) = start_broker();
+        let publisher = RumqttPublisher::new(&MqttConfiguration {
+            id: "mqtt".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            broker: MqttBrokerConfiguration {
+                host: "127.0.0.1".to_string(),
+                port: Some(port),
+                tls: Some(false),
+                client_id: None,
+                credentials: None,
+            },
+            topics: None,
+            qos: Some(1),
+            retain: Some(true),
+        })
+        .unwrap();
+
+        // When
+        publisher
+            .publish(&[
+                MqttMessage {
+                    topic: "duck/status".to_string(),
+                    payload: "Failed".to_string(),
+                },
+                MqttMessage {
+                    topic: "duck/builds/teamcity/Duck/Build/master".to_string(),
+                    payload: "{}".to_string(),
+                },
+            ])
+            .unwrap();
+
+        // Then
+        let (_, received) = broker.join().unwrap();
+        assert_eq!(2, received.len());
+        assert_eq!("duck/status", received[0].topic);
+        assert_eq!("Failed", received[0].payload);
+        assert_eq!(1, received[0].qos);
+        assert!(received[0].retain);
+        assert_eq!("duck/builds/teamcity/Duck/Build/master", received[1].topic);
+    }
+
+    #[test]
+    fn should_send_client_id_and_credentials() {
+        // Given
+        let (port, broker) = start_broker();
+        let publisher = RumqttPublisher::new(&MqttConfiguration {
+            id: "mqtt".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            broker: MqttBrokerConfiguration {
+                host: "127.0.0.1".to_string(),
+                port: Some(port),
+                tls: Some(false),
+                client_id: None,
+                credentials: Some(MqttCredentials {
+                    username: "patrik".to_string(),
+                    password: "hunter2".to_string(),
+                }),
+            },
+            topics: None,
+            qos: Some(1),
+            retain: Some(true),
+        })
+        .unwrap();
+
+        // When
+        publisher
+            .publish(&[MqttMessage {
+                topic: "duck/status".to_string(),
+                payload: "Success".to_string(),
+            }])
+            .unwrap();
+
+        // Then
+        let (connect, _) = broker.join().unwrap();
+        let connect = String::from_utf8_lossy(&connect);
+        assert!(connect.contains("duck-mqtt"));
+        assert!(connect.contains("patrik"));
+        assert!(connect.contains("hunter2"));
+    }
+
+    #[test]
+    fn should_return_error_if_broker_does_not_acknowledge_messages() {
+        // Given
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            while let Some((header, _)) = read_packet(&mut stream) {
+                // Accept the connection, but never acknowledge anything else.
+                if header >> 4 == 1 {
+                    stream.write_all(&[0x20, 0x02, 0x00, 0x00]).unwrap();
+                }
+            }
+        });
+        let mut publisher = RumqttPublisher::new(&MqttConfiguration {
+            id: "mqtt".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            broker: MqttBrokerConfiguration {
+                host: "127.0.0.1".to_string(),
+                port: Some(port),
+                tls: Some(false),
+                client_id: None,
+                credentials: None,
+            },
+            topics: None,
+            qos: Some(1),
+            retain: Some(true),
+        })
+        .unwrap();
+        publisher.timeout = Duration::from_millis(500);
+
+        // When
+        let result = publisher.publish(&[MqttMessage {
+            topic: "duck/status".to_string(),
+            payload: "Failed".to_string(),
+        }]);
+
+        // Then
+        assert_eq!(
+            "Timed out while publishing MQTT messages to 127.0.0.1.",
+            result.err().unwrap().to_string()
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use crate::config::{MqttConfiguration, Validate};
+use crate::utils::DuckResult;
+
+impl Validate for MqttConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if self.id.is_empty() {
+            return Err(format_err!("MQTT observer have no ID."));
+        }
+        if self.broker.host.is_empty() {
+            return Err(format_err!(
+                "MQTT observer '{}' have no broker host.",
+                self.id
+            ));
+        }
+        if let Some(qos) = self.qos {
+            if qos > 2 {
+                return Err(format_err!(
+                    "MQTT observer '{}' have an invalid QoS ({}).",
+                    self.id,
+                    qos
+                ));
+            }
+        }
+        if let Some(topics) = &self.topics {
+            for topic in topics.status.iter().chain(topics.builds.iter()) {
+                if topic.is_empty() || topic.contains('+') || topic.contains('#') {
+                    return Err(format_err!(
+                        "MQTT observer '{}' have an invalid topic '{}'.",
+                        self.id,
+                        topic
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers::DuckProviderCollection;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "MQTT observer 'mqtt' have an invalid QoS (3).")]
+    fn should_return_error_if_qos_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "mqtt": {
+                            "id": "mqtt",
+                            "broker": {
+                                "host": "localhost"
+                            },
+                            "qos": 3
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "MQTT observer 'mqtt' have an invalid topic 'duck/#'.")]
+    fn should_return_error_if_topic_contains_wildcard() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "mqtt": {
+                            "id": "mqtt",
+                            "broker": {
+                                "host": "localhost"
+                            },
+                            "topics": {
+                                "status": "duck/#"
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+}
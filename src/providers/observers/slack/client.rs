@@ -1,38 +1,125 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde_json::Value;
+
+use crate::builds::{Build, BuildStatus};
 use crate::config::{SlackConfiguration, SlackCredentials};
 use crate::utils::http::{HttpClient, HttpRequestBuilder, HttpResponse};
 use crate::utils::DuckResult;
 
+const SLACK_API_URL: &str = "https://slack.com/api";
+const ACKNOWLEDGEMENT_ICON: &str = ":eyes:";
+
 pub struct SlackClient {
     credentials: SlackCredentials,
+    channel: Option<String>,
+    /// Messages about failed builds, keyed by build partition.
+    messages: Mutex<HashMap<u64, SlackMessage>>,
 }
 
-impl SlackCredentials {
-    pub fn get_url(&self) -> &str {
-        match self {
-            SlackCredentials::Webhook { url } => &url[..],
-        }
-    }
+/// A message that has been posted using the Web API.
+#[derive(Clone)]
+struct SlackMessage {
+    channel: String,
+    ts: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct SlackApiResponse {
+    ok: bool,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    channel: Option<String>,
+    #[serde(default)]
+    ts: Option<String>,
 }
 
 impl SlackClient {
     pub fn new(config: &SlackConfiguration) -> Self {
         SlackClient {
             credentials: config.credentials.clone(),
+            channel: config.channel.clone(),
+            messages: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn send(&self, client: &impl HttpClient, build: &Build) -> DuckResult<()> {
+        match &self.credentials {
+            SlackCredentials::Webhook { url } => self.send_to_webhook(client, url, build),
+            SlackCredentials::Token { token } => self.send_to_api(client, token, build),
         }
     }
 
-    pub fn send(&self, client: &impl HttpClient, message: &str, icon: &str) -> DuckResult<()> {
-        let mut builder = HttpRequestBuilder::put(self.credentials.get_url().to_string());
+    /// Lets everyone know that someone is looking into the build.
+    pub fn send_acknowledgement(&self, client: &impl HttpClient, build: &Build) -> DuckResult<()> {
+        let text = match get_acknowledgement_text(build) {
+            Some(text) => text,
+            None => return Ok(()),
+        };
+
+        match &self.credentials {
+            SlackCredentials::Webhook { url } => {
+                self.post_to_webhook(client, url, ACKNOWLEDGEMENT_ICON, text)
+            }
+            SlackCredentials::Token { token } => {
+                // Reply in the thread about the failed build if there is one.
+                let existing = self.messages.lock().unwrap().get(&build.partition).cloned();
+                let mut payload = json!({
+                    "username": "Duck",
+                    "icon_emoji": ACKNOWLEDGEMENT_ICON,
+                    "text": text
+                });
+                match existing {
+                    Some(message) => {
+                        payload["channel"] = json!(message.channel);
+                        payload["thread_ts"] = json!(message.ts);
+                    }
+                    None => payload["channel"] = json!(self.channel.clone().unwrap_or_default()),
+                };
+                self.call(client, token, "chat.postMessage", payload)?;
+                Ok(())
+            }
+        }
+    }
+
+    fn send_to_webhook(
+        &self,
+        client: &impl HttpClient,
+        url: &str,
+        build: &Build,
+    ) -> DuckResult<()> {
+        let mut text = get_text(build);
+        if let Some(failed) = get_failed_stages(build) {
+            text.push_str(&format!("\n*Failed:* {}", failed));
+        }
+        if let Some(tests) = get_tests(build) {
+            text.push_str(&format!("\n*Tests:* {}", tests));
+        }
+        self.post_to_webhook(client, url, get_icon(build), text)
+    }
+
+    fn post_to_webhook(
+        &self,
+        client: &impl HttpClient,
+        url: &str,
+        icon: &str,
+        text: String,
+    ) -> DuckResult<()> {
+        let mut payload = json!({
+            "username": "Duck",
+            "icon_emoji": icon,
+            "text": text
+        });
+        if let Some(channel) = &self.channel {
+            payload["channel"] = json!(channel);
+        }
+
+        let mut builder = HttpRequestBuilder::put(url.to_string());
         builder.add_header("Content-Type", "application/json");
         builder.add_header("Accept", "application/json");
-        builder.set_body(
-            json!({
-                "username": "Duck",
-                "icon_emoji": icon,
-                "text": message
-            })
-            .to_string(),
-        );
+        builder.set_body(payload.to_string());
 
         let response = client.send(&builder)?;
         if !response.status().is_success() {
@@ -44,4 +131,227 @@ impl SlackClient {
 
         Ok(())
     }
+
+    fn send_to_api(&self, client: &impl HttpClient, token: &str, build: &Build) -> DuckResult<()> {
+        let mut messages = self.messages.lock().unwrap();
+        let existing = messages.get(&build.partition).cloned();
+
+        match (&build.status, existing) {
+            (BuildStatus::Failed, None) => {
+                // Post a new message about the failed build,
+                // and remember it so we can update it later.
+                let channel = self.channel.clone().unwrap_or_default();
+                let response = self.call(
+                    client,
+                    token,
+                    "chat.postMessage",
+                    get_api_payload(&channel, build),
+                )?;
+                if let (Some(channel), Some(ts)) = (response.channel, response.ts) {
+                    messages.insert(build.partition, SlackMessage { channel, ts });
+                }
+            }
+            (_, Some(message)) => {
+                // The build has been re-run, so update the original
+                // message and let everyone in the thread know about it.
+                let mut payload = get_api_payload(&message.channel, build);
+                payload["ts"] = json!(message.ts);
+                self.call(client, token, "chat.update", payload)?;
+
+                let mut reply = get_api_payload(&message.channel, build);
+                reply["thread_ts"] = json!(message.ts);
+                self.call(client, token, "chat.postMessage", reply)?;
+
+                if build.status == BuildStatus::Success {
+                    messages.remove(&build.partition);
+                }
+            }
+            (_, None) => {
+                let channel = self.channel.clone().unwrap_or_default();
+                self.call(
+                    client,
+                    token,
+                    "chat.postMessage",
+                    get_api_payload(&channel, build),
+                )?;
+            }
+        };
+
+        Ok(())
+    }
+
+    fn call(
+        &self,
+        client: &impl HttpClient,
+        token: &str,
+        method: &str,
+        payload: Value,
+    ) -> DuckResult<SlackApiResponse> {
+        let mut builder = HttpRequestBuilder::post(format!("{}/{}", SLACK_API_URL, method));
+        builder.add_header("Content-Type", "application/json; charset=utf-8");
+        builder.add_header("Accept", "application/json");
+        builder.add_header("Authorization", &*format!("Bearer {}", token));
+        builder.set_body(payload.to_string());
+
+        let mut response = client.send(&builder)?;
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Could not send Slack message ({})",
+                response.status()
+            ));
+        }
+
+        // The Slack Web API returns 200 OK even if the call failed.
+        let result: SlackApiResponse = response.deserialize_json()?;
+        if !result.ok {
+            return Err(format_err!(
+                "Could not send Slack message ({})",
+                result.error.unwrap_or_else(|| "unknown error".to_string())
+            ));
+        }
+
+        Ok(result)
+    }
+}
+
+fn get_icon(build: &Build) -> &'static str {
+    match build.status {
+        BuildStatus::Success => ":heavy_check_mark:",
+        BuildStatus::Failed => ":heavy_multiplication_x:",
+        _ => ":question:",
+    }
+}
+
+fn get_acknowledgement_text(build: &Build) -> Option<String> {
+    let acknowledgement = build.acknowledged.as_ref()?;
+    let mut text = format!(
+        "{} is looking into {}::{} ({})",
+        acknowledgement.by, build.project_name, build.definition_name, build.branch
+    );
+    if let Some(note) = &acknowledgement.note {
+        text.push_str(&format!("\n> {}", note));
+    }
+    Some(text)
+}
+
+fn get_text(build: &Build) -> String {
+    format!(
+        "{:?} build status for {}::{} ({}) changed to *{:?}*",
+        build.provider, build.project_name, build.definition_name, build.branch, build.status
+    )
+}
+
+fn get_failed_stages(build: &Build) -> Option<String> {
+    if build.status != BuildStatus::Failed {
+        return None;
+    }
+    let failed: Vec<String> = build
+        .get_failed_stages()
+        .iter()
+        .map(|stage| match &stage.url {
+            Some(url) => format!("<{}|{}>", url, stage.name),
+            None => stage.name.clone(),
+        })
+        .collect();
+    if failed.is_empty() {
+        return None;
+    }
+    Some(failed.join(", "))
+}
+
+fn get_tests(build: &Build) -> Option<String> {
+    if build.status != BuildStatus::Failed {
+        return None;
+    }
+    let tests = build.tests.as_ref()?;
+    let mut text = tests.summary();
+    if !tests.failures.is_empty() {
+        let failures: Vec<String> = tests
+            .failures
+            .iter()
+            .map(|name| format!("`{}`", name))
+            .collect();
+        text.push_str(&format!(" ({})", failures.join(", ")));
+    }
+    Some(text)
+}
+
+fn get_context(build: &Build) -> String {
+    let mut context = format!("{:?} build {}", build.provider, build.build_number);
+    if let Some(commit) = &build.commit {
+        context.push_str(&format!(" for `{}`", get_short_commit(commit)));
+    }
+    if let Some(author) = &build.author {
+        context.push_str(&format!(" by {}", author));
+    }
+    context
+}
+
+fn get_short_commit(commit: &str) -> &str {
+    match commit.char_indices().nth(7) {
+        Some((index, _)) => &commit[..index],
+        None => commit,
+    }
+}
+
+fn get_api_payload(channel: &str, build: &Build) -> Value {
+    let mut payload = json!({
+        "channel": channel,
+        "username": "Duck",
+        "icon_emoji": get_icon(build),
+        "text": get_text(build),
+        "blocks": [
+            {
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "{} <{}|{}::{}> ({}) changed to *{:?}*",
+                        get_icon(build),
+                        build.url,
+                        build.project_name,
+                        build.definition_name,
+                        build.branch,
+                        build.status
+                    )
+                }
+            },
+            {
+                "type": "context",
+                "elements": [
+                    {
+                        "type": "mrkdwn",
+                        "text": get_context(build)
+                    }
+                ]
+            }
+        ]
+    });
+    if let Some(failed) = get_failed_stages(build) {
+        payload["blocks"].as_array_mut().unwrap().insert(
+            1,
+            json!({
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": format!("*Failed:* {}", failed)
+                }
+            }),
+        );
+    }
+    if let Some(tests) = get_tests(build) {
+        let blocks = payload["blocks"].as_array_mut().unwrap();
+        let index = blocks.len() - 1;
+        blocks.insert(
+            index,
+            json!({
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": format!("*Tests:* {}", tests)
+                }
+            }),
+        );
+    }
+    payload
 }
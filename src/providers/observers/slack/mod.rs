@@ -37,6 +37,8 @@ impl<T: HttpClient + Default> SlackObserver<T> {
                         Some(HashSet::from_iter(collectors.iter().cloned()))
                     }
                 },
+                view: config.view.clone(),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
             },
         }
     }
@@ -53,29 +55,23 @@ impl<T: HttpClient + Default> Observer for SlackObserver<T> {
     }
 
     fn observe(&self, observation: Observation) -> DuckResult<()> {
-        if let Observation::BuildStatusChanged(build) = observation {
-            if is_interesting_status(&build.status) {
+        if observation.is_muted() {
+            return Ok(());
+        }
+
+        match observation {
+            Observation::BuildStatusChanged(build) if is_interesting_status(&build.status) => {
                 info!(
                     "Sending Slack message since build status changed ({:?})...",
                     build.status
                 );
-                self.client.send(
-                    &self.http,
-                    &format!(
-                        "{:?} build status for {}::{} ({}) changed to *{:?}*",
-                        build.provider,
-                        build.project_name,
-                        build.definition_name,
-                        build.branch,
-                        build.status
-                    )[..],
-                    match build.status {
-                        BuildStatus::Success => ":heavy_check_mark:",
-                        BuildStatus::Failed => ":heavy_multiplication_x:",
-                        _ => ":question:",
-                    },
-                )?;
+                self.client.send(&self.http, build)?;
             }
+            Observation::BuildAcknowledged(build) => {
+                info!("Sending Slack message since build was acknowledged...");
+                self.client.send_acknowledgement(&self.http, build)?;
+            }
+            _ => {}
         };
 
         Ok(())
@@ -92,9 +88,11 @@ fn is_interesting_status(status: &BuildStatus) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::builds::{BuildBuilder, BuildStatus};
+    use crate::builds::{Acknowledgement, BuildBuilder, BuildStage, BuildStatus, BuildTests};
     use crate::config::SlackCredentials;
-    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use crate::utils::http::{
+        HttpMethod, HttpRequestBuilder, MockHttpClient, MockHttpResponseBuilder,
+    };
     use reqwest::StatusCode;
     use test_case::test_case;
 
@@ -105,6 +103,8 @@ mod tests {
             id: "hue".to_string(),
             enabled: Some(true),
             collectors: None,
+            view: None,
+            pull_requests: None,
             channel: None,
             credentials: SlackCredentials::Webhook {
                 url: "https://example.com/webhook".to_string(),
@@ -139,6 +139,8 @@ mod tests {
             id: "hue".to_string(),
             enabled: Some(true),
             collectors: None,
+            view: None,
+            pull_requests: None,
             channel: None,
             credentials: SlackCredentials::Webhook {
                 url: "https://example.com/webhook".to_string(),
@@ -173,6 +175,8 @@ mod tests {
             id: "hue".to_string(),
             enabled: Some(true),
             collectors: None,
+            view: None,
+            pull_requests: None,
             channel: None,
             credentials: SlackCredentials::Webhook {
                 url: "https://example.com/webhook".to_string(),
@@ -197,4 +201,438 @@ mod tests {
             .observe(Observation::DuckStatusChanged(BuildStatus::Success))
             .unwrap();
     }
+
+    fn get_body(request: &HttpRequestBuilder) -> serde_json::Value {
+        serde_json::from_str(&request.body.clone().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn should_include_channel_in_webhook_payload() {
+        // Given
+        let slack = SlackObserver::<MockHttpClient>::new(&SlackConfiguration {
+            id: "slack".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: Some("#builds".to_string()),
+            credentials: SlackCredentials::Webhook {
+                url: "https://example.com/webhook".to_string(),
+            },
+        });
+
+        let client = slack.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Put, "https://example.com/webhook")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        slack
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!("#builds", get_body(&requests[0])["channel"]);
+    }
+
+    #[test]
+    fn should_post_message_using_web_api() {
+        // Given
+        let slack = SlackObserver::<MockHttpClient>::new(&SlackConfiguration {
+            id: "slack".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: Some("#builds".to_string()),
+            credentials: SlackCredentials::Token {
+                token: "xoxb-secret".to_string(),
+            },
+        });
+
+        let client = slack.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://slack.com/api/chat.postMessage",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(
+                "{ \"ok\": true, \"channel\": \"C1234\", \"ts\": \"1503435956.000247\" }",
+            ),
+        );
+
+        // When
+        slack
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(HttpMethod::Post, requests[0].method);
+        assert_eq!("https://slack.com/api/chat.postMessage", &requests[0].url);
+        assert_eq!(
+            "Bearer xoxb-secret",
+            requests[0].headers.get("Authorization").unwrap()
+        );
+        let body = get_body(&requests[0]);
+        assert_eq!("#builds", body["channel"]);
+        assert_eq!(
+            ":heavy_multiplication_x: <https://dummy|project_name::definition_name> (branch) changed to *Failed*",
+            body["blocks"][0]["text"]["text"]
+        );
+    }
+
+    #[test]
+    fn should_include_commit_and_author_in_message_context() {
+        // Given
+        let slack = SlackObserver::<MockHttpClient>::new(&SlackConfiguration {
+            id: "slack".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: Some("#builds".to_string()),
+            credentials: SlackCredentials::Token {
+                token: "xoxb-secret".to_string(),
+            },
+        });
+
+        let client = slack.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://slack.com/api/chat.postMessage",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(
+                "{ \"ok\": true, \"channel\": \"C1234\", \"ts\": \"1503435956.000247\" }",
+            ),
+        );
+
+        // When
+        slack
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .status(BuildStatus::Failed)
+                    .commit(Some("677c1482e734f5df".to_string()))
+                    .author(Some("Patrik".to_string()))
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(
+            "TeamCity build build_number for `677c148` by Patrik",
+            get_body(&requests[0])["blocks"][1]["elements"][0]["text"]
+        );
+    }
+
+    #[test]
+    fn should_include_failed_stages_for_failed_builds() {
+        // Given
+        let slack = SlackObserver::<MockHttpClient>::new(&SlackConfiguration {
+            id: "slack".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: Some("#builds".to_string()),
+            credentials: SlackCredentials::Token {
+                token: "xoxb-secret".to_string(),
+            },
+        });
+
+        let client = slack.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://slack.com/api/chat.postMessage",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(
+                "{ \"ok\": true, \"channel\": \"C1234\", \"ts\": \"1503435956.000247\" }",
+            ),
+        );
+
+        // When
+        slack
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .status(BuildStatus::Failed)
+                    .stages(Some(vec![
+                        BuildStage::dummy("build", BuildStatus::Failed),
+                        BuildStage::dummy("test", BuildStatus::Skipped),
+                    ]))
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(
+            "*Failed:* <https://dummy/build|build>",
+            get_body(&requests[0])["blocks"][1]["text"]["text"]
+        );
+    }
+
+    #[test]
+    fn should_include_test_summary_for_failed_builds() {
+        // Given
+        let slack = SlackObserver::<MockHttpClient>::new(&SlackConfiguration {
+            id: "slack".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: Some("#builds".to_string()),
+            credentials: SlackCredentials::Token {
+                token: "xoxb-secret".to_string(),
+            },
+        });
+
+        let client = slack.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://slack.com/api/chat.postMessage",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(
+                "{ \"ok\": true, \"channel\": \"C1234\", \"ts\": \"1503435956.000247\" }",
+            ),
+        );
+
+        // When
+        slack
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .status(BuildStatus::Failed)
+                    .tests(Some(BuildTests {
+                        total: Some(145),
+                        passed: Some(142),
+                        failed: 3,
+                        ignored: None,
+                        failures: vec!["foo".to_string(), "bar".to_string()],
+                    }))
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(
+            "*Tests:* 142 passed, 3 failed (`foo`, `bar`)",
+            get_body(&requests[0])["blocks"][1]["text"]["text"]
+        );
+    }
+
+    #[test]
+    fn should_update_message_and_reply_in_thread_when_build_is_fixed() {
+        // Given
+        let slack = SlackObserver::<MockHttpClient>::new(&SlackConfiguration {
+            id: "slack".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: Some("#builds".to_string()),
+            credentials: SlackCredentials::Token {
+                token: "xoxb-secret".to_string(),
+            },
+        });
+
+        let client = slack.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://slack.com/api/chat.postMessage",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(
+                "{ \"ok\": true, \"channel\": \"C1234\", \"ts\": \"1503435956.000247\" }",
+            ),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://slack.com/api/chat.update")
+                .returns_status(StatusCode::OK)
+                .returns_body(
+                    "{ \"ok\": true, \"channel\": \"C1234\", \"ts\": \"1503435956.000247\" }",
+                ),
+        );
+        slack
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+
+        // When
+        slack
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Success).unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(3, requests.len());
+        assert_eq!("https://slack.com/api/chat.update", &requests[1].url);
+        let update = get_body(&requests[1]);
+        assert_eq!("C1234", update["channel"]);
+        assert_eq!("1503435956.000247", update["ts"]);
+        assert_eq!(
+            "TeamCity build status for project_name::definition_name (branch) changed to *Success*",
+            update["text"]
+        );
+        assert_eq!("https://slack.com/api/chat.postMessage", &requests[2].url);
+        let reply = get_body(&requests[2]);
+        assert_eq!("C1234", reply["channel"]);
+        assert_eq!("1503435956.000247", reply["thread_ts"]);
+    }
+
+    #[test]
+    fn should_post_new_message_for_next_failure_once_fixed() {
+        // Given
+        let slack = SlackObserver::<MockHttpClient>::new(&SlackConfiguration {
+            id: "slack".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: Some("#builds".to_string()),
+            credentials: SlackCredentials::Token {
+                token: "xoxb-secret".to_string(),
+            },
+        });
+
+        let client = slack.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://slack.com/api/chat.postMessage",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(
+                "{ \"ok\": true, \"channel\": \"C1234\", \"ts\": \"1503435956.000247\" }",
+            ),
+        );
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://slack.com/api/chat.update")
+                .returns_status(StatusCode::OK)
+                .returns_body(
+                    "{ \"ok\": true, \"channel\": \"C1234\", \"ts\": \"1503435956.000247\" }",
+                ),
+        );
+        for status in &[BuildStatus::Failed, BuildStatus::Success] {
+            slack
+                .observe(Observation::BuildStatusChanged(
+                    &BuildBuilder::dummy().status(status.clone()).unwrap(),
+                ))
+                .unwrap();
+        }
+
+        // When
+        slack
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(4, requests.len());
+        assert_eq!("https://slack.com/api/chat.postMessage", &requests[3].url);
+        assert!(get_body(&requests[3]).get("thread_ts").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not send Slack message (channel_not_found)")]
+    fn should_return_error_if_web_api_call_was_not_successful() {
+        // Given
+        let slack = SlackObserver::<MockHttpClient>::new(&SlackConfiguration {
+            id: "slack".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: Some("#builds".to_string()),
+            credentials: SlackCredentials::Token {
+                token: "xoxb-secret".to_string(),
+            },
+        });
+
+        let client = slack.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://slack.com/api/chat.postMessage",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body("{ \"ok\": false, \"error\": \"channel_not_found\" }"),
+        );
+
+        // When, Then
+        slack
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+    }
+
+    #[test]
+    fn should_reply_in_thread_when_build_is_acknowledged() {
+        // Given
+        let slack = SlackObserver::<MockHttpClient>::new(&SlackConfiguration {
+            id: "slack".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            channel: Some("#builds".to_string()),
+            credentials: SlackCredentials::Token {
+                token: "xoxb-secret".to_string(),
+            },
+        });
+
+        let client = slack.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(
+                HttpMethod::Post,
+                "https://slack.com/api/chat.postMessage",
+            )
+            .returns_status(StatusCode::OK)
+            .returns_body(
+                "{ \"ok\": true, \"channel\": \"C1234\", \"ts\": \"1503435956.000247\" }",
+            ),
+        );
+        let mut build = BuildBuilder::dummy().status(BuildStatus::Failed).unwrap();
+        build.acknowledged = Some(Acknowledgement::dummy("Patrik"));
+        slack
+            .observe(Observation::BuildStatusChanged(&build))
+            .unwrap();
+
+        // When
+        slack
+            .observe(Observation::BuildAcknowledged(&build))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(2, requests.len());
+        let reply = get_body(&requests[1]);
+        assert_eq!("C1234", reply["channel"]);
+        assert_eq!("1503435956.000247", reply["thread_ts"]);
+        assert_eq!(
+            "Patrik is looking into project_name::definition_name (branch)\n> On it",
+            reply["text"]
+        );
+    }
 }
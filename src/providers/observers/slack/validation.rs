@@ -14,6 +14,14 @@ impl Validate for SlackConfiguration {
                     return Err(format_err!("Slack webhook URL is invalid: {}", e));
                 }
             }
+            SlackCredentials::Token { token } => {
+                if token.is_empty() {
+                    return Err(format_err!("Slack token is empty."));
+                }
+                if self.channel.as_ref().map(|c| c.is_empty()).unwrap_or(true) {
+                    return Err(format_err!("Slack channel must be set when using a token."));
+                }
+            }
         };
         Ok(())
     }
@@ -82,4 +90,63 @@ mod tests {
         let collection = DuckProviderCollection::new();
         collection.get_observers(&config).unwrap();
     }
+
+    #[test]
+    #[should_panic(expected = "Slack token is empty.")]
+    fn should_return_error_if_slack_token_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "slack": {
+                            "id": "foo",
+                            "channel": "builds",
+                            "credentials": {
+                                "token": {
+                                    "token": ""
+                                }
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Slack channel must be set when using a token.")]
+    fn should_return_error_if_slack_channel_is_missing_when_using_token() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "slack": {
+                            "id": "foo",
+                            "credentials": {
+                                "token": {
+                                    "token": "xoxb-secret"
+                                }
+                            }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
 }
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use log::warn;
+use sha2::Sha256;
+
+use crate::config::{WebhookConfiguration, WebhookEvent};
+use crate::utils::http::{HttpClient, HttpRequestBuilder, HttpResponse};
+use crate::utils::DuckResult;
+
+pub struct WebhookClient {
+    url: String,
+    headers: HashMap<String, String>,
+    secret: Option<String>,
+    retries: u32,
+    retry_delay: Duration,
+}
+
+impl WebhookClient {
+    pub fn new(config: &WebhookConfiguration) -> Self {
+        WebhookClient {
+            url: config.url.clone(),
+            headers: config.headers.clone().unwrap_or_default(),
+            secret: config.secret.clone(),
+            retries: config.retries.unwrap_or(3),
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn set_retry_delay(&mut self, delay: Duration) {
+        self.retry_delay = delay;
+    }
+
+    pub fn send(
+        &self,
+        client: &impl HttpClient,
+        event: WebhookEvent,
+        body: &str,
+    ) -> DuckResult<()> {
+        let mut builder = HttpRequestBuilder::post(self.url.clone());
+        for (name, value) in self.headers.iter() {
+            builder.add_header(&name[..], &value[..]);
+        }
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("X-Duck-Event", &get_event_name(event)[..]);
+        if let Some(secret) = &self.secret {
+            builder.add_header("X-Duck-Signature", &sign(secret, body)[..]);
+        }
+        builder.set_body(body.to_string());
+
+        let mut attempt = 0;
+        loop {
+            let error = match client.send(&builder) {
+                Ok(response) => {
+                    let status = response.status();
+                    if status.is_success() {
+                        return Ok(());
+                    }
+                    if status.is_client_error() && status.as_u16() != 429 {
+                        // No use in retrying this.
+                        return Err(format_err!("Could not send webhook ({})", status));
+                    }
+                    format_err!("Could not send webhook ({})", status)
+                }
+                Err(e) => e,
+            };
+
+            if attempt >= self.retries {
+                return Err(error);
+            }
+
+            // Back off a little more for every attempt.
+            attempt += 1;
+            warn!("{} Retrying ({}/{})...", error, attempt, self.retries);
+            thread::sleep(self.retry_delay * attempt);
+        }
+    }
+}
+
+fn get_event_name(event: WebhookEvent) -> String {
+    match serde_json::to_value(event) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => format!("{:?}", event),
+    }
+}
+
+/// Signs the body using HMAC-SHA256 and returns
+/// the signature in the form `sha256=<hex digest>`.
+fn sign(secret: &str, body: &str) -> String {
+    // HMAC accepts keys of any length, so this can't fail.
+    let mut mac = Hmac::<Sha256>::new_varkey(secret.as_bytes()).unwrap();
+    mac.input(body.as_bytes());
+    let digest = mac.result().code();
+    let hex: Vec<String> = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex.join(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_sign_body_with_hmac_sha256() {
+        // Test vector from RFC 4231 (test case 2)
+        assert_eq!(
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            sign("Jefe", "what do ya want for nothing?")
+        );
+    }
+}
@@ -0,0 +1,622 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use chrono::Utc;
+
+use crate::config::{WebhookConfiguration, WebhookEvent};
+use crate::providers::observers::{Observation, Observer, ObserverInfo};
+use crate::utils::http::HttpClient;
+use crate::utils::DuckResult;
+
+use self::client::WebhookClient;
+use self::payload::WebhookPayload;
+
+mod client;
+pub mod payload;
+mod validation;
+
+pub struct WebhookObserver<T: HttpClient + Default> {
+    client: WebhookClient,
+    http: T,
+    events: Option<HashSet<WebhookEvent>>,
+    info: ObserverInfo,
+}
+
+impl<T: HttpClient + Default> WebhookObserver<T> {
+    pub fn new(config: &WebhookConfiguration) -> Self {
+        WebhookObserver {
+            client: WebhookClient::new(config),
+            http: Default::default(),
+            events: match &config.events {
+                Option::None => Option::None,
+                Option::Some(events) => Some(HashSet::from_iter(events.iter().cloned())),
+            },
+            info: ObserverInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    None => true,
+                    Some(e) => e,
+                },
+                collectors: match &config.collectors {
+                    Option::None => Option::None,
+                    Option::Some(collectors) => {
+                        Some(HashSet::from_iter(collectors.iter().cloned()))
+                    }
+                },
+                view: config.view.clone(),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+}
+
+impl<T: HttpClient + Default> Observer for WebhookObserver<T> {
+    fn info(&self) -> &ObserverInfo {
+        &self.info
+    }
+
+    fn observe(&self, observation: Observation) -> DuckResult<()> {
+        let payload = WebhookPayload::new(&observation, Utc::now().timestamp());
+        if let Some(events) = &self.events {
+            if !events.contains(&payload.event) {
+                return Ok(());
+            }
+        }
+
+        let body = serde_json::to_string(&payload)?;
+        self.client.send(&self.http, payload.event, &body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::{Acknowledgement, BuildBuilder, BuildStatus, BuildTests, BuildTrigger};
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    #[test]
+    fn should_post_build_status_changed_payload() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: None,
+            events: None,
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        webhook
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().status(BuildStatus::Failed).unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(HttpMethod::Post, requests[0].method);
+        assert_eq!(
+            "buildStatusChanged",
+            requests[0].headers.get("X-Duck-Event").unwrap()
+        );
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()[..]).unwrap();
+        assert_eq!("buildStatusChanged", body["event"]);
+        assert_eq!("Failed", body["status"]);
+        assert_eq!("TeamCity", body["build"]["provider"]);
+        assert_eq!("collector", body["build"]["collector"]);
+        assert_eq!("project_name", body["build"]["project"]);
+        assert_eq!("definition_name", body["build"]["definition"]);
+        assert_eq!("branch", body["build"]["branch"]);
+        assert_eq!("foo", body["build"]["buildId"]);
+        assert_eq!("build_number", body["build"]["buildNumber"]);
+        assert_eq!("Failed", body["build"]["status"]);
+    }
+
+    #[test]
+    fn should_include_commit_information_in_payload() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: None,
+            events: None,
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        webhook
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .commit(Some("abc123".to_string()))
+                    .author(Some("Patrik".to_string()))
+                    .trigger(Some(BuildTrigger::PullRequest))
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()[..]).unwrap();
+        assert_eq!("abc123", body["build"]["commit"]);
+        assert_eq!("Patrik", body["build"]["author"]);
+        assert_eq!(json!(null), body["build"]["committer"]);
+        assert_eq!("PullRequest", body["build"]["trigger"]);
+        assert_eq!(1000, body["build"]["duration"]);
+    }
+
+    #[test]
+    fn should_include_tests_in_payload() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: None,
+            events: None,
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        webhook
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy()
+                    .status(BuildStatus::Failed)
+                    .tests(Some(BuildTests {
+                        total: Some(145),
+                        passed: Some(142),
+                        failed: 3,
+                        ignored: None,
+                        failures: vec!["foo".to_string(), "bar".to_string()],
+                    }))
+                    .unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()[..]).unwrap();
+        assert_eq!(145, body["build"]["tests"]["total"]);
+        assert_eq!(3, body["build"]["tests"]["failed"]);
+        assert_eq!(json!(null), body["build"]["tests"]["ignored"]);
+        assert_eq!(json!(["foo", "bar"]), body["build"]["tests"]["failures"]);
+    }
+
+    #[test]
+    fn should_post_build_acknowledged_payload() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: None,
+            events: None,
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::OK),
+        );
+        let mut build = BuildBuilder::dummy().status(BuildStatus::Failed).unwrap();
+        build.acknowledged = Some(Acknowledgement::dummy("Patrik"));
+
+        // When
+        webhook
+            .observe(Observation::BuildAcknowledged(&build))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()[..]).unwrap();
+        assert_eq!("buildAcknowledged", body["event"]);
+        assert_eq!("Patrik", body["build"]["acknowledged"]["by"]);
+        assert_eq!("On it", body["build"]["acknowledged"]["note"]);
+        assert_eq!(json!(null), body["build"]["muted"]);
+    }
+
+    #[test]
+    fn should_post_build_updated_payload() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: None,
+            events: None,
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        webhook
+            .observe(Observation::BuildUpdated(
+                &BuildBuilder::dummy().status(BuildStatus::Running).unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()[..]).unwrap();
+        assert_eq!("buildUpdated", body["event"]);
+        assert_eq!("Running", body["build"]["status"]);
+    }
+
+    #[test]
+    fn should_post_duck_status_changed_payload() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: None,
+            events: None,
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        webhook
+            .observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()[..]).unwrap();
+        assert_eq!("duckStatusChanged", body["event"]);
+        assert_eq!("Success", body["status"]);
+        assert!(body["build"].is_null());
+    }
+
+    #[test]
+    fn should_post_shutting_down_payload() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: None,
+            events: None,
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        webhook.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let body: serde_json::Value =
+            serde_json::from_str(&requests[0].body.clone().unwrap()[..]).unwrap();
+        assert_eq!("shuttingDown", body["event"]);
+        assert!(body["status"].is_null());
+        assert!(body["build"].is_null());
+    }
+
+    #[test]
+    fn should_send_custom_headers() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: None,
+            events: None,
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        webhook.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!("SECRET-KEY", requests[0].headers.get("X-Api-Key").unwrap());
+    }
+
+    #[test]
+    fn should_sign_payload_if_secret_is_configured() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: Some("s3cr3t".to_string()),
+            events: None,
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        webhook.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        let signature = requests[0].headers.get("X-Duck-Signature").unwrap();
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(71, signature.len());
+    }
+
+    #[test]
+    fn should_not_sign_payload_if_secret_is_not_configured() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: None,
+            events: None,
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        webhook.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert!(!requests[0].headers.contains_key("X-Duck-Signature"));
+    }
+
+    #[test]
+    fn should_only_send_selected_events() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: None,
+            events: Some(vec![WebhookEvent::BuildStatusChanged]),
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        webhook
+            .observe(Observation::BuildUpdated(&BuildBuilder::dummy().unwrap()))
+            .unwrap();
+        webhook
+            .observe(Observation::DuckStatusChanged(BuildStatus::Failed))
+            .unwrap();
+        webhook.observe(Observation::ShuttingDown).unwrap();
+        webhook
+            .observe(Observation::BuildStatusChanged(
+                &BuildBuilder::dummy().unwrap(),
+            ))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(
+            "buildStatusChanged",
+            requests[0].headers.get("X-Duck-Event").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_retry_if_server_returns_error() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: None,
+            events: None,
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::BAD_GATEWAY),
+        );
+
+        // When
+        let result = webhook.observe(Observation::ShuttingDown);
+
+        // Then
+        assert!(result.is_err());
+        assert_eq!(3, client.get_sent_requests().len());
+    }
+
+    #[test]
+    fn should_not_retry_if_server_rejects_request() {
+        // Given
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), "SECRET-KEY".to_string());
+
+        let mut webhook = WebhookObserver::<MockHttpClient>::new(&WebhookConfiguration {
+            id: "webhook".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "https://example.com/hooks/duck".to_string(),
+            headers: Some(headers),
+            secret: None,
+            events: None,
+            retries: Some(2),
+        });
+        webhook.client.set_retry_delay(Duration::from_millis(1));
+
+        let client = webhook.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "https://example.com/hooks/duck")
+                .returns_status(StatusCode::UNAUTHORIZED),
+        );
+
+        // When
+        let result = webhook.observe(Observation::ShuttingDown);
+
+        // Then
+        assert!(result.is_err());
+        assert_eq!(1, client.get_sent_requests().len());
+    }
+}
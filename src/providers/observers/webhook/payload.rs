@@ -0,0 +1,157 @@
+use crate::builds::{Acknowledgement, Build, BuildStatus, BuildTests};
+use crate::config::WebhookEvent;
+use crate::providers::observers::Observation;
+
+/// The JSON payload posted by the webhook observer.
+///
+/// This is part of Duck's public contract, so fields
+/// should only ever be added, never renamed or removed.
+#[derive(Serialize)]
+pub struct WebhookPayload<'a> {
+    pub event: WebhookEvent,
+    pub timestamp: i64,
+    pub status: Option<String>,
+    pub build: Option<WebhookBuild<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct WebhookBuild<'a> {
+    pub id: u64,
+    pub provider: String,
+    pub collector: &'a str,
+    pub origin: Option<&'a str>,
+    pub project: &'a str,
+    pub definition: &'a str,
+    pub branch: &'a str,
+    #[serde(rename = "buildId")]
+    pub build_id: &'a str,
+    #[serde(rename = "buildNumber")]
+    pub build_number: &'a str,
+    pub status: String,
+    pub url: &'a str,
+    pub started: i64,
+    pub finished: Option<i64>,
+    pub commit: Option<&'a str>,
+    #[serde(rename = "commitMessage")]
+    pub commit_message: Option<&'a str>,
+    pub author: Option<&'a str>,
+    pub committer: Option<&'a str>,
+    pub trigger: Option<String>,
+    pub queued: Option<i64>,
+    pub duration: Option<i64>,
+    pub tests: Option<WebhookTests<'a>>,
+    pub acknowledged: Option<WebhookAcknowledgement<'a>>,
+    pub muted: Option<WebhookAcknowledgement<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct WebhookTests<'a> {
+    pub total: Option<u32>,
+    pub passed: Option<u32>,
+    pub failed: u32,
+    pub ignored: Option<u32>,
+    pub failures: &'a [String],
+}
+
+#[derive(Serialize)]
+pub struct WebhookAcknowledgement<'a> {
+    pub by: &'a str,
+    pub note: Option<&'a str>,
+    pub at: i64,
+    pub expires: Option<i64>,
+}
+
+impl<'a> WebhookPayload<'a> {
+    pub fn new(observation: &Observation<'a>, timestamp: i64) -> Self {
+        let (event, status, build) = match observation {
+            Observation::DuckStatusChanged(status) => (
+                WebhookEvent::DuckStatusChanged,
+                Some(get_status(status)),
+                None,
+            ),
+            Observation::BuildUpdated(build) => (
+                WebhookEvent::BuildUpdated,
+                Some(get_status(&build.status)),
+                Some(WebhookBuild::from(*build)),
+            ),
+            Observation::BuildStatusChanged(build) => (
+                WebhookEvent::BuildStatusChanged,
+                Some(get_status(&build.status)),
+                Some(WebhookBuild::from(*build)),
+            ),
+            Observation::BuildAcknowledged(build) => (
+                WebhookEvent::BuildAcknowledged,
+                Some(get_status(&build.status)),
+                Some(WebhookBuild::from(*build)),
+            ),
+            Observation::ShuttingDown => (WebhookEvent::ShuttingDown, None, None),
+        };
+
+        WebhookPayload {
+            event,
+            timestamp,
+            status,
+            build,
+        }
+    }
+}
+
+impl<'a> From<&'a Build> for WebhookBuild<'a> {
+    fn from(build: &'a Build) -> Self {
+        WebhookBuild {
+            id: build.id,
+            provider: format!("{:?}", build.provider),
+            collector: &build.collector,
+            origin: build.origin.as_ref().map(|o| &o[..]),
+            project: &build.project_name,
+            definition: &build.definition_name,
+            branch: &build.branch,
+            build_id: &build.build_id,
+            build_number: &build.build_number,
+            status: get_status(&build.status),
+            url: &build.url,
+            started: build.started_at,
+            finished: build.finished_at,
+            commit: build.commit.as_ref().map(|c| &c[..]),
+            commit_message: build.commit_message.as_ref().map(|m| &m[..]),
+            author: build.author.as_ref().map(|a| &a[..]),
+            committer: build.committer.as_ref().map(|c| &c[..]),
+            trigger: build.trigger.as_ref().map(|t| format!("{:?}", t)),
+            queued: build.queued_at,
+            duration: build.duration(),
+            tests: build.tests.as_ref().map(WebhookTests::from),
+            acknowledged: build
+                .acknowledged
+                .as_ref()
+                .map(WebhookAcknowledgement::from),
+            muted: build.muted.as_ref().map(WebhookAcknowledgement::from),
+        }
+    }
+}
+
+impl<'a> From<&'a BuildTests> for WebhookTests<'a> {
+    fn from(tests: &'a BuildTests) -> Self {
+        WebhookTests {
+            total: tests.total,
+            passed: tests.passed,
+            failed: tests.failed,
+            ignored: tests.ignored,
+            failures: &tests.failures,
+        }
+    }
+}
+
+impl<'a> From<&'a Acknowledgement> for WebhookAcknowledgement<'a> {
+    fn from(acknowledgement: &'a Acknowledgement) -> Self {
+        WebhookAcknowledgement {
+            by: &acknowledgement.by,
+            note: acknowledgement.note.as_ref().map(|n| &n[..]),
+            at: acknowledgement.at,
+            expires: acknowledgement.expires_at,
+        }
+    }
+}
+
+fn get_status(status: &BuildStatus) -> String {
+    format!("{:?}", status)
+}
@@ -0,0 +1,90 @@
+use url::Url;
+
+use crate::config::{Validate, WebhookConfiguration};
+use crate::utils::DuckResult;
+
+impl Validate for WebhookConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if self.id.is_empty() {
+            return Err(format_err!("Webhook observer have no ID."));
+        }
+        if let Err(e) = Url::parse(&self.url) {
+            return Err(format_err!("Webhook URL is invalid: {}", e));
+        }
+        if let Some(secret) = &self.secret {
+            if secret.is_empty() {
+                return Err(format_err!(
+                    "Webhook observer '{}' have an empty secret.",
+                    self.id
+                ));
+            }
+        }
+        if let Some(events) = &self.events {
+            if events.is_empty() {
+                return Err(format_err!(
+                    "Webhook observer '{}' have no events.",
+                    self.id
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers::DuckProviderCollection;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "Webhook URL is invalid: relative URL without a base")]
+    fn should_return_error_if_webhook_url_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "webhook": {
+                            "id": "webhook",
+                            "url": ""
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Webhook observer 'webhook' have no events.")]
+    fn should_return_error_if_events_are_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "webhook": {
+                            "id": "webhook",
+                            "url": "https://example.com/hooks/duck",
+                            "events": [ ]
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+}
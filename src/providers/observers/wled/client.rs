@@ -0,0 +1,91 @@
+use url::Url;
+
+use crate::builds::BuildStatus;
+use crate::config::{WledConfiguration, WledStateConfiguration, WledStatesConfiguration};
+use crate::utils::http::{HttpClient, HttpRequestBuilder, HttpResponse};
+use crate::utils::{colors::Rgb, DuckResult};
+
+pub struct WledClient {
+    brightness: u8,
+    url: Url,
+    states: WledStatesConfiguration,
+}
+
+impl WledClient {
+    pub fn new(config: &WledConfiguration) -> Self {
+        WledClient {
+            brightness: config.brightness.unwrap_or(255),
+            url: Url::parse(&config.url[..]).unwrap(),
+            states: config.states.clone().unwrap_or_default(),
+        }
+    }
+
+    pub fn turn_off(&self, client: &impl HttpClient) -> DuckResult<()> {
+        self.send(client, json!({ "on": false }))
+    }
+
+    pub fn set_state(&self, client: &impl HttpClient, status: BuildStatus) -> DuckResult<()> {
+        let state = self.get_state(&status);
+        let color = match &state.color {
+            Some(color) => Rgb::parse(color),
+            None => Rgb::for_status(&status),
+        };
+        let color = match color {
+            Some(color) => color,
+            None => return Ok(()),
+        };
+
+        let mut segment = json!({
+            "col": [color.to_array()],
+            "fx": state.effect.unwrap_or(0),
+        });
+        if let Some(speed) = state.speed {
+            segment["sx"] = json!(speed);
+        }
+
+        self.send(
+            client,
+            json!({
+                "on": true,
+                "bri": state.brightness.unwrap_or(self.brightness),
+                "seg": [segment],
+            }),
+        )
+    }
+
+    fn get_state(&self, status: &BuildStatus) -> WledStateConfiguration {
+        let configured = match status {
+            BuildStatus::Success => &self.states.success,
+            BuildStatus::Failed => &self.states.failed,
+            BuildStatus::Running => &self.states.running,
+            BuildStatus::Queued if self.states.queued.is_none() => &self.states.running,
+            BuildStatus::Queued => &self.states.queued,
+            BuildStatus::Blocked => &self.states.blocked,
+            BuildStatus::Canceled => &self.states.canceled,
+            BuildStatus::Unknown | BuildStatus::Skipped => &self.states.unknown,
+        };
+        match configured {
+            Some(state) => state.clone(),
+            None => WledStateConfiguration::default(),
+        }
+    }
+
+    fn send(&self, client: &impl HttpClient, body: serde_json::Value) -> DuckResult<()> {
+        let url = format!("{url}json/state", url = self.url);
+
+        let mut builder = HttpRequestBuilder::post(url);
+        builder.add_header("Content-Type", "application/json");
+        builder.add_header("Accept", "application/json");
+        builder.set_body(body.to_string());
+
+        let response = client.send(&builder)?;
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Could not update WLED state ({status})",
+                status = response.status()
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+use log::info;
+
+use crate::config::WledConfiguration;
+use crate::providers::observers::{Observation, Observer, ObserverInfo};
+use crate::utils::http::HttpClient;
+use crate::utils::DuckResult;
+
+use self::client::WledClient;
+
+mod client;
+mod validation;
+
+pub struct WledObserver<T: HttpClient + Default> {
+    client: WledClient,
+    http: T,
+    info: ObserverInfo,
+}
+
+impl<T: HttpClient + Default> WledObserver<T> {
+    pub fn new(config: &WledConfiguration) -> Self {
+        WledObserver {
+            client: WledClient::new(config),
+            http: Default::default(),
+            info: ObserverInfo {
+                id: config.id.clone(),
+                enabled: match config.enabled {
+                    None => true,
+                    Some(e) => e,
+                },
+                collectors: match &config.collectors {
+                    Option::None => Option::None,
+                    Option::Some(collectors) => {
+                        Some(HashSet::from_iter(collectors.iter().cloned()))
+                    }
+                },
+                view: config.view.clone(),
+                pull_requests: config.pull_requests.clone().unwrap_or_default(),
+            },
+        }
+    }
+
+    #[cfg(test)]
+    pub fn get_client(&self) -> &T {
+        &self.http
+    }
+}
+
+impl<T: HttpClient + Default> Observer for WledObserver<T> {
+    fn info(&self) -> &ObserverInfo {
+        &self.info
+    }
+
+    fn observe(&self, observation: Observation) -> DuckResult<()> {
+        match observation {
+            Observation::DuckStatusChanged(status) => {
+                info!(
+                    "[{}] Setting light state to '{:?}'...",
+                    self.info.id, status
+                );
+                self.client.set_state(&self.http, status)?;
+            }
+            Observation::ShuttingDown => {
+                info!("[{}] Turning off all lights...", self.info.id);
+                self.client.turn_off(&self.http)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builds::BuildStatus;
+    use crate::config::{WledStateConfiguration, WledStatesConfiguration};
+    use crate::utils::http::{HttpMethod, MockHttpClient, MockHttpResponseBuilder};
+    use reqwest::StatusCode;
+    use test_case::test_case;
+
+    #[test]
+    fn should_post_to_correct_url() {
+        // Given
+        let wled = WledObserver::<MockHttpClient>::new(&WledConfiguration {
+            id: "wled".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "http://192.168.1.50".to_string(),
+            brightness: None,
+            states: None,
+        });
+
+        let client = wled.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "http://192.168.1.50/json/state")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        wled.observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(HttpMethod::Post, requests[0].method);
+        assert_eq!("http://192.168.1.50/json/state", &requests[0].url);
+    }
+
+    #[test_case(BuildStatus::Success, "{\"bri\":255,\"on\":true,\"seg\":[{\"col\":[[0,255,0]],\"fx\":0}]}" ; "Success")]
+    #[test_case(BuildStatus::Failed, "{\"bri\":255,\"on\":true,\"seg\":[{\"col\":[[255,0,0]],\"fx\":0}]}" ; "Failed")]
+    #[test_case(BuildStatus::Running, "{\"bri\":255,\"on\":true,\"seg\":[{\"col\":[[127,200,255]],\"fx\":0}]}" ; "Running")]
+    fn should_send_correct_payload(status: BuildStatus, expected: &str) {
+        // Given
+        let wled = WledObserver::<MockHttpClient>::new(&WledConfiguration {
+            id: "wled".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "http://192.168.1.50".to_string(),
+            brightness: None,
+            states: None,
+        });
+
+        let client = wled.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "http://192.168.1.50/json/state")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        wled.observe(Observation::DuckStatusChanged(status))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(expected, &requests[0].body.clone().unwrap());
+    }
+
+    #[test_case(BuildStatus::Canceled ; "Canceled")]
+    #[test_case(BuildStatus::Unknown ; "Unknown")]
+    fn should_not_change_lights_for_unconfigured_status(status: BuildStatus) {
+        // Given
+        let wled = WledObserver::<MockHttpClient>::new(&WledConfiguration {
+            id: "wled".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "http://192.168.1.50".to_string(),
+            brightness: None,
+            states: None,
+        });
+
+        let client = wled.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "http://192.168.1.50/json/state")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        wled.observe(Observation::DuckStatusChanged(status))
+            .unwrap();
+
+        // Then
+        assert_eq!(0, client.get_sent_requests().len());
+    }
+
+    #[test]
+    fn should_use_configured_state() {
+        // Given
+        let wled = WledObserver::<MockHttpClient>::new(&WledConfiguration {
+            id: "wled".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "http://192.168.1.50".to_string(),
+            brightness: None,
+            states: Some(WledStatesConfiguration {
+                running: Some(WledStateConfiguration {
+                    color: Some("#FF8000".to_string()),
+                    brightness: Some(128),
+                    effect: Some(2),
+                    speed: Some(200),
+                }),
+                ..Default::default()
+            }),
+        });
+
+        let client = wled.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "http://192.168.1.50/json/state")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        wled.observe(Observation::DuckStatusChanged(BuildStatus::Running))
+            .unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!(
+            "{\"bri\":128,\"on\":true,\"seg\":[{\"col\":[[255,128,0]],\"fx\":2,\"sx\":200}]}",
+            &requests[0].body.clone().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_turn_off_lights_when_shutting_down() {
+        // Given
+        let wled = WledObserver::<MockHttpClient>::new(&WledConfiguration {
+            id: "wled".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "http://192.168.1.50".to_string(),
+            brightness: None,
+            states: None,
+        });
+
+        let client = wled.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "http://192.168.1.50/json/state")
+                .returns_status(StatusCode::OK),
+        );
+
+        // When
+        wled.observe(Observation::ShuttingDown).unwrap();
+
+        // Then
+        let requests = client.get_sent_requests();
+        assert_eq!(1, requests.len());
+        assert_eq!("{\"on\":false}", &requests[0].body.clone().unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not update WLED state (500 Internal Server Error)")]
+    fn should_return_error_if_server_return_non_successful_http_status_code() {
+        // Given
+        let wled = WledObserver::<MockHttpClient>::new(&WledConfiguration {
+            id: "wled".to_string(),
+            enabled: Some(true),
+            collectors: None,
+            view: None,
+            pull_requests: None,
+            url: "http://192.168.1.50".to_string(),
+            brightness: None,
+            states: None,
+        });
+
+        let client = wled.get_client();
+        client.add_response(
+            MockHttpResponseBuilder::new(HttpMethod::Post, "http://192.168.1.50/json/state")
+                .returns_status(StatusCode::INTERNAL_SERVER_ERROR),
+        );
+
+        // When, Then
+        wled.observe(Observation::DuckStatusChanged(BuildStatus::Success))
+            .unwrap();
+    }
+}
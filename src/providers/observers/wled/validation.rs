@@ -0,0 +1,121 @@
+use url::Url;
+
+use crate::config::{Validate, WledConfiguration, WledStateConfiguration};
+use crate::utils::{colors::Rgb, DuckResult};
+
+impl Validate for WledConfiguration {
+    fn validate(&self) -> DuckResult<()> {
+        if self.id.is_empty() {
+            return Err(format_err!("WLED ID is empty."));
+        }
+        if let Err(e) = Url::parse(&self.url[..]) {
+            return Err(format_err!("WLED URL is invalid: {}", e));
+        }
+        if let Some(states) = &self.states {
+            validate_state("success", &states.success)?;
+            validate_state("failed", &states.failed)?;
+            validate_state("running", &states.running)?;
+            validate_state("canceled", &states.canceled)?;
+            validate_state("unknown", &states.unknown)?;
+        }
+        Ok(())
+    }
+}
+
+fn validate_state(name: &str, state: &Option<WledStateConfiguration>) -> DuckResult<()> {
+    if let Some(state) = state {
+        if let Some(color) = &state.color {
+            if Rgb::parse(color).is_none() {
+                return Err(format_err!(
+                    "WLED {} color '{}' is not a valid hex color.",
+                    name,
+                    color
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Configuration;
+    use crate::providers::DuckProviderCollection;
+    use crate::utils::text::TestVariableProvider;
+
+    #[test]
+    #[should_panic(expected = "The id '' is invalid.")]
+    fn should_return_error_if_wled_id_is_empty() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "wled": {
+                            "id": "",
+                            "url": "http://192.168.1.50"
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "WLED URL is invalid: relative URL without a base")]
+    fn should_return_error_if_wled_url_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "wled": {
+                            "id": "wled",
+                            "url": ""
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "WLED failed color 'red' is not a valid hex color.")]
+    fn should_return_error_if_wled_state_color_is_invalid() {
+        let config = Configuration::from_json(
+            &TestVariableProvider::new(),
+            r#"
+            { 
+                "collectors": [ ],
+                "observers": [
+                    {
+                        "wled": {
+                            "id": "wled",
+                            "url": "http://192.168.1.50",
+                            "states": { "failed": { "color": "red" } }
+                        }
+                    }
+                ]
+            }
+        "#,
+        )
+        .unwrap();
+
+        let collection = DuckProviderCollection::new();
+        collection.get_observers(&config).unwrap();
+    }
+}
@@ -1,3 +1,6 @@
+use crate::builds::BuildStatus;
+
+#[derive(Debug, PartialEq)]
 pub struct Rgb {
     red: u8,
     green: u8,
@@ -9,6 +12,37 @@ impl Rgb {
         Rgb { red, green, blue }
     }
 
+    // Parses a color from a hex value such as #FF8000.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim_start_matches('#');
+        if value.len() != 6 || !value.is_ascii() {
+            return None;
+        }
+        let red = u8::from_str_radix(&value[0..2], 16).ok()?;
+        let green = u8::from_str_radix(&value[2..4], 16).ok()?;
+        let blue = u8::from_str_radix(&value[4..6], 16).ok()?;
+        Some(Rgb::new(red, green, blue))
+    }
+
+    // Gets the default color used by light observers for a build status.
+    pub fn for_status(status: &BuildStatus) -> Option<Self> {
+        match status {
+            BuildStatus::Success => Some(Rgb::new(0, 255, 0)),
+            BuildStatus::Failed => Some(Rgb::new(255, 0, 0)),
+            BuildStatus::Running | BuildStatus::Queued => Some(Rgb::new(127, 200, 255)),
+            BuildStatus::Blocked => Some(Rgb::new(255, 191, 0)),
+            _ => None,
+        }
+    }
+
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+
+    pub fn to_array(&self) -> [u8; 3] {
+        [self.red, self.green, self.blue]
+    }
+
     // Converts a RGB color to coordinates in the CIE color space.
     // https://developers.meethue.com/develop/application-design-guidance/color-conversion-formulas-rgb-to-xy-and-back/
     pub fn to_cie_coordinates(&self) -> (f32, f32) {
@@ -42,3 +76,22 @@ impl Rgb {
         return (cx, cy);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("#FF8000", Some(Rgb::new(255, 128, 0)) ; "With hash")]
+    #[test_case("00ff7f", Some(Rgb::new(0, 255, 127)) ; "Without hash")]
+    #[test_case("#FF80", None ; "Too short")]
+    #[test_case("#GG0000", None ; "Not hex")]
+    fn should_parse_hex_color(value: &str, expected: Option<Rgb>) {
+        assert_eq!(expected, Rgb::parse(value));
+    }
+
+    #[test]
+    fn should_format_hex_color() {
+        assert_eq!("#ff8000", Rgb::new(255, 128, 0).to_hex());
+    }
+}
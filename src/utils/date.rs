@@ -5,6 +5,7 @@ pub static TEAMCITY_FORMAT: &str = "%Y%m%dT%H%M%S%z";
 pub static AZURE_DEVOPS_FORMAT: &str = "%+";
 pub static GITHUB_FORMAT: &str = "%+";
 pub static OCTOPUS_DEPLOY_FORMAT: &str = "%+";
+pub static ARGOCD_FORMAT: &str = "%+";
 
 pub fn to_timestamp(input: &str, pattern: &str) -> DuckResult<i64> {
     match DateTime::parse_from_str(input, pattern) {
@@ -40,4 +41,10 @@ mod tests {
         let result = to_timestamp("2020-02-01T20:43:16Z", GITHUB_FORMAT).unwrap();
         assert_eq!(1580589796, result);
     }
+
+    #[test]
+    fn should_parse_argocd_format() {
+        let result = to_timestamp("2020-02-01T20:43:16Z", ARGOCD_FORMAT).unwrap();
+        assert_eq!(1580589796, result);
+    }
 }
@@ -0,0 +1,51 @@
+use regex::Regex;
+
+/// A glob pattern where `*` matches any number of
+/// characters and `?` matches a single character.
+#[derive(Clone, Debug)]
+pub struct Glob {
+    regex: Regex,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Self {
+        let mut expression = String::from("^");
+        for c in pattern.chars() {
+            match c {
+                '*' => expression.push_str(".*"),
+                '?' => expression.push('.'),
+                c => expression.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        expression.push('$');
+
+        // Everything except the wildcards has been
+        // escaped, so the expression is always valid.
+        Glob {
+            regex: Regex::new(&expression).unwrap(),
+        }
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("master", "master", true ; "Exact match")]
+    #[test_case("master", "master2", false ; "No partial match")]
+    #[test_case("feature/*", "feature/GH-30", true ; "Star")]
+    #[test_case("feature/*", "bugfix/GH-30", false ; "Star without match")]
+    #[test_case("*", "", true ; "Star matches empty")]
+    #[test_case("release-?", "release-1", true ; "Question mark")]
+    #[test_case("release-?", "release-10", false ; "Question mark is a single character")]
+    #[test_case("Duck (*)", "Duck (Web)", true ; "Regex characters are escaped")]
+    #[test_case("Duck.*", "Duck-Web", false ; "Dots are escaped")]
+    fn should_match_glob(pattern: &str, text: &str, expected: bool) {
+        assert_eq!(expected, Glob::new(pattern).is_match(text));
+    }
+}
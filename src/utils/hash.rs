@@ -0,0 +1,57 @@
+use sha2::{Digest, Sha256};
+
+/// The largest integer a JavaScript number can represent exactly.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// Calculates a hash for the fields that is stable across
+/// platforms, Rust versions and Duck releases.
+///
+/// Every field is written as its length in bytes (as a big endian `u32`)
+/// followed by its UTF-8 bytes, and the result is the first eight bytes of
+/// the SHA-256 digest as a big endian integer, truncated to 53 bits so that
+/// it can be used as a number in JSON without losing precision.
+pub fn stable_hash(fields: &[&str]) -> u64 {
+    let mut hasher = Sha256::new();
+    for field in fields {
+        hasher.input((field.len() as u32).to_be_bytes());
+        hasher.input(field.as_bytes());
+    }
+
+    let digest = hasher.result();
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes) & MAX_SAFE_INTEGER
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_return_same_hash_for_same_fields() {
+        // Given, When
+        let hash = stable_hash(&["TeamCity", "teamcity", "", "Duck", "Duck_Build", "master"]);
+
+        // Then
+        assert_eq!(2_836_175_146_701_137, hash);
+    }
+
+    #[test]
+    fn should_not_be_ambiguous_when_fields_are_concatenated() {
+        // Given, When
+        let first = stable_hash(&["ab", "c"]);
+        let second = stable_hash(&["a", "bc"]);
+
+        // Then
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn should_fit_in_a_javascript_number() {
+        // Given, When
+        let hash = stable_hash(&["foo"]);
+
+        // Then
+        assert!(hash <= MAX_SAFE_INTEGER);
+    }
+}
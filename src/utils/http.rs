@@ -232,6 +232,7 @@ pub struct MockHttpResponseBuilder {
     pub method: HttpMethod,
     pub status: Option<StatusCode>,
     pub body: Option<String>,
+    pub headers: reqwest::header::HeaderMap,
 }
 
 #[cfg(test)]
@@ -242,6 +243,7 @@ impl MockHttpResponseBuilder {
             method,
             status: Some(StatusCode::OK),
             body: None,
+            headers: reqwest::header::HeaderMap::new(),
         }
     }
 
@@ -255,6 +257,11 @@ impl MockHttpResponseBuilder {
         self
     }
 
+    pub fn returns_header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers.insert(name, value.parse().unwrap());
+        self
+    }
+
     pub fn build(self) -> DuckResult<MockHttpResponse> {
         if self.status.is_none() {
             return Err(format_err!("Status is not setup for expectation."));
@@ -264,7 +271,7 @@ impl MockHttpResponseBuilder {
             method: self.method,
             status: self.status.unwrap(),
             body: self.body,
-            headers: reqwest::header::HeaderMap::new(),
+            headers: self.headers,
         })
     }
 }
@@ -2,7 +2,10 @@ use failure::Error;
 
 pub mod colors;
 pub mod date;
+pub mod glob;
+pub mod hash;
 pub mod http;
+pub mod process;
 pub mod text;
 
 pub type DuckResult<T> = Result<T, Error>;
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use log::trace;
+use waithandle::{EventWaitHandle, WaitHandle};
+
+use crate::utils::DuckResult;
+
+pub struct ProcessRequest<'a> {
+    pub program: &'a str,
+    pub arguments: &'a [String],
+    pub working_directory: Option<&'a str>,
+    pub environment: Option<&'a HashMap<String, String>>,
+    pub stdin: Option<String>,
+    pub timeout: Duration,
+    pub cancellation: Option<&'a EventWaitHandle>,
+}
+
+pub struct ProcessOutput {
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub timed_out: bool,
+    pub cancelled: bool,
+}
+
+impl ProcessOutput {
+    pub fn is_success(&self) -> bool {
+        !self.timed_out && !self.cancelled && self.exit_code == Some(0)
+    }
+}
+
+/// Runs a process to completion and captures its standard output.
+/// The process is killed if it has not exited within the timeout,
+/// or if the cancellation handle is signaled.
+pub fn run(request: &ProcessRequest) -> DuckResult<ProcessOutput> {
+    trace!("Running process: {}", request.program);
+    let mut command = Command::new(request.program);
+    command
+        .args(request.arguments)
+        .stdin(if request.stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    if let Some(directory) = request.working_directory {
+        command.current_dir(directory);
+    }
+    if let Some(environment) = request.environment {
+        command.envs(environment);
+    }
+
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => return Err(format_err!("Could not start '{}': {}", request.program, e)),
+    };
+
+    // Write to stdin from a separate thread so that we
+    // don't block on processes that never read it.
+    if let (Some(input), Some(mut stdin)) = (request.stdin.clone(), child.stdin.take()) {
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        });
+    }
+
+    // Read stdout from a separate thread so that the
+    // process doesn't block on a full pipe.
+    let mut stdout = child.stdout.take().unwrap();
+    let reader = std::thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stdout.read_to_string(&mut output);
+        output
+    });
+
+    let started = Instant::now();
+    let mut timed_out = false;
+    let mut cancelled = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if started.elapsed() >= request.timeout {
+            trace!("Process '{}' timed out.", request.program);
+            child.kill()?;
+            child.wait()?;
+            timed_out = true;
+            break None;
+        }
+        match request.cancellation {
+            Some(handle) => {
+                if handle.wait(Duration::from_millis(50))? {
+                    trace!("Process '{}' was cancelled.", request.program);
+                    child.kill()?;
+                    child.wait()?;
+                    cancelled = true;
+                    break None;
+                }
+            }
+            None => std::thread::sleep(Duration::from_millis(50)),
+        }
+    };
+
+    // Processes spawned by a killed process might still hold on
+    // to stdout, so don't wait for any output if we killed it.
+    let stdout = if timed_out || cancelled {
+        String::new()
+    } else {
+        reader.join().unwrap_or_default()
+    };
+
+    Ok(ProcessOutput {
+        exit_code: status.and_then(|s| s.code()),
+        stdout,
+        timed_out,
+        cancelled,
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> Vec<String> {
+        vec!["-c".to_string(), script.to_string()]
+    }
+
+    #[test]
+    fn should_capture_exit_code_and_output() {
+        let arguments = shell("echo hello; exit 3");
+        let result = run(&ProcessRequest {
+            program: "sh",
+            arguments: &arguments,
+            working_directory: None,
+            environment: None,
+            stdin: None,
+            timeout: Duration::from_secs(5),
+            cancellation: None,
+        })
+        .unwrap();
+
+        assert_eq!(Some(3), result.exit_code);
+        assert_eq!("hello\n", result.stdout);
+        assert!(!result.timed_out);
+        assert!(!result.is_success());
+    }
+
+    #[test]
+    fn should_pass_environment_and_stdin() {
+        let arguments = shell("echo $FOO; cat");
+        let mut environment = HashMap::new();
+        environment.insert("FOO".to_string(), "bar".to_string());
+        let result = run(&ProcessRequest {
+            program: "sh",
+            arguments: &arguments,
+            working_directory: None,
+            environment: Some(&environment),
+            stdin: Some("baz".to_string()),
+            timeout: Duration::from_secs(5),
+            cancellation: None,
+        })
+        .unwrap();
+
+        assert!(result.is_success());
+        assert_eq!("bar\nbaz", result.stdout);
+    }
+
+    #[test]
+    fn should_kill_process_that_times_out() {
+        let arguments = shell("sleep 10");
+        let result = run(&ProcessRequest {
+            program: "sh",
+            arguments: &arguments,
+            working_directory: None,
+            environment: None,
+            stdin: None,
+            timeout: Duration::from_millis(200),
+            cancellation: None,
+        })
+        .unwrap();
+
+        assert!(result.timed_out);
+        assert_eq!(None, result.exit_code);
+    }
+
+    #[test]
+    fn should_kill_process_that_is_cancelled() {
+        let arguments = shell("sleep 10");
+        let handle = EventWaitHandle::new();
+        handle.signal().unwrap();
+        let result = run(&ProcessRequest {
+            program: "sh",
+            arguments: &arguments,
+            working_directory: None,
+            environment: None,
+            stdin: None,
+            timeout: Duration::from_secs(5),
+            cancellation: Some(&handle),
+        })
+        .unwrap();
+
+        assert!(result.cancelled);
+        assert!(!result.timed_out);
+        assert_eq!(None, result.exit_code);
+    }
+}
@@ -11,6 +11,11 @@ import { faExclamationTriangle } from '@fortawesome/free-solid-svg-icons'
 import { faCodeBranch } from '@fortawesome/free-solid-svg-icons'
 import { faTools } from '@fortawesome/free-solid-svg-icons'
 import { faTimesCircle } from '@fortawesome/free-solid-svg-icons'
+import { faHourglassHalf } from '@fortawesome/free-solid-svg-icons'
+import { faForward } from '@fortawesome/free-solid-svg-icons'
+import { faHandPaper } from '@fortawesome/free-solid-svg-icons'
+import { faEye } from '@fortawesome/free-solid-svg-icons'
+import { faBellSlash } from '@fortawesome/free-solid-svg-icons'
 import { FontAwesomeIcon } from '@fortawesome/vue-fontawesome'
 
 const options = {
@@ -32,6 +37,11 @@ library.add(faExclamationTriangle)
 library.add(faCodeBranch)
 library.add(faTools)
 library.add(faTimesCircle)
+library.add(faHourglassHalf)
+library.add(faForward)
+library.add(faHandPaper)
+library.add(faEye)
+library.add(faBellSlash)
 Vue.component('font-awesome-icon', FontAwesomeIcon)
 
 Vue.component('vue-headful', vueHeadful);